@@ -45,7 +45,7 @@ fn main() -> NcResult<()> {
     let selplane: &mut NcPlane = NcPlane::new_child(stdplane, &planeopts)?;
 
     // Create selector
-    let selector = NcSelector::builder()
+    let mut selector = NcSelector::builder()
         .item("Afrikaans", "Ek kan glas eet, dit maak my nie seer nie.")
         .item("AngloSax", "ᛁᚳ᛫ᛗᚨᚷ᛫ᚷᛚᚨᛋ᛫ᛖᚩᛏᚪᚾ᛫ᚩᚾᛞ᛫ᚻᛁᛏ᛫ᚾᛖ᛫ᚻᛖᚪᚱᛗᛁᚪᚧ᛫ᛗᛖ᛬")
         .item(
@@ -87,7 +87,7 @@ fn main() -> NcResult<()> {
     )?;
 
     // Render loop
-    let selected: String = run_selector(nc, selector)?;
+    let selected: String = run_selector(nc, &mut selector)?;
 
     // Destroy ressources
     selector.destroy()?;
@@ -102,7 +102,7 @@ fn main() -> NcResult<()> {
 }
 
 /// Helper to avoid having a render loop in the main function
-fn run_selector(nc: &mut Nc, selector: &mut NcSelector) -> NcResult<String> {
+fn run_selector(nc: &mut Nc, selector: &mut NcItemSelector) -> NcResult<String> {
     // Allocate input placeholder
     let mut ni: NcInput = NcInput::new_empty();
 
@@ -129,19 +129,19 @@ fn run_selector(nc: &mut Nc, selector: &mut NcSelector) -> NcResult<String> {
                         }
                         // J => down
                         'j' | 'J' => {
-                            selector.nextitem()?;
+                            selector.selector().nextitem()?;
                         }
                         // K => up
                         'k' | 'K' => {
-                            selector.previtem()?;
+                            selector.selector().previtem()?;
                         }
                         // Tab => up or down depending if shift is pressed
                         '\u{0009}' => match ni.shift {
                             true => {
-                                selector.previtem()?;
+                                selector.selector().previtem()?;
                             }
                             false => {
-                                selector.nextitem()?;
+                                selector.selector().nextitem()?;
                             }
                         },
                         _ => (),
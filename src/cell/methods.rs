@@ -1,5 +1,7 @@
 //! `NcCell` methods and associated functions.
 
+use core::ptr::null_mut;
+
 use crate::{
     c_api::{self, nccell_load, NcChannels_u64, NCRESULT_ERR},
     cstring, error, rstring, NcAlpha, NcCell, NcChannel, NcChannels, NcError, NcPaletteIndex,
@@ -388,7 +390,28 @@ impl NcCell {
         c_api::nccell_cols(self)
     }
 
-    // TODO: add ncstrwidth associated method
+    /// Returns the number of columns occupied by the string of `egcs`.
+    ///
+    /// It is an error if an invalid character is found. See
+    /// [`strwidth_valid`][NcCell#method.strwidth_valid] for a lenient version.
+    ///
+    /// *C style function: [ncstrwidth()][c_api::ncstrwidth].*
+    pub fn strwidth(egcs: &str) -> NcResult<u32> {
+        let cs = cstring![egcs];
+        let res = unsafe { c_api::ncstrwidth(cs.as_ptr(), null_mut(), null_mut()) };
+        error![res, &format!("NcCell::strwidth({:?})", egcs), res as u32]
+    }
+
+    /// Returns the number of bytes and columns occupied by the longest valid
+    /// prefix of the string of `egcs`, as a tuple `(bytes, columns)`.
+    ///
+    /// *C style function: [ncstrwidth()][c_api::ncstrwidth].*
+    pub fn strwidth_valid(egcs: &str) -> (u32, u32) {
+        let (mut validbytes, mut validwidth) = (0, 0);
+        let cs = cstring![egcs];
+        unsafe { c_api::ncstrwidth(cs.as_ptr(), &mut validbytes, &mut validwidth) };
+        (validbytes as u32, validwidth as u32)
+    }
 
     /// Returns a pointer to the `EGC` of this NcCell in the `plane`.
     ///
@@ -2,14 +2,14 @@
 
 // functions already exported by bindgen : 5
 // -----------------------------------------
-// (W) wrap: 4
+// (W) wrap: 5
 // (#) test: 3
 // ------------------------------------------
 //W  nccell_extended_gcluster
 //W# nccell_load
 //W# nccell_duplicate
 //W# nccell_release
-//   ncstrwidth_valid
+//W  ncstrwidth
 //
 // functions manually reimplemented: 50
 // ------------------------------------------
@@ -0,0 +1,103 @@
+//! `NcDirectF`
+
+use core::ptr::NonNull;
+
+#[cfg(not(feature = "std"))]
+use alloc::format;
+
+use crate::{
+    c_api, cstring, error, error_ref_mut, NcDirect, NcError, NcPlane, NcResult, NcVisual,
+    NcVisualGeometry, NcVisualOptions,
+};
+
+/// An owned media frame loaded from disk for rendering in [`NcDirect`] mode.
+///
+/// Loading the frame once allows to query its geometry with [`geom`], and to
+/// [`render`] it several times in different ways, without reopening the file.
+///
+/// Unlike most of the types in this library, the frame is automatically freed
+/// with [`ncdirectf_free`] when it goes out of scope.
+///
+/// `type in C: ncdirectf (struct)`
+///
+/// [`geom`]: NcDirectF#method.geom
+/// [`render`]: NcDirectF#method.render
+/// [`ncdirectf_free`]: c_api::ncdirectf_free
+#[derive(Debug)]
+pub struct NcDirectF {
+    frame: NonNull<NcVisual>,
+}
+
+/// # `NcDirectF` constructors
+impl NcDirectF {
+    /// Loads media from `path`, but doesn't render it yet.
+    ///
+    /// *C style function: [ncdirectf_from_file()][c_api::ncdirectf_from_file].*
+    pub fn open(ncd: &mut NcDirect, path: &str) -> NcResult<NcDirectF> {
+        let cs = cstring![path];
+        let frame = unsafe { c_api::ncdirectf_from_file(ncd, cs.as_ptr()) };
+        match NonNull::new(frame) {
+            Some(frame) => Ok(Self { frame }),
+            None => Err(NcError::with_msg(
+                c_api::NCRESULT_ERR,
+                &format!("NcDirectF::open(ncd, {})", path),
+            )),
+        }
+    }
+}
+
+/// # `NcDirectF` methods
+impl NcDirectF {
+    /// Returns the geometry of a potential render of this frame.
+    ///
+    /// *C style function: [ncdirectf_geom()][c_api::ncdirectf_geom].*
+    pub fn geom(
+        &mut self,
+        ncd: &mut NcDirect,
+        options: &NcVisualOptions,
+    ) -> NcResult<NcVisualGeometry> {
+        let mut geom = c_api::NcVGeom::new();
+        let res = unsafe { c_api::ncdirectf_geom(ncd, self.frame.as_ptr(), options, &mut geom) };
+        error![res, "NcDirectF.geom()", geom.into()];
+    }
+
+    /// Renders this frame, but doesn't write the result.
+    ///
+    /// The returned plane must be written (and freed) with
+    /// [`NcDirect.raster_frame`].
+    ///
+    /// The frame is not consumed and may be rendered again.
+    ///
+    /// *C style function: [ncdirectf_render()][c_api::ncdirectf_render].*
+    ///
+    /// [`NcDirect.raster_frame`]: NcDirect#method.raster_frame
+    pub fn render<'a>(
+        &mut self,
+        ncd: &mut NcDirect,
+        options: &NcVisualOptions,
+    ) -> NcResult<&'a mut NcPlane> {
+        error_ref_mut![
+            unsafe { c_api::ncdirectf_render(ncd, self.frame.as_ptr(), options) },
+            "NcDirectF.render()"
+        ]
+    }
+
+    /// Returns a reference to the inner [`NcVisual`].
+    pub fn as_visual(&self) -> &NcVisual {
+        unsafe { self.frame.as_ref() }
+    }
+
+    /// Returns a mutable reference to the inner [`NcVisual`].
+    pub fn as_visual_mut(&mut self) -> &mut NcVisual {
+        unsafe { self.frame.as_mut() }
+    }
+}
+
+impl Drop for NcDirectF {
+    /// Frees the loaded frame.
+    ///
+    /// *C style function: [ncdirectf_free()][c_api::ncdirectf_free].*
+    fn drop(&mut self) {
+        unsafe { c_api::ncdirectf_free(self.frame.as_ptr()) };
+    }
+}
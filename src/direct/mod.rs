@@ -81,9 +81,12 @@
 #[cfg(test)]
 mod test;
 
+mod directf;
 mod methods;
 pub(crate) mod reimplemented;
 
+pub use directf::NcDirectF;
+
 use c_api::NcDirectFlag_u64;
 
 /// Minimal notcurses instance for styling text.
@@ -0,0 +1,293 @@
+//! `NcGradient`
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::{math, NcColorSpace, NcResult, NcRgba, NcVisual};
+
+/// A gradient of any number of color stops.
+///
+/// Unlike [`NcPlane.gradient`] which interpolates between exactly four corner
+/// channels, an `NcGradient` can have arbitrary stops, be [linear] at any
+/// angle, [radial] or [conic], and interpolate in any [`NcColorSpace`].
+///
+/// It can fill a plane region with [`NcPlane.gradient_fill`], or be
+/// rasterized into an [`NcVisual`] with [`to_visual`] for blitting it.
+///
+/// # Example
+/// ```
+/// # use libnotcurses_sys::*;
+/// let g = NcGradient::linear(45.0)
+///     .stop(0.0, [0xFF, 0, 0, 0xFF])
+///     .stop(0.5, [0, 0xFF, 0, 0xFF])
+///     .stop(1.0, [0, 0, 0xFF, 0xFF]);
+/// assert_eq![g.color_at(0.5), NcRgba::from([0, 0xFF, 0, 0xFF])];
+/// ```
+///
+/// [`NcPlane.gradient`]: crate::NcPlane#method.gradient
+/// [`NcPlane.gradient_fill`]: crate::NcPlane#method.gradient_fill
+/// [linear]: NcGradient#method.linear
+/// [radial]: NcGradient#method.radial
+/// [conic]: NcGradient#method.conic
+/// [`to_visual`]: NcGradient#method.to_visual
+#[derive(Clone, Debug, PartialEq)]
+pub struct NcGradient {
+    stops: Vec<(f32, NcRgba)>,
+    kind: NcGradientKind,
+    space: NcColorSpace,
+}
+
+/// The shape of an [`NcGradient`].
+///
+/// Angles are in degrees, clockwise, with `0.0` pointing to the right.
+/// Centers are relative to the filled area, from `0.0` to `1.0`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NcGradientKind {
+    /// Colors change along a straight line in the direction of `angle`.
+    Linear {
+        /// The direction of the gradient.
+        angle: f32,
+    },
+    /// Colors change with the distance from the center, up to the farthest
+    /// corner.
+    Radial {
+        /// The vertical position of the center.
+        center_y: f32,
+        /// The horizontal position of the center.
+        center_x: f32,
+    },
+    /// Colors change around the center, starting at `angle`.
+    Conic {
+        /// The vertical position of the center.
+        center_y: f32,
+        /// The horizontal position of the center.
+        center_x: f32,
+        /// The angle of the first stop.
+        angle: f32,
+    },
+}
+
+/// The channels of a plane filled by an [`NcGradient`].
+///
+/// # Default
+/// *[`NcGradientTarget::Background`]*
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NcGradientTarget {
+    /// Only the foreground channel.
+    Foreground,
+    /// Only the background channel.
+    #[default]
+    Background,
+    /// Both the foreground and background channels.
+    Both,
+}
+
+/// # `NcGradient` constructors
+impl NcGradient {
+    /// New linear `NcGradient` in the direction of `angle` degrees.
+    ///
+    /// `0.0` goes from left to right, and `90.0` from top to bottom.
+    pub fn linear(angle: f32) -> Self {
+        Self::with_kind(NcGradientKind::Linear { angle })
+    }
+
+    /// New radial `NcGradient` centered at `center_y`, `center_x`.
+    pub fn radial(center_y: f32, center_x: f32) -> Self {
+        Self::with_kind(NcGradientKind::Radial { center_y, center_x })
+    }
+
+    /// New conic `NcGradient` centered at `center_y`, `center_x`, with the
+    /// first stop at `angle` degrees.
+    pub fn conic(center_y: f32, center_x: f32, angle: f32) -> Self {
+        Self::with_kind(NcGradientKind::Conic { center_y, center_x, angle })
+    }
+
+    /// New `NcGradient` of the given `kind`, without stops, interpolated in
+    /// the default color space.
+    pub fn with_kind(kind: NcGradientKind) -> Self {
+        Self { stops: Vec::new(), kind, space: NcColorSpace::default() }
+    }
+
+    /// Adds a color stop at `position`, clamped between `0.0` and `1.0`.
+    ///
+    /// Stops at the same position make a sharp transition.
+    pub fn stop(mut self, position: f32, color: impl Into<NcRgba>) -> Self {
+        self.add_stop(position, color);
+        self
+    }
+
+    /// Sets the color space where the colors are interpolated.
+    pub fn space(mut self, space: NcColorSpace) -> Self {
+        self.space = space;
+        self
+    }
+}
+
+/// # `NcGradient` methods
+impl NcGradient {
+    /// Adds a color stop at `position`, clamped between `0.0` and `1.0`.
+    pub fn add_stop(&mut self, position: f32, color: impl Into<NcRgba>) {
+        let position = if position.is_nan() { 0.0 } else { position.clamp(0.0, 1.0) };
+        let index = self
+            .stops
+            .iter()
+            .take_while(|(p, _)| *p <= position)
+            .count();
+        self.stops.insert(index, (position, color.into()));
+    }
+
+    /// Returns the color stops, sorted by position.
+    pub fn stops(&self) -> &[(f32, NcRgba)] {
+        &self.stops
+    }
+
+    /// Returns the shape of the gradient.
+    pub fn kind(&self) -> NcGradientKind {
+        self.kind
+    }
+
+    /// Returns the color space where the colors are interpolated.
+    pub fn color_space(&self) -> NcColorSpace {
+        self.space
+    }
+
+    /// Returns the color at `t`, between `0.0` and `1.0`.
+    ///
+    /// A gradient without stops is transparent.
+    pub fn color_at(&self, t: f32) -> NcRgba {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let next = self.stops.iter().position(|(p, _)| *p > t);
+        match next {
+            None => self.stops.last().map_or(NcRgba(0), |s| s.1),
+            Some(0) => self.stops[0].1,
+            Some(i) => {
+                let ((p0, c0), (p1, c1)) = (self.stops[i - 1], self.stops[i]);
+                if t == p0 {
+                    return c0;
+                }
+                c0.lerp_in(c1, (t - p0) / (p1 - p0), self.space)
+            }
+        }
+    }
+
+    /// Returns the position in the gradient of the point at `y`, `x`, in an
+    /// area of `height` by `width`, all in the same units.
+    pub fn position_at(&self, y: f32, x: f32, height: f32, width: f32) -> f32 {
+        let (y, x, h, w) = (y as f64, x as f64, height as f64, width as f64);
+        let t = match self.kind {
+            NcGradientKind::Linear { angle } => {
+                let a = (angle as f64).to_radians();
+                let (dy, dx) = (math::sin(a), math::cos(a));
+                let half = (math::abs(w * dx) + math::abs(h * dy)) / 2.0;
+                if half == 0.0 {
+                    0.0
+                } else {
+                    ((x - w / 2.0) * dx + (y - h / 2.0) * dy + half) / (2.0 * half)
+                }
+            }
+            NcGradientKind::Radial { center_y, center_x } => {
+                let (cy, cx) = (center_y as f64 * h, center_x as f64 * w);
+                let (fy, fx) = (cy.max(h - cy), cx.max(w - cx));
+                let radius = math::sqrt(fy * fy + fx * fx);
+                if radius == 0.0 {
+                    0.0
+                } else {
+                    math::sqrt((y - cy) * (y - cy) + (x - cx) * (x - cx)) / radius
+                }
+            }
+            NcGradientKind::Conic { center_y, center_x, angle } => {
+                let (cy, cx) = (center_y as f64 * h, center_x as f64 * w);
+                let a = math::atan2(y - cy, x - cx) - (angle as f64).to_radians();
+                (a - math::TAU * math::floor(a / math::TAU)) / math::TAU
+            }
+        };
+        t as f32
+    }
+
+    /// Returns the color of the point at `y`, `x`, in an area of `height` by
+    /// `width`, all in the same units.
+    pub fn sample(&self, y: f32, x: f32, height: f32, width: f32) -> NcRgba {
+        self.color_at(self.position_at(y, x, height, width))
+    }
+
+    /// Rasterizes the gradient into `rows` by `cols` square pixels,
+    /// as a buffer of 8bpc RGBA pixels with no padding.
+    pub fn to_rgba(&self, rows: u32, cols: u32) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(rows as usize * cols as usize * 4);
+        for y in 0..rows {
+            for x in 0..cols {
+                let color = self.sample(y as f32 + 0.5, x as f32 + 0.5, rows as f32, cols as f32);
+                buf.extend_from_slice(&<[u8; 4]>::from(color));
+            }
+        }
+        buf
+    }
+
+    /// Rasterizes the gradient into a new [`NcVisual`] of `rows` by `cols`
+    /// pixels.
+    ///
+    /// The visual must be destroyed with [`NcVisual.destroy`].
+    ///
+    /// [`NcVisual.destroy`]: NcVisual#method.destroy
+    pub fn to_visual<'a>(&self, rows: u32, cols: u32) -> NcResult<&'a mut NcVisual> {
+        NcVisual::from_rgba(&self.to_rgba(rows, cols), rows, cols * 4, cols)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const RED: [u8; 4] = [0xFF, 0, 0, 0xFF];
+    const BLUE: [u8; 4] = [0, 0, 0xFF, 0xFF];
+
+    #[test]
+    fn stops() {
+        let g = NcGradient::linear(0.0)
+            .stop(1.0, BLUE)
+            .stop(0.0, RED)
+            .stop(2.0, RED);
+        let positions: Vec<f32> = g.stops().iter().map(|s| s.0).collect();
+        assert_eq![positions, [0.0, 1.0, 1.0]];
+
+        assert_eq![NcGradient::linear(0.0).color_at(0.5), NcRgba(0)];
+        assert_eq![g.color_at(-1.0), RED.into()];
+        assert_eq![g.color_at(1.0), RED.into()];
+
+        let g = g.space(NcColorSpace::Srgb);
+        let (r, g_, b, a) = g.color_at(0.5).into();
+        assert_eq![(r, g_, b, a), (0x80, 0, 0x80, 0xFF)];
+    }
+
+    #[test]
+    fn shapes() {
+        let close = |a: f32, b: f32| math::abs((a - b) as f64) < 1e-4;
+
+        let g = NcGradient::linear(0.0);
+        assert!(close(g.position_at(5.0, 0.0, 10.0, 20.0), 0.0));
+        assert!(close(g.position_at(5.0, 10.0, 10.0, 20.0), 0.5));
+        let g = NcGradient::linear(90.0);
+        assert!(close(g.position_at(10.0, 3.0, 10.0, 20.0), 1.0));
+        let g = NcGradient::linear(45.0);
+        assert!(close(g.position_at(0.0, 0.0, 10.0, 10.0), 0.0));
+        assert!(close(g.position_at(10.0, 10.0, 10.0, 10.0), 1.0));
+
+        let g = NcGradient::radial(0.5, 0.5);
+        assert!(close(g.position_at(5.0, 5.0, 10.0, 10.0), 0.0));
+        assert!(close(g.position_at(0.0, 10.0, 10.0, 10.0), 1.0));
+
+        let g = NcGradient::conic(0.5, 0.5, 0.0);
+        assert!(close(g.position_at(5.0, 10.0, 10.0, 10.0), 0.0));
+        assert!(close(g.position_at(10.0, 5.0, 10.0, 10.0), 0.25));
+        assert!(close(g.position_at(5.0, 0.0, 10.0, 10.0), 0.5));
+    }
+
+    #[test]
+    fn rasterize() {
+        let g = NcGradient::linear(0.0).stop(0.0, RED).stop(1.0, BLUE);
+        let buf = g.to_rgba(2, 3);
+        assert_eq![buf.len(), 2 * 3 * 4];
+        assert_eq![buf[..4], buf[12..16]];
+        assert!(buf[0] > buf[2] && buf[8] < buf[10]);
+    }
+}
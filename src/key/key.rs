@@ -212,6 +212,8 @@ impl NcKey {
     /// Alias of [`Button4`][NcKey::Button4]
     pub const ScrollUp: NcKey = NcKey(c_api::NCKEY_SCROLL_UP);
     /// Alias of [`Button5`][NcKey::Button5]
+    pub const ScrollDown: NcKey = NcKey(c_api::NCKEY_SCROLL_DOWN);
+    /// Alias of [`Button5`][NcKey::Button5]
     pub const Scrolldown: NcKey = NcKey(c_api::NCKEY_SCROLL_DOWN);
     /// Alias of [`Enter`][NcKey::Enter]
     pub const Return: NcKey = NcKey(c_api::NCKEY_RETURN);
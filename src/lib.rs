@@ -21,7 +21,11 @@
 //! ### Notes on the Rust API
 //!
 //! The `Drop` trait is not implemented for any wrapping type in this library
-//! over structures created by the underlying C library.
+//! over structures created by the underlying C library, with the exception of
+//! the owning [`NcDirectF`], [`NcPanes`], [`NcPlayer`] and [`NcSprite`] types.
+//! Since stopping the `Nc` context frees every plane, the ones that destroy
+//! planes, [`NcPanes`] and [`NcSprite`], must be dropped before calling its
+//! `stop()` method.
 //!
 //! This means you still have to manually call the `stop()` method for `Nc`
 //! and `NcDirect` objects, and the `destroy()` method for the rest of types that
@@ -91,10 +95,12 @@ mod fade;
 mod fd;
 #[cfg(feature = "std")]
 mod file;
+mod gradient;
 mod input;
 mod key;
 mod log_level;
 mod macros;
+mod math;
 mod metric;
 mod notcurses;
 mod palette;
@@ -106,6 +112,7 @@ mod scale;
 mod stats;
 mod string;
 mod style;
+mod text;
 mod time;
 mod visual;
 
@@ -122,30 +129,40 @@ pub use blitter::NcBlitter;
 pub use capabilities::NcCapabilities;
 pub use cell::NcCell;
 pub use channel::{NcChannel, NcChannels};
-pub use direct::{NcDirect, NcDirectFlag};
+pub use direct::{NcDirect, NcDirectF, NcDirectFlag};
 pub use error::{NcError, NcResult};
 pub use fade::{NcFadeCb, NcFadeCtx};
 pub use fd::{NcFd, NcFdPlane, NcFdPlaneOptions, NcSubproc, NcSubprocOptions};
 #[cfg(feature = "std")]
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "std")))]
 pub use file::NcFile;
+pub use gradient::{NcGradient, NcGradientKind, NcGradientTarget};
 pub use input::{NcInput, NcInputType, NcMiceEvents, NcReceived};
 pub use key::{NcKey, NcKeyMod};
 pub use log_level::NcLogLevel;
 pub use notcurses::{Nc, NcFlag, NcOptions, NcOptionsBuilder};
 pub use palette::{NcPalette, NcPaletteIndex};
 pub use pixel::{NcPixel, NcPixelGeometry, NcPixelImpl};
-pub use plane::{NcPlane, NcPlaneFlag, NcPlaneOptions, NcPlaneOptionsBuilder};
+pub use plane::{
+    NcCellChange, NcCellContent, NcConstraint, NcLayout, NcPanes, NcPlane, NcPlaneCells,
+    NcPlaneCellsIter, NcPlaneDiff, NcPlaneFlag, NcPlaneOptions, NcPlaneOptionsBuilder, NcPlaneRows,
+    NcScrollback, NcSplit,
+};
 pub use r#box::NcBoxMask;
 pub use resizecb::NcResizeCb;
-pub use rgb::{NcRgb, NcRgba};
+pub use rgb::{NcColorSpace, NcHsl, NcHsv, NcLinearRgb, NcOklab, NcRgb, NcRgba};
 pub use scale::NcScale;
 pub use stats::NcStats;
 pub use string::NcString;
 pub use style::NcStyle;
+pub use text::{
+    NcAnsi, NcGraphemes, NcMarkup, NcMarkupSpan, NcTextAlign, NcTextLayout, NcTextLine,
+    NcTextTruncate, NcTextWrap,
+};
 pub use time::NcTime;
 pub use visual::{
-    NcStreamCb, NcVisual, NcVisualFlag, NcVisualGeometry, NcVisualOptions, NcVisualOptionsBuilder,
+    NcCanvas, NcPlayer, NcSprite, NcStreamCb, NcVisual, NcVisualFlag, NcVisualGeometry,
+    NcVisualOptions, NcVisualOptionsBuilder,
 };
 
 pub mod c_api {
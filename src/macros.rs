@@ -158,6 +158,39 @@ macro_rules! putstrln {
     };
 }
 
+/// Wrapper around [`NcPlane.putstr_markup`][NcPlane#method.putstr_markup],
+/// rendering and rasterizing the plane afterwards.
+///
+/// The formatted string is parsed as [`NcMarkup`][crate::NcMarkup].
+///
+/// Returns an `NcResult` with the number of columns advanced,
+/// with newlines counting as 1 column.
+///
+/// # Example
+/// ```
+/// # use libnotcurses_sys::*;
+/// # fn main() -> NcResult<()> {
+/// let nc = unsafe { Nc::new_cli()? };
+/// let splane = unsafe { nc.stdplane() };
+/// splane.set_scrolling(true);
+/// assert_eq![12, markup!(splane, "[b]hello[/] [i]world[/]\n")?];
+/// markup!(splane, "[fg=#ff8800]{}[/]% [u]done[/]\n", 42)?;
+/// # unsafe { nc.stop()? };
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! markup {
+    ($plane:ident, $($args:tt)*) => {
+        {
+            let res = $plane.putstr_markup(&format![$($args)*])?;
+            $plane.render()?;
+            $plane.rasterize()?;
+            Ok(res)
+        }
+    };
+}
+
 // Error Wrappers Macros -------------------------------------------------------
 
 /// Returns an `Ok($ok)`,
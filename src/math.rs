@@ -0,0 +1,45 @@
+//! Floating point helpers usable without `std`.
+//
+// `core` doesn't provide the transcendental functions of `f64`, so these come
+// from `libm`. They are used the same way with and without the `std` feature,
+// in order to get identical results in both cases.
+
+pub(crate) use libm::{atan2, cbrt, cos, fabs as abs, floor, pow as powf, round, sin, sqrt};
+
+pub(crate) const PI: f64 = core::f64::consts::PI;
+pub(crate) const TAU: f64 = 2.0 * PI;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        abs(a - b) < 1e-9 * b.max(1.0)
+    }
+
+    #[test]
+    fn roots_and_powers() {
+        assert!(close(sqrt(2.0), core::f64::consts::SQRT_2));
+        assert!(close(sqrt(1e-10), 1e-5));
+        assert!(close(cbrt(27.0), 3.0));
+        assert!(close(cbrt(-8.0), -2.0));
+        assert!(close(powf(2.0, 10.0), 1024.0));
+        assert!(close(powf(0.5, 2.4), 0.189_464_570_813_799_8));
+    }
+
+    #[test]
+    fn trigonometry() {
+        assert!(close(sin(PI / 6.0), 0.5));
+        assert!(close(cos(PI / 3.0), 0.5));
+        assert!(close(cos(7.0 * PI), -1.0));
+        assert!(close(atan2(-1.0, -1.0), -3.0 * PI / 4.0));
+    }
+
+    #[test]
+    fn rounding() {
+        assert_eq![floor(-1.5), -2.0];
+        assert_eq![floor(2.0), 2.0];
+        assert_eq![round(2.5), 3.0];
+        assert_eq![round(-2.5), -3.0];
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     c_api::{self, NcPixel_u32},
-    NcPixel, NcRgb, NcRgba,
+    NcHsl, NcHsv, NcLinearRgb, NcOklab, NcPixel, NcRgb, NcRgba,
 };
 
 impl NcPixel {
@@ -72,7 +72,7 @@ impl NcPixel {
 
     /// Sets the 8-bit green component of an ABGR pixel.
     pub fn set_g(&mut self, green: u8) {
-        c_api::ncpixel_set_b(self.into(), green)
+        c_api::ncpixel_set_g(self.into(), green)
     }
 
     /// Sets the 8-bit blue component of an ABGR pixel.
@@ -92,6 +92,89 @@ impl NcPixel {
     }
 }
 
+/// # `NcPixel` color spaces, blending & adjustments
+///
+/// These methods work like the equivalent [`NcRgba`] methods.
+impl NcPixel {
+    /// Converts to the HSL color space, returning the alpha apart.
+    pub fn to_hsl(&self) -> (NcHsl, u8) {
+        self.to_rgba().to_hsl()
+    }
+
+    /// Converts from the HSL color space, with the provided `alpha`.
+    pub fn from_hsl(hsl: NcHsl, alpha: u8) -> Self {
+        Self::from_rgba(NcRgba::from_hsl(hsl, alpha))
+    }
+
+    /// Converts to the HSV color space, returning the alpha apart.
+    pub fn to_hsv(&self) -> (NcHsv, u8) {
+        self.to_rgba().to_hsv()
+    }
+
+    /// Converts from the HSV color space, with the provided `alpha`.
+    pub fn from_hsv(hsv: NcHsv, alpha: u8) -> Self {
+        Self::from_rgba(NcRgba::from_hsv(hsv, alpha))
+    }
+
+    /// Converts to the linear-light sRGB color space, returning the alpha apart.
+    pub fn to_linear(&self) -> (NcLinearRgb, u8) {
+        self.to_rgba().to_linear()
+    }
+
+    /// Converts from the linear-light sRGB color space, with the provided `alpha`.
+    pub fn from_linear(linear: NcLinearRgb, alpha: u8) -> Self {
+        Self::from_rgba(NcRgba::from_linear(linear, alpha))
+    }
+
+    /// Converts to the OKLab color space, returning the alpha apart.
+    pub fn to_oklab(&self) -> (NcOklab, u8) {
+        self.to_rgba().to_oklab()
+    }
+
+    /// Converts from the OKLab color space, with the provided `alpha`.
+    pub fn from_oklab(oklab: NcOklab, alpha: u8) -> Self {
+        Self::from_rgba(NcRgba::from_oklab(oklab, alpha))
+    }
+
+    /// Interpolates perceptually between `self` and `other`.
+    ///
+    /// See [`NcRgba::lerp`].
+    pub fn lerp(&self, other: impl Into<NcPixel>, t: f32) -> NcPixel {
+        Self::from_rgba(self.to_rgba().lerp(other.into().to_rgba(), t))
+    }
+
+    /// Composites `self` over the `background` pixel (Porter-Duff *over*).
+    pub fn over(&self, background: impl Into<NcPixel>) -> NcPixel {
+        Self::from_rgba(self.to_rgba().over(background.into().to_rgba()))
+    }
+
+    /// Returns the WCAG contrast ratio between `self` and `other`,
+    /// ignoring the alpha.
+    pub fn contrast_ratio(&self, other: impl Into<NcPixel>) -> f32 {
+        self.to_rgb().contrast_ratio(other.into().to_rgb())
+    }
+
+    /// Returns the pixel with its HSL lightness increased by `amount`.
+    pub fn lighten(&self, amount: f32) -> NcPixel {
+        Self::from_rgba(self.to_rgba().lighten(amount))
+    }
+
+    /// Returns the pixel with its HSL lightness decreased by `amount`.
+    pub fn darken(&self, amount: f32) -> NcPixel {
+        Self::from_rgba(self.to_rgba().darken(amount))
+    }
+
+    /// Returns the pixel with its HSL saturation increased by `amount`.
+    pub fn saturate(&self, amount: f32) -> NcPixel {
+        Self::from_rgba(self.to_rgba().saturate(amount))
+    }
+
+    /// Returns the pixel with its HSL saturation decreased by `amount`.
+    pub fn desaturate(&self, amount: f32) -> NcPixel {
+        Self::from_rgba(self.to_rgba().desaturate(amount))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,5 +185,18 @@ mod tests {
         let abgr: NcPixel = rgba.into();
 
         assert_eq![0x44332211_u32, abgr.into()];
+        assert_eq![rgba, abgr.to_rgba()];
+    }
+
+    #[test]
+    fn color_spaces() {
+        let red = NcPixel::from_rgb(0xFF0000);
+        let (hsl, alpha) = red.to_hsl();
+        assert_eq![(0.0, 1.0, 0.5, 0xFF), (hsl.h, hsl.s, hsl.l, alpha)];
+        assert_eq![red, NcPixel::from_hsl(hsl, alpha)];
+
+        let pixel = NcPixel::from_rgba((0x12, 0x34, 0x56, 0x78));
+        assert_eq![pixel, pixel.lerp(red, 0.0)];
+        assert_eq![red, pixel.lerp(red, 1.0)];
     }
 }
@@ -0,0 +1,249 @@
+//! `NcPlaneCells`
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use crate::{c_api, NcCell, NcChannels, NcMarkupSpan, NcPlane, NcResult, NcStyle};
+
+/// A snapshot of the cells of a region of an [`NcPlane`].
+///
+/// It's returned by [`NcPlane.cells`], and can be iterated by cell, skipping
+/// the right halves of wide glyphs, or by rows of styled text with [`rows`].
+///
+/// The `EGC`s are copied into a single buffer, so iterating doesn't allocate,
+/// and the snapshot isn't affected by later changes to the plane.
+///
+/// # Example
+/// ```no_run
+/// # use libnotcurses_sys::*;
+/// # fn main() -> NcResult<()> {
+/// # let nc = unsafe { Nc::new()? };
+/// # let plane = unsafe { nc.stdplane() };
+/// for (y, x, egc, styles, _channels, _width) in &plane.cells(Some(0), Some(0), None, None)? {
+///     if egc == "@" && styles.has(NcStyle::Bold) {
+///         println!("found it at {}, {}", y, x);
+///     }
+/// }
+/// # unsafe { nc.stop()? };
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`NcPlane.cells`]: NcPlane#method.cells
+/// [`rows`]: NcPlaneCells#method.rows
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NcPlaneCells {
+    /// The first row of the region.
+    beg_y: u32,
+    /// The number of rows of the region.
+    len_y: u32,
+    egcs: String,
+    cells: Vec<Cell>,
+}
+
+/// A cell in an [`NcPlaneCells`] snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Cell {
+    y: u32,
+    x: u32,
+    /// The range of its `EGC` in the buffer.
+    egc: (u32, u32),
+    styles: NcStyle,
+    channels: NcChannels,
+    width: u32,
+}
+
+/// # `NcPlaneCells` constructors
+impl NcPlaneCells {
+    /// Reads the `len_y` × `len_x` cells of the `plane` starting at
+    /// `beg_y`, `beg_x`, which must be inside of it.
+    pub(crate) fn read(
+        plane: &mut NcPlane,
+        beg_y: u32,
+        beg_x: u32,
+        len_y: u32,
+        len_x: u32,
+    ) -> NcResult<Self> {
+        let mut cells = Self::empty(beg_y, len_y);
+        let mut cell = NcCell::new();
+        for y in beg_y..beg_y + len_y {
+            for x in beg_x..beg_x + len_x {
+                plane.at_yx_cell(y, x, &mut cell)?;
+                if !cell.wide_right_p() {
+                    let width = c_api::nccell_cols(&cell) as u32;
+                    cells.push(y, x, cell.egc(plane), cell.styles(), cell.channels(), width);
+                }
+                cell.release(plane);
+            }
+        }
+        Ok(cells)
+    }
+
+    pub(crate) fn empty(beg_y: u32, len_y: u32) -> Self {
+        Self { beg_y, len_y, ..Default::default() }
+    }
+
+    pub(crate) fn push(
+        &mut self,
+        y: u32,
+        x: u32,
+        egc: &str,
+        styles: NcStyle,
+        channels: NcChannels,
+        width: u32,
+    ) {
+        let start = self.egcs.len() as u32;
+        self.egcs.push_str(egc);
+        let egc = (start, self.egcs.len() as u32);
+        self.cells.push(Cell { y, x, egc, styles, channels, width });
+    }
+}
+
+/// # `NcPlaneCells` methods
+impl NcPlaneCells {
+    /// Returns the number of cells, not counting the right halves of wide
+    /// glyphs.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns `true` if there are no cells.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Returns an iterator over the cells, as tuples of
+    /// `(y, x, egc, styles, channels, width)`.
+    ///
+    /// The coordinates are relative to the plane, and the width is in
+    /// columns. Empty cells have an empty `egc`.
+    pub fn iter(&self) -> NcPlaneCellsIter<'_> {
+        NcPlaneCellsIter { snapshot: self, index: 0 }
+    }
+
+    /// Returns an iterator over the rows, as tuples of `(y, spans)`,
+    /// where consecutive cells with the same styles and channels are joined
+    /// in the same span of text.
+    ///
+    /// Empty cells are returned as spaces.
+    pub fn rows(&self) -> NcPlaneRows<'_> {
+        NcPlaneRows { snapshot: self, y: self.beg_y, index: 0 }
+    }
+
+    fn egc(&self, cell: &Cell) -> &str {
+        &self.egcs[cell.egc.0 as usize..cell.egc.1 as usize]
+    }
+}
+
+impl<'a> IntoIterator for &'a NcPlaneCells {
+    type Item = (u32, u32, &'a str, NcStyle, NcChannels, u32);
+    type IntoIter = NcPlaneCellsIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the cells of an [`NcPlaneCells`] snapshot.
+///
+/// It's returned by [`NcPlaneCells.iter`][NcPlaneCells#method.iter].
+#[derive(Clone, Debug)]
+pub struct NcPlaneCellsIter<'a> {
+    snapshot: &'a NcPlaneCells,
+    index: usize,
+}
+
+impl<'a> Iterator for NcPlaneCellsIter<'a> {
+    type Item = (u32, u32, &'a str, NcStyle, NcChannels, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cell = self.snapshot.cells.get(self.index)?;
+        self.index += 1;
+        let egc = self.snapshot.egc(cell);
+        Some((cell.y, cell.x, egc, cell.styles, cell.channels, cell.width))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.snapshot.cells.len() - self.index;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for NcPlaneCellsIter<'_> {}
+
+/// An iterator over the rows of an [`NcPlaneCells`] snapshot.
+///
+/// It's returned by [`NcPlaneCells.rows`][NcPlaneCells#method.rows].
+#[derive(Clone, Debug)]
+pub struct NcPlaneRows<'a> {
+    snapshot: &'a NcPlaneCells,
+    y: u32,
+    index: usize,
+}
+
+impl<'a> Iterator for NcPlaneRows<'a> {
+    type Item = (u32, Vec<NcMarkupSpan>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let y = self.y;
+        if y >= self.snapshot.beg_y + self.snapshot.len_y {
+            return None;
+        }
+        self.y += 1;
+
+        let mut spans: Vec<NcMarkupSpan> = Vec::new();
+        for cell in self.snapshot.cells[self.index..]
+            .iter()
+            .take_while(|c| c.y == y)
+        {
+            self.index += 1;
+            let egc = match self.snapshot.egc(cell) {
+                "" => " ",
+                egc => egc,
+            };
+            match spans.last_mut() {
+                Some(span) if span.styles == cell.styles && span.channels == cell.channels => {
+                    span.text.push_str(egc)
+                }
+                _ => spans.push(NcMarkupSpan {
+                    text: egc.into(),
+                    styles: cell.styles,
+                    channels: cell.channels,
+                }),
+            }
+        }
+        Some((y, spans))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::{string::String, vec::Vec};
+
+    #[test]
+    fn iterate() {
+        let (b, n) = (NcStyle::Bold, NcStyle::None);
+        let red = NcChannels::from_rgb(0xFF0000, 0);
+        let mut cells = NcPlaneCells::empty(2, 3);
+        cells.push(2, 4, "a", b, red, 1);
+        cells.push(2, 5, "ｂ", b, red, 2);
+        cells.push(2, 7, "", n, red, 1);
+        cells.push(4, 4, "c", n, red, 1);
+
+        assert_eq![cells.len(), 4];
+        let all: Vec<_> = cells.iter().map(|(y, x, egc, ..)| (y, x, egc)).collect();
+        assert_eq![all, [(2, 4, "a"), (2, 5, "ｂ"), (2, 7, ""), (4, 4, "c")]];
+        assert_eq![cells.iter().map(|c| c.5).sum::<u32>(), 5];
+
+        let rows: Vec<(u32, Vec<String>)> = cells
+            .rows()
+            .map(|(y, spans)| (y, spans.into_iter().map(|s| s.text).collect()))
+            .collect();
+        assert_eq![rows[0], (2, ["aｂ".into(), " ".into()].into())];
+        assert_eq![rows[1], (3, [].into())];
+        assert_eq![rows[2], (4, ["c".into()].into())];
+    }
+}
@@ -0,0 +1,345 @@
+//! `NcPlaneDiff`
+
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use crate::{NcChannels, NcPlane, NcPlaneCells, NcResult, NcStyle};
+
+/// The cells that differ between two [`NcPlaneCells`] snapshots, of two
+/// planes or of the same plane at two moments.
+///
+/// The changes can be merged into rectangles with [`rects`], e.g. for
+/// updating only those regions, and the [`Display`][fmt::Display]
+/// implementation shows a report that highlights the differing cells,
+/// useful for test assertions.
+///
+/// # Example
+/// ```no_run
+/// # use libnotcurses_sys::*;
+/// # fn main() -> NcResult<()> {
+/// # let nc = unsafe { Nc::new()? };
+/// # let plane = unsafe { nc.stdplane() };
+/// let before = plane.cells(Some(0), Some(0), None, None)?;
+/// plane.putstr_yx(Some(1), Some(2), "hello")?;
+/// let after = plane.cells(Some(0), Some(0), None, None)?;
+///
+/// let diff = NcPlaneDiff::new(&before, &after);
+/// assert_eq![diff.rects(), [(1, 2, 1, 5)]];
+/// println!("{}", diff);
+/// # unsafe { nc.stop()? };
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`rects`]: NcPlaneDiff#method.rects
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NcPlaneDiff {
+    changes: Vec<NcCellChange>,
+    /// The rows with changes, as `(y, old, new, markers)` lines of text.
+    rows: Vec<(u32, String, String, String)>,
+}
+
+/// A cell that differs in an [`NcPlaneDiff`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NcCellChange {
+    /// The row of the cell.
+    pub y: u32,
+    /// The column of the cell.
+    pub x: u32,
+    /// The old contents, or `None` if it wasn't in the old snapshot.
+    pub old: Option<NcCellContent>,
+    /// The new contents, or `None` if it isn't in the new snapshot.
+    pub new: Option<NcCellContent>,
+}
+
+/// The contents of a cell in an [`NcCellChange`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NcCellContent {
+    /// The `EGC`, empty for an empty cell.
+    pub egc: String,
+    /// The styles.
+    pub styles: NcStyle,
+    /// The channels.
+    pub channels: NcChannels,
+    /// The width in columns.
+    pub width: u32,
+}
+
+/// # `NcPlaneDiff` constructors
+impl NcPlaneDiff {
+    /// New `NcPlaneDiff` between the `old` and the `new` snapshots.
+    ///
+    /// Cells are compared by their coordinates in the plane, and those only
+    /// present in one of the snapshots count as changed.
+    pub fn new(old: &NcPlaneCells, new: &NcPlaneCells) -> Self {
+        let mut diff = Self::default();
+        let (mut old, mut new) = (old.iter().peekable(), new.iter().peekable());
+
+        // the row being compared, with its text and the columns used so far
+        let mut row: Option<Row> = None;
+        loop {
+            let (o, n) = match (old.peek(), new.peek()) {
+                (None, None) => break,
+                (Some(o), Some(n)) if (o.0, o.1) == (n.0, n.1) => (old.next(), new.next()),
+                (Some(o), Some(n)) if (o.0, o.1) < (n.0, n.1) => (old.next(), None),
+                (Some(_), None) => (old.next(), None),
+                _ => (None, new.next()),
+            };
+            let content = |c: (u32, u32, &str, NcStyle, NcChannels, u32)| NcCellContent {
+                egc: c.2.into(),
+                styles: c.3,
+                channels: c.4,
+                width: c.5,
+            };
+            let (y, x) = o.or(n).map(|c| (c.0, c.1)).unwrap_or_default();
+            let (old, new) = (o.map(content), n.map(content));
+
+            if row.as_ref().map(|r| r.y) != Some(y) {
+                diff.end_row(row.take());
+                row = Some(Row { y, beg_x: x, ..Default::default() });
+            }
+            if let Some(row) = row.as_mut() {
+                row.push(x, old.as_ref(), new.as_ref());
+            }
+            if old != new {
+                diff.changes.push(NcCellChange { y, x, old, new });
+            }
+        }
+        diff.end_row(row);
+        diff
+    }
+
+    /// New `NcPlaneDiff` between the whole `old` and `new` planes.
+    pub fn planes(old: &mut NcPlane, new: &mut NcPlane) -> NcResult<Self> {
+        let old = old.cells(Some(0), Some(0), None, None)?;
+        let new = new.cells(Some(0), Some(0), None, None)?;
+        Ok(Self::new(&old, &new))
+    }
+
+    /// Keeps the text of the `row` if it has changes.
+    fn end_row(&mut self, row: Option<Row>) {
+        if let Some(row) = row.filter(|r| r.changed) {
+            self.rows.push((row.y, row.old, row.new, row.markers));
+        }
+    }
+}
+
+/// # `NcPlaneDiff` methods
+impl NcPlaneDiff {
+    /// Returns `true` if there are no changes.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the number of changed cells.
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Returns the changed cells, sorted by row and column.
+    pub fn changes(&self) -> &[NcCellChange] {
+        &self.changes
+    }
+
+    /// Returns the changed cells merged into rectangles, as tuples of
+    /// `(y, x, len_y, len_x)`.
+    ///
+    /// Contiguous changes in a row become a span, and spans covering the
+    /// same columns in consecutive rows are merged together.
+    pub fn rects(&self) -> Vec<(u32, u32, u32, u32)> {
+        let mut rects: Vec<(u32, u32, u32, u32)> = Vec::new();
+        // the index of the first rectangle that may still grow downwards
+        let mut open = 0;
+        let mut i = 0;
+        while i < self.changes.len() {
+            let (y, x) = (self.changes[i].y, self.changes[i].x);
+            let mut end = x + self.changes[i].width();
+            i += 1;
+            while let Some(c) = self.changes.get(i).filter(|c| c.y == y && c.x == end) {
+                end += c.width();
+                i += 1;
+            }
+
+            let grows = rects[open..]
+                .iter()
+                .position(|r| r.0 + r.2 == y && r.1 == x && r.3 == end - x);
+            match grows {
+                Some(r) => rects[open + r].2 += 1,
+                None => rects.push((y, x, 1, end - x)),
+            }
+            // rectangles that can't reach the next row
+            if self.changes.get(i).map_or(true, |c| c.y != y) {
+                let next_y = self.changes.get(i).map_or(u32::MAX, |c| c.y);
+                open += rects[open..]
+                    .iter()
+                    .take_while(|r| r.0 + r.2 < next_y)
+                    .count();
+            }
+        }
+        rects.sort_unstable();
+        rects
+    }
+}
+
+impl NcCellChange {
+    /// The columns covered by the change.
+    fn width(&self) -> u32 {
+        let width = |c: &Option<NcCellContent>| c.as_ref().map_or(1, |c| c.width);
+        width(&self.old).max(width(&self.new)).max(1)
+    }
+}
+
+/// A row of text being compared.
+#[derive(Debug, Default)]
+struct Row {
+    y: u32,
+    beg_x: u32,
+    old: String,
+    new: String,
+    markers: String,
+    /// The columns used in each of `old`, `new` and `markers`.
+    cols: (u32, u32, u32),
+    changed: bool,
+}
+
+impl Row {
+    fn push(&mut self, x: u32, old: Option<&NcCellContent>, new: Option<&NcCellContent>) {
+        let col = x - self.beg_x;
+        let put = |text: &mut String, cols: &mut u32, cell: Option<&NcCellContent>| {
+            if let Some(cell) = cell {
+                pad(text, cols, col);
+                text.push_str(if cell.egc.is_empty() { " " } else { &cell.egc });
+                *cols = col + cell.width.max(1);
+            }
+        };
+        put(&mut self.old, &mut self.cols.0, old);
+        put(&mut self.new, &mut self.cols.1, new);
+        if old != new {
+            let width = old.or(new).map_or(1, |c| c.width.max(1));
+            pad(&mut self.markers, &mut self.cols.2, col);
+            self.markers
+                .extend(core::iter::repeat('^').take(width as usize));
+            self.cols.2 = col + width;
+            self.changed = true;
+        }
+    }
+}
+
+/// Pads the `text`, which takes `cols` columns, with spaces up to `col`.
+fn pad(text: &mut String, cols: &mut u32, col: u32) {
+    while *cols < col {
+        text.push(' ');
+        *cols += 1;
+    }
+}
+
+impl fmt::Display for NcPlaneDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rects = self.rects().len();
+        writeln!(
+            f,
+            "{} changed cell{}, in {} rectangle{}",
+            self.len(),
+            if self.len() == 1 { "" } else { "s" },
+            rects,
+            if rects == 1 { "" } else { "s" },
+        )?;
+        let mut changes = self.changes.iter().peekable();
+        for (y, old, new, markers) in &self.rows {
+            writeln!(f, "row {}:", y)?;
+            writeln!(f, "  - |{}|", old)?;
+            writeln!(f, "  + |{}|", new)?;
+            writeln!(f, "     {}", markers)?;
+            while let Some(c) = changes.next_if(|c| c.y == *y) {
+                writeln!(f, "  x {}: {} => {}", c.x, Content(&c.old), Content(&c.new))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Formats the contents of a cell for a report.
+struct Content<'a>(&'a Option<NcCellContent>);
+
+impl fmt::Display for Content<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(cell) = self.0 else {
+            return write!(f, "(none)");
+        };
+        write!(f, "{:?} [{}] fg ", cell.egc, cell.styles)?;
+        let ch = cell.channels;
+        if ch.fg_default_p() {
+            write!(f, "default")?;
+        } else if ch.fg_palindex_p() {
+            write!(f, "pal({})", ch.fg_palindex())?;
+        } else {
+            write!(f, "#{:06x}", ch.fg_rgb().0)?;
+        }
+        write!(f, " bg ")?;
+        if ch.bg_default_p() {
+            write!(f, "default")
+        } else if ch.bg_palindex_p() {
+            write!(f, "pal({})", ch.bg_palindex())
+        } else {
+            write!(f, "#{:06x}", ch.bg_rgb().0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    fn snapshot(rows: &[&str]) -> NcPlaneCells {
+        let mut cells = NcPlaneCells::empty(0, rows.len() as u32);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                let styles = if c.is_uppercase() { NcStyle::Bold } else { NcStyle::None };
+                let egc = if c == '.' { String::new() } else { c.to_string() };
+                cells.push(y as u32, x as u32, &egc, styles, NcChannels::default(), 1);
+            }
+        }
+        cells
+    }
+
+    #[test]
+    fn changes() {
+        let old = snapshot(&["abcd", "abcd", "abcd", "abcd"]);
+        assert![NcPlaneDiff::new(&old, &old).is_empty()];
+
+        let new = snapshot(&["aXYd", "aXYd", "abcd", "Abcd", "ab"]);
+        let diff = NcPlaneDiff::new(&old, &new);
+        assert_eq![diff.len(), 7];
+        assert_eq![diff.changes()[0].x, 1];
+        assert_eq![diff.changes()[0].old.as_ref().unwrap().egc, "b"];
+        assert_eq![
+            diff.changes()[0].new.as_ref().unwrap().styles,
+            NcStyle::Bold
+        ];
+        assert_eq![diff.changes()[5].old, None];
+        assert_eq![diff.rects(), [(0, 1, 2, 2), (3, 0, 1, 1), (4, 0, 1, 2)]];
+
+        let report = diff.to_string();
+        assert![report.starts_with("7 changed cells, in 3 rectangles\nrow 0:\n")];
+        assert![report.contains("  - |abcd|\n  + |aXYd|\n      ^^\n")];
+        assert![report.contains("  x 1: \"b\" [None] fg default bg default => \"X\" [Bold]")];
+        assert![report.contains("  - ||\n  + |ab|\n     ^^\n")];
+        assert![report.contains("  x 1: (none) => \"b\"")];
+    }
+
+    #[test]
+    fn rects() {
+        let old = snapshot(&["....", "....", "....", "...."]);
+        let new = snapshot(&["xx..", "xxx.", ".x.x", "...x"]);
+        let diff = NcPlaneDiff::new(&old, &new);
+        assert_eq![
+            diff.rects(),
+            [(0, 0, 1, 2), (1, 0, 1, 3), (2, 1, 1, 1), (2, 3, 2, 1)]
+        ];
+    }
+}
@@ -0,0 +1,436 @@
+//! `NcLayout`
+
+use core::{ffi::c_void, marker::PhantomData, ptr::NonNull};
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{
+    c_api::{self, NcResult_i32},
+    NcPlane, NcPlaneOptions, NcResult,
+};
+
+/// The direction in which an [`NcLayout`] split places its children.
+///
+/// # Default
+/// *[`NcSplit::Vertical`]*
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NcSplit {
+    /// Children placed from left to right, side by side.
+    Horizontal,
+    /// Children placed from top to bottom, one above the other.
+    #[default]
+    Vertical,
+}
+
+/// The size of a child of an [`NcLayout`] split, along the split direction.
+///
+/// The fixed, percent, ratio and minimum sizes are allotted first, in order,
+/// for as long as there's space. The remaining space is then shared equally
+/// between the `Min`, `Max` and `Fill` children, with the `Max` ones never
+/// growing past their limit.
+///
+/// # Default
+/// *[`NcConstraint::Fill`]*
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NcConstraint {
+    /// Exactly this number of cells.
+    Fixed(u32),
+    /// This percentage of the available cells.
+    Percent(u32),
+    /// At least this number of cells.
+    Min(u32),
+    /// At most this number of cells.
+    Max(u32),
+    /// This fraction of the available cells, as numerator and denominator.
+    Ratio(u32, u32),
+    /// A share of the remaining cells.
+    #[default]
+    Fill,
+}
+
+/// A tree of horizontal and vertical splits, for dividing a plane into panes.
+///
+/// Each node can have a margin around it, and splits a gap between their
+/// children. The leaves of the tree are the panes, which are numbered in the
+/// order they are declared, depth first.
+///
+/// The layout can be [`solve`]d for a given size, or used to [`create`] a
+/// child plane of a root plane for each pane, that is moved and resized
+/// automatically whenever the root plane is resized.
+///
+/// # Example
+/// ```no_run
+/// # use libnotcurses_sys::*;
+/// # fn main() -> NcResult<()> {
+/// # let nc = unsafe { Nc::new()? };
+/// # let stdplane = unsafe { nc.stdplane() };
+/// use NcConstraint::*;
+///
+/// // a title bar, a sidebar and a main area, and a status line
+/// let layout = NcLayout::vertical()
+///     .child(Fixed(1), NcLayout::pane())
+///     .child(
+///         Fill,
+///         NcLayout::horizontal()
+///             .gap(1)
+///             .child(Percent(25), NcLayout::pane())
+///             .child(Fill, NcLayout::pane().margin(0, 1)),
+///     )
+///     .child(Fixed(1), NcLayout::pane());
+///
+/// let mut panes = layout.create(stdplane)?;
+/// panes.pane(2).unwrap().putstr("main")?;
+/// nc.render()?;
+/// # drop(panes);
+/// # unsafe { nc.stop()? };
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`solve`]: NcLayout#method.solve
+/// [`create`]: NcLayout#method.create
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NcLayout {
+    /// `None` for a pane.
+    split: Option<NcSplit>,
+    margin_yx: (u32, u32),
+    gap: u32,
+    children: Vec<(NcConstraint, NcLayout)>,
+}
+
+/// # `NcLayout` constructors
+impl NcLayout {
+    /// New pane, a leaf of the layout.
+    pub fn pane() -> Self {
+        Self::default()
+    }
+
+    /// New split that places its children from left to right.
+    pub fn horizontal() -> Self {
+        Self::split(NcSplit::Horizontal)
+    }
+
+    /// New split that places its children from top to bottom.
+    pub fn vertical() -> Self {
+        Self::split(NcSplit::Vertical)
+    }
+
+    /// New split in the provided `direction`.
+    pub fn split(direction: NcSplit) -> Self {
+        Self { split: Some(direction), ..Default::default() }
+    }
+
+    /// Sets the margin of `y` rows above and below, and `x` columns to the
+    /// left and right.
+    pub fn margin(mut self, y: u32, x: u32) -> Self {
+        self.margin_yx = (y, x);
+        self
+    }
+
+    /// Sets the gap between the children of a split.
+    pub fn gap(mut self, gap: u32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Adds a `child` to a split, with a size defined by `constraint`.
+    ///
+    /// The children of a pane are ignored.
+    pub fn child(mut self, constraint: NcConstraint, child: NcLayout) -> Self {
+        self.children.push((constraint, child));
+        self
+    }
+}
+
+/// # `NcLayout` methods
+impl NcLayout {
+    /// Returns the number of panes.
+    pub fn panes(&self) -> usize {
+        match self.split {
+            None => 1,
+            Some(_) => self.children.iter().map(|(_, c)| c.panes()).sum(),
+        }
+    }
+
+    /// Returns the region of each pane in an area of `len_y` × `len_x` cells,
+    /// as `(y, x, len_y, len_x)`.
+    ///
+    /// Panes that don't fit have a length of 0.
+    pub fn solve(&self, len_y: u32, len_x: u32) -> Vec<(u32, u32, u32, u32)> {
+        let mut rects = Vec::with_capacity(self.panes());
+        self.solve_into(0, 0, len_y, len_x, &mut rects);
+        rects
+    }
+
+    fn solve_into(
+        &self,
+        y: u32,
+        x: u32,
+        len_y: u32,
+        len_x: u32,
+        rects: &mut Vec<(u32, u32, u32, u32)>,
+    ) {
+        let (margin_y, margin_x) = self.margin_yx;
+        let (y, x) = (y + margin_y.min(len_y), x + margin_x.min(len_x));
+        let len_y = len_y.saturating_sub(margin_y * 2);
+        let len_x = len_x.saturating_sub(margin_x * 2);
+
+        let Some(split) = self.split else {
+            rects.push((y, x, len_y, len_x));
+            return;
+        };
+        let total = match split {
+            NcSplit::Horizontal => len_x,
+            NcSplit::Vertical => len_y,
+        };
+        let constraints: Vec<_> = self.children.iter().map(|(c, _)| *c).collect();
+        let mut offset = 0;
+        for ((_, child), len) in self
+            .children
+            .iter()
+            .zip(distribute(total, self.gap, &constraints))
+        {
+            let offset_len = offset.min(total);
+            match split {
+                NcSplit::Horizontal => child.solve_into(y, x + offset_len, len_y, len, rects),
+                NcSplit::Vertical => child.solve_into(y + offset_len, x, len, len_x, rects),
+            }
+            offset += len + self.gap;
+        }
+    }
+
+    /// Creates a child plane of `root` for each pane, and returns them.
+    ///
+    /// Every time the root plane is resized, the layout is solved again and
+    /// the panes are moved and resized to fit, from their resize callbacks.
+    /// Panes that don't fit are kept at 1×1.
+    ///
+    /// *C style function: [ncplane_create()][c_api::ncplane_create].*
+    pub fn create<'root>(self, root: &'root mut NcPlane) -> NcResult<NcPanes<'root>> {
+        let (len_y, len_x) = root.dim_yx();
+        let rects = self.solve(len_y, len_x);
+
+        let mut panes = NcPanes {
+            inner: Box::new(Inner {
+                root: NonNull::from(&mut *root),
+                layout: self,
+                panes: Vec::with_capacity(rects.len()),
+            }),
+            _root: PhantomData,
+        };
+        let userptr = &*panes.inner as *const Inner as *mut c_void;
+        for (y, x, len_y, len_x) in rects {
+            let mut options = NcPlaneOptions::builder()
+                .yx(y as i32, x as i32)
+                .rows_cols(len_y.max(1), len_x.max(1))
+                .resizecb(Some(resize_pane))
+                .build();
+            options.userptr = userptr;
+            let pane = NcPlane::new_child(root, &options)?;
+            panes.inner.panes.push(NonNull::from(pane));
+        }
+        Ok(panes)
+    }
+}
+
+/// Returns the lengths of the children with the provided `constraints`,
+/// sharing `total` cells minus the gaps between them.
+pub(crate) fn distribute(total: u32, gap: u32, constraints: &[NcConstraint]) -> Vec<u32> {
+    use NcConstraint::*;
+    let gaps = gap.saturating_mul(constraints.len().saturating_sub(1) as u32);
+    let available = total.saturating_sub(gaps);
+    let part = |num: u32, den: u32| (available as u64 * num as u64 / den.max(1) as u64) as u32;
+
+    let mut left = available;
+    let mut lens: Vec<u32> = constraints
+        .iter()
+        .map(|c| {
+            let len = match *c {
+                Fixed(len) | Min(len) => len,
+                Percent(percent) => part(percent.min(100), 100),
+                Ratio(num, den) => part(num.min(den), den),
+                Max(_) | Fill => 0,
+            };
+            let len = len.min(left);
+            left -= len;
+            len
+        })
+        .collect();
+
+    while left > 0 {
+        let growable: Vec<usize> = (0..lens.len())
+            .filter(|&i| match constraints[i] {
+                Min(_) | Fill => true,
+                Max(max) => lens[i] < max,
+                _ => false,
+            })
+            .collect();
+        if growable.is_empty() {
+            break;
+        }
+        let (share, extra) = (left / growable.len() as u32, left % growable.len() as u32);
+        for (n, &i) in growable.iter().enumerate() {
+            let mut grow = share + u32::from((n as u32) < extra);
+            if let Max(max) = constraints[i] {
+                grow = grow.min(max - lens[i]);
+            }
+            lens[i] += grow;
+            left -= grow;
+        }
+    }
+    lens
+}
+
+/// The child planes created for the panes of an [`NcLayout`].
+///
+/// It's returned by [`NcLayout.create`], and borrows the root plane.
+///
+/// Unlike most of the types in this library, the planes are destroyed
+/// together with it. It must therefore be dropped before calling
+/// [`Nc.stop`], which frees every plane.
+///
+/// [`NcLayout.create`]: NcLayout#method.create
+/// [`Nc.stop`]: crate::Nc#method.stop
+#[derive(Debug)]
+pub struct NcPanes<'root> {
+    // boxed, so that its address stays the same for the resize callbacks
+    inner: Box<Inner>,
+    _root: PhantomData<&'root NcPlane>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    root: NonNull<NcPlane>,
+    layout: NcLayout,
+    panes: Vec<NonNull<NcPlane>>,
+}
+
+/// # `NcPanes` methods
+impl<'root> NcPanes<'root> {
+    /// Returns the number of panes.
+    pub fn len(&self) -> usize {
+        self.inner.panes.len()
+    }
+
+    /// Returns `true` if there are no panes.
+    pub fn is_empty(&self) -> bool {
+        self.inner.panes.is_empty()
+    }
+
+    /// Returns the plane of the pane at `index`.
+    pub fn pane(&mut self, index: usize) -> Option<&mut NcPlane> {
+        self.inner
+            .panes
+            .get_mut(index)
+            .map(|pane| unsafe { pane.as_mut() })
+    }
+
+    /// Returns the layout.
+    pub fn layout(&self) -> &NcLayout {
+        &self.inner.layout
+    }
+
+    /// Solves the layout again for the current size of the root plane, and
+    /// moves and resizes the panes to fit.
+    ///
+    /// This is done automatically when the root plane is resized.
+    pub fn relayout(&mut self) -> NcResult<()> {
+        let (len_y, len_x) = unsafe { self.inner.root.as_ref() }.dim_yx();
+        let rects = self.inner.layout.solve(len_y, len_x);
+        for (pane, rect) in self.inner.panes.iter_mut().zip(rects) {
+            place(unsafe { pane.as_mut() }, rect)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for NcPanes<'_> {
+    /// Destroys the planes of the panes.
+    ///
+    /// *C style function: [ncplane_destroy()][c_api::ncplane_destroy].*
+    fn drop(&mut self) {
+        for pane in &self.inner.panes {
+            unsafe {
+                c_api::ncplane_destroy(pane.as_ptr());
+            }
+        }
+    }
+}
+
+/// Moves and resizes the `pane` to the region `(y, x, len_y, len_x)`.
+fn place(pane: &mut NcPlane, (y, x, len_y, len_x): (u32, u32, u32, u32)) -> NcResult<()> {
+    pane.resize_simple(len_y.max(1), len_x.max(1))?;
+    pane.move_yx(y as i32, x as i32)
+}
+
+/// The resize callback of the panes, called when the root plane is resized.
+fn resize_pane(pane: &mut NcPlane) -> NcResult_i32 {
+    let inner = unsafe { c_api::ncplane_userptr(pane) } as *const Inner;
+    let Some(inner) = (unsafe { inner.as_ref() }) else {
+        return c_api::NCRESULT_OK;
+    };
+    let Some(index) = inner
+        .panes
+        .iter()
+        .position(|p| core::ptr::eq(p.as_ptr(), pane))
+    else {
+        return c_api::NCRESULT_ERR;
+    };
+    let (len_y, len_x) = unsafe { inner.root.as_ref() }.dim_yx();
+    match place(pane, inner.layout.solve(len_y, len_x)[index]) {
+        Ok(()) => c_api::NCRESULT_OK,
+        Err(e) => e.int,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{NcConstraint::*, *};
+
+    #[test]
+    fn constraints() {
+        assert_eq![distribute(20, 0, &[Fixed(3), Fill, Fixed(1)]), [3, 16, 1]];
+        assert_eq![distribute(20, 1, &[Fixed(3), Fill, Fixed(1)]), [3, 14, 1]];
+        assert_eq![distribute(10, 0, &[Fill, Fill, Fill]), [4, 3, 3]];
+        assert_eq![
+            distribute(100, 0, &[Percent(25), Ratio(1, 2), Fill]),
+            [25, 50, 25]
+        ];
+        assert_eq![distribute(30, 0, &[Max(5), Min(10), Fill]), [5, 18, 7]];
+        assert_eq![distribute(8, 0, &[Fixed(5), Min(5), Fill]), [5, 3, 0]];
+        assert_eq![distribute(10, 0, &[Max(2), Fixed(1)]), [2, 1]];
+        assert_eq![distribute(2, 4, &[Fill, Fill]), [0, 0]];
+        assert![distribute(5, 0, &[]).is_empty()];
+    }
+
+    #[test]
+    fn solve() {
+        let layout = NcLayout::vertical()
+            .child(Fixed(1), NcLayout::pane())
+            .child(
+                Fill,
+                NcLayout::horizontal()
+                    .gap(1)
+                    .child(Percent(25), NcLayout::pane())
+                    .child(Fill, NcLayout::pane().margin(1, 2)),
+            )
+            .child(Fixed(1), NcLayout::pane())
+            .margin(1, 0);
+        assert_eq![layout.panes(), 4];
+        assert_eq![
+            layout.solve(24, 81),
+            [
+                (1, 0, 1, 81),
+                (2, 0, 20, 20),
+                (3, 23, 18, 56),
+                (22, 0, 1, 81)
+            ]
+        ];
+        assert_eq![
+            layout.solve(2, 2),
+            [(1, 0, 0, 2), (1, 0, 0, 0), (1, 2, 0, 0), (1, 0, 0, 2)]
+        ];
+        assert_eq![NcLayout::pane().solve(3, 4), [(0, 0, 3, 4)]];
+    }
+}
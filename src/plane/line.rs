@@ -0,0 +1,75 @@
+//! Helpers for drawing lines.
+
+use crate::{NcChannels, NcColorSpace};
+
+/// Returns the cells of the line from `y1`, `x1` to `y2`, `x2`, both ends
+/// included, using Bresenham's algorithm.
+pub(crate) fn line_points(y1: i32, x1: i32, y2: i32, x2: i32) -> impl Iterator<Item = (i32, i32)> {
+    let (dy, dx) = (-(y2 - y1).abs(), (x2 - x1).abs());
+    let (sy, sx) = ((y2 - y1).signum(), (x2 - x1).signum());
+    let (mut y, mut x, mut err) = (y1, x1, dx + dy);
+    let len = dx.max(-dy) + 1;
+    (0..len).map(move |_| {
+        let point = (y, x);
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+        point
+    })
+}
+
+/// Interpolates between the `start` and `end` channels at `t`,
+/// from `0.0` to `1.0`.
+///
+/// Like the C library, each of the foreground and background is only
+/// interpolated when both ends are RGB, otherwise the one at `start` is kept.
+pub(crate) fn lerp_channels(start: NcChannels, end: NcChannels, t: f32) -> NcChannels {
+    let mut channels = start;
+    if start.fg_rgb_p() && end.fg_rgb_p() {
+        let rgb = start.fg_rgb().lerp_in(end.fg_rgb(), t, NcColorSpace::Srgb);
+        channels.set_fg_rgb(rgb);
+    }
+    if start.bg_rgb_p() && end.bg_rgb_p() {
+        let rgb = start.bg_rgb().lerp_in(end.bg_rgb(), t, NcColorSpace::Srgb);
+        channels.set_bg_rgb(rgb);
+    }
+    channels
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::NcRgb;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    #[test]
+    fn points() {
+        let line: Vec<_> = line_points(0, 0, 2, 4).collect();
+        assert_eq![line, [(0, 0), (1, 1), (1, 2), (2, 3), (2, 4)]];
+        let line: Vec<_> = line_points(3, 1, 0, 1).collect();
+        assert_eq![line, [(3, 1), (2, 1), (1, 1), (0, 1)]];
+        assert_eq![line_points(5, 5, 5, 5).count(), 1];
+        assert_eq![line_points(0, 0, -3, -3).last(), Some((-3, -3))];
+    }
+
+    #[test]
+    fn channels() {
+        let start = NcChannels::from_rgb(0x000000, 0xFF0000);
+        let end = NcChannels::from_rgb(0xFFFFFF, 0xFF0000);
+        let mid = lerp_channels(start, end, 0.5);
+        assert_eq![mid.fg_rgb(), NcRgb(0x808080)];
+        assert_eq![mid.bg_rgb(), NcRgb(0xFF0000)];
+        assert_eq![lerp_channels(start, end, 1.0), end];
+
+        let end = NcChannels::default();
+        assert_eq![lerp_channels(start, end, 0.5), start];
+    }
+}
@@ -6,10 +6,13 @@ use core::{
     slice::from_raw_parts_mut,
 };
 
+use super::line::{lerp_channels, line_points};
 use crate::{
-    c_api, cstring, error, error_ref, error_ref_mut, rstring_free, Nc, NcAlign, NcAlpha, NcBlitter,
-    NcBoxMask, NcCell, NcChannel, NcChannels, NcError, NcFadeCb, NcPaletteIndex, NcPixelGeometry,
-    NcPlane, NcPlaneOptions, NcResizeCb, NcResult, NcRgb, NcRgba, NcStyle, NcTime,
+    c_api, cstring, error, error_ref, error_ref_mut, rstring_free, Nc, NcAlign, NcAlpha, NcAnsi,
+    NcBlitter, NcBoxMask, NcCell, NcChannel, NcChannels, NcError, NcFadeCb, NcGradient,
+    NcGradientTarget, NcMarkup, NcPaletteIndex, NcPixelGeometry, NcPlane, NcPlaneCells,
+    NcPlaneOptions, NcResizeCb, NcResult, NcRgb, NcRgba, NcStyle, NcTextAlign, NcTextLayout,
+    NcTime,
 };
 
 #[cfg(feature = "std")]
@@ -675,6 +678,49 @@ impl NcPlane {
         }
     }
 
+    /// Returns a snapshot of the cells of the selected region of the `NcPlane`,
+    /// that can be iterated by cell or by row.
+    ///
+    /// Starts at the plane's `beg_y` * `beg_x` coordinates (which must lie on
+    /// the plane), continuing for `len_y` x `len_x` cells.
+    ///
+    /// Use `None` for either or all of `beg_y` and `beg_x` in order to
+    /// use the current cursor position along that axis.
+    ///
+    /// Use `None` for either or both of `len_y` and `len_x` in order to
+    /// go through the boundary of the plane in that axis (same as `0`).
+    ///
+    /// The right halves of wide glyphs are skipped.
+    ///
+    /// # Errors
+    /// If either `len_y` or `len_x` fall outside the plane's boundaries.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn cells(
+        &mut self,
+        beg_y: Option<u32>,
+        beg_x: Option<u32>,
+        len_y: Option<u32>,
+        len_x: Option<u32>,
+    ) -> NcResult<NcPlaneCells> {
+        let (rows, cols) = self.dim_yx();
+        let (cur_y, cur_x) = self.cursor_yx();
+        let (beg_y, beg_x) = (beg_y.unwrap_or(cur_y), beg_x.unwrap_or(cur_x));
+        let len_y = len_y
+            .filter(|&l| l > 0)
+            .unwrap_or(rows.saturating_sub(beg_y));
+        let len_x = len_x
+            .filter(|&l| l > 0)
+            .unwrap_or(cols.saturating_sub(beg_x));
+        if beg_y >= rows || beg_x >= cols || len_y > rows - beg_y || len_x > cols - beg_x {
+            return Err(NcError::with_msg(
+                c_api::NCRESULT_ERR,
+                &format!("NcPlane.cells({}, {}, {}, {})", beg_y, beg_x, len_y, len_x),
+            ));
+        }
+        NcPlaneCells::read(self, beg_y, beg_x, len_y, len_x)
+    }
+
     /// Erases every [`NcCell`] in this `NcPlane`, resetting all attributes to
     /// normal, all colors to the default color, and all cells to undrawn.
     ///
@@ -948,6 +994,85 @@ impl NcPlane {
         error![res, &format!("NcPlane.puttext({:?})", string), res as u32]
     }
 
+    /// Writes `text` laid out with an [`NcTextLayout`], in the region whose
+    /// upper left corner is at `y`, `x`.
+    ///
+    /// `None` may be specified to indicate the cursor's position in that
+    /// dimension.
+    ///
+    /// Lines below the bottom of the plane are not written.
+    ///
+    /// Returns the number of lines written.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn puttext_layout(
+        &mut self,
+        y: Option<u32>,
+        x: Option<u32>,
+        layout: &NcTextLayout,
+        text: &str,
+    ) -> NcResult<u32> {
+        let (cur_y, cur_x) = self.cursor_yx();
+        let (y, x) = (y.unwrap_or(cur_y), x.unwrap_or(cur_x));
+        let rows = self.dim_y();
+
+        let mut written = 0;
+        for line in layout.lines(text) {
+            if y + written >= rows {
+                break;
+            }
+            if !line.text.is_empty() {
+                self.putstr_yx(Some(y + written), Some(x + line.x), &line.text)?;
+            }
+            written += 1;
+        }
+        Ok(written)
+    }
+
+    /// Writes the inline style [markup][NcMarkup] to the provided row,
+    /// wrapped to the width of the plane and [`NcAlign`]ed on *x*.
+    ///
+    /// The styles and channels of the plane are restored afterwards.
+    ///
+    /// Lines below the bottom of the plane are not written.
+    ///
+    /// Returns the number of columns written.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn puttext_markup(
+        &mut self,
+        y: u32,
+        align: impl Into<NcAlign>,
+        markup: &str,
+    ) -> NcResult<u32> {
+        let (styles, channels) = (self.styles(), self.channels());
+        let markup = NcMarkup::parse(markup, styles, channels)?;
+        let align = match align.into() {
+            NcAlign::Center => NcTextAlign::Center,
+            NcAlign::Right => NcTextAlign::Right,
+            _ => NcTextAlign::Left,
+        };
+        let (rows, cols) = self.dim_yx();
+        let layout = NcTextLayout::new(cols).align(align);
+
+        let put = || -> NcResult<u32> {
+            let mut written = 0;
+            for (row, (x, spans)) in (y..rows).zip(markup.lines(layout)) {
+                self.cursor_move_yx(row, x)?;
+                for span in spans {
+                    self.set_styles(span.styles);
+                    self.set_channels(span.channels);
+                    written += self.putstr(&span.text)?;
+                }
+            }
+            Ok(written)
+        };
+        let res = put();
+        self.set_styles(styles);
+        self.set_channels(channels);
+        res
+    }
+
     /// Writes a string to the current location, using the current style.
     ///
     /// Advances the cursor by some positive number of columns (though not
@@ -990,6 +1115,108 @@ impl NcPlane {
         Ok(cols)
     }
 
+    /// Writes the inline style [markup][NcMarkup] to the current location.
+    ///
+    /// The styles and channels of the plane are restored afterwards.
+    ///
+    /// Returns the number of columns advanced, with newlines counting
+    /// as 1 column.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn putstr_markup(&mut self, markup: &str) -> NcResult<u32> {
+        self.putstr_markup_yx(None, None, markup)
+    }
+
+    /// Writes the inline style [markup][NcMarkup] to the provided location.
+    ///
+    /// `None` may be specified to indicate the cursor's position in that
+    /// dimension.
+    ///
+    /// The styles and channels of the plane are restored afterwards.
+    ///
+    /// Returns the number of columns advanced, with newlines counting
+    /// as 1 column.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn putstr_markup_yx(
+        &mut self,
+        y: Option<u32>,
+        x: Option<u32>,
+        markup: &str,
+    ) -> NcResult<u32> {
+        let (styles, channels) = (self.styles(), self.channels());
+        let markup = NcMarkup::parse(markup, styles, channels)?;
+
+        let mut put = || -> NcResult<u32> {
+            if y.is_some() || x.is_some() {
+                let (cur_y, cur_x) = self.cursor_yx();
+                self.cursor_move_yx(y.unwrap_or(cur_y), x.unwrap_or(cur_x))?;
+            }
+            let mut cols = 0;
+            for span in markup.spans() {
+                self.set_styles(span.styles);
+                self.set_channels(span.channels);
+                cols += self.putstr(&span.text)?;
+            }
+            Ok(cols)
+        };
+        let res = put();
+        self.set_styles(styles);
+        self.set_channels(channels);
+        res
+    }
+
+    /// Writes text with ANSI escape sequences to the current location,
+    /// as a terminal would.
+    ///
+    /// SGR sequences change the styles and channels of the plane, starting
+    /// from the current ones, and are kept afterwards. Other escape sequences
+    /// and control characters are stripped. A carriage return moves the
+    /// cursor to the first column, and with scrolling enabled a newline at
+    /// the bottom of the plane scrolls it up.
+    ///
+    /// An incomplete escape sequence at the end of the text is discarded.
+    /// Use [`put_ansi_with`] for text arriving in chunks.
+    ///
+    /// Returns the number of columns advanced, with newlines counting
+    /// as 1 column.
+    ///
+    /// *(No equivalent C style function)*
+    ///
+    /// [`put_ansi_with`]: NcPlane#method.put_ansi_with
+    pub fn put_ansi(&mut self, text: &str) -> NcResult<u32> {
+        let mut ansi = NcAnsi::new(self.styles(), self.channels());
+        self.put_ansi_with(&mut ansi, text)
+    }
+
+    /// Writes text with ANSI escape sequences to the current location,
+    /// parsed with an existing [`NcAnsi`] parser.
+    ///
+    /// Like [`put_ansi`], but the styles are those of the parser, which also
+    /// keeps any incomplete escape sequence until the next call.
+    ///
+    /// *(No equivalent C style function)*
+    ///
+    /// [`put_ansi`]: NcPlane#method.put_ansi
+    pub fn put_ansi_with(&mut self, ansi: &mut NcAnsi, text: &str) -> NcResult<u32> {
+        let mut cols = 0;
+        for span in ansi.parse(text) {
+            self.set_styles(span.styles);
+            self.set_channels(span.channels);
+            for (i, line) in span.text.split('\r').enumerate() {
+                if i > 0 {
+                    self.cursor_move_x(0)?;
+                }
+                if !line.is_empty() {
+                    cols += self.putstr(line)?;
+                }
+            }
+        }
+        self.set_styles(ansi.styles());
+        self.set_channels(ansi.channels());
+        Ok(cols)
+    }
+
     /// Writes a string to the current location, retaining the previous style.
     ///
     /// Advances the cursor by some positive number of columns (though not
@@ -2238,7 +2465,7 @@ impl NcPlane {
 }
 
 // -----------------------------------------------------------------------------
-/// ## NcPlane methods: boxes & perimeters
+/// ## NcPlane methods: boxes, lines & perimeters
 impl NcPlane {
     /// Draws a box with its upper-left corner at the current cursor position,
     /// and its lower-right corner at `stop_y` * `stop_x`.
@@ -2446,6 +2673,144 @@ impl NcPlane {
             boxmask.into()
         )]
     }
+
+    /// Draws a horizontal line of `len` cells from the current cursor
+    /// position, interpolating between the channels `c1` and `c2`.
+    ///
+    /// The egc and styles are taken from the `cell`, which may not use more
+    /// than one column.
+    ///
+    /// Returns the number of cells drawn.
+    ///
+    /// *C style function: [ncplane_hline_interp()][c_api::ncplane_hline_interp].*
+    pub fn hline_interp(
+        &mut self,
+        cell: &NcCell,
+        len: u32,
+        c1: impl Into<NcChannels>,
+        c2: impl Into<NcChannels>,
+    ) -> NcResult<u32> {
+        let (c1, c2) = (c1.into(), c2.into());
+        let res = unsafe { c_api::ncplane_hline_interp(self, cell, len, c1.0, c2.0) };
+        error![
+            res,
+            &format!(
+                "NcPlane.hline_interp({:?}, {}, {:0X}, {:0X})",
+                cell, len, c1, c2
+            ),
+            res as u32
+        ]
+    }
+
+    /// Draws a vertical line of `len` cells from the current cursor
+    /// position, interpolating between the channels `c1` and `c2`.
+    ///
+    /// The egc and styles are taken from the `cell`, which may not use more
+    /// than one column.
+    ///
+    /// Returns the number of cells drawn.
+    ///
+    /// *C style function: [ncplane_vline_interp()][c_api::ncplane_vline_interp].*
+    pub fn vline_interp(
+        &mut self,
+        cell: &NcCell,
+        len: u32,
+        c1: impl Into<NcChannels>,
+        c2: impl Into<NcChannels>,
+    ) -> NcResult<u32> {
+        let (c1, c2) = (c1.into(), c2.into());
+        let res = unsafe { c_api::ncplane_vline_interp(self, cell, len, c1.0, c2.0) };
+        error![
+            res,
+            &format!(
+                "NcPlane.vline_interp({:?}, {}, {:0X}, {:0X})",
+                cell, len, c1, c2
+            ),
+            res as u32
+        ]
+    }
+
+    /// Like [`hline_interp`][NcPlane#method.hline_interp], but drawing the
+    /// `egc` with the current styles.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn hline_interp_egc(
+        &mut self,
+        egc: &str,
+        len: u32,
+        c1: impl Into<NcChannels>,
+        c2: impl Into<NcChannels>,
+    ) -> NcResult<u32> {
+        let (mut cell, styles) = (NcCell::new(), self.styles());
+        NcCell::prime(self, &mut cell, egc, styles, 0)?;
+        let res = self.hline_interp(&cell, len, c1, c2);
+        cell.release(self);
+        res
+    }
+
+    /// Like [`vline_interp`][NcPlane#method.vline_interp], but drawing the
+    /// `egc` with the current styles.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn vline_interp_egc(
+        &mut self,
+        egc: &str,
+        len: u32,
+        c1: impl Into<NcChannels>,
+        c2: impl Into<NcChannels>,
+    ) -> NcResult<u32> {
+        let (mut cell, styles) = (NcCell::new(), self.styles());
+        NcCell::prime(self, &mut cell, egc, styles, 0)?;
+        let res = self.vline_interp(&cell, len, c1, c2);
+        cell.release(self);
+        res
+    }
+
+    /// Draws a line of `egc`s, with the current styles, from `y1`, `x1` to
+    /// `y2`, `x2`, both ends included, interpolating between the channels
+    /// `c1` and `c2` along the way.
+    ///
+    /// The line can go in any direction, and the `egc` may not use more than
+    /// one column. The parts of the line outside of the plane are skipped.
+    ///
+    /// Each of the foreground and background is only interpolated if it's RGB
+    /// in both `c1` and `c2`, otherwise the one in `c1` is used.
+    ///
+    /// Returns the number of cells drawn.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn line(
+        &mut self,
+        y1: i32,
+        x1: i32,
+        y2: i32,
+        x2: i32,
+        egc: &str,
+        c1: impl Into<NcChannels>,
+        c2: impl Into<NcChannels>,
+    ) -> NcResult<u32> {
+        let (c1, c2) = (c1.into(), c2.into());
+        let (rows, cols) = self.dim_yx();
+        let (mut cell, styles) = (NcCell::new(), self.styles());
+        NcCell::prime(self, &mut cell, egc, styles, c1)?;
+
+        let steps = (y2 - y1).abs().max((x2 - x1).abs()).max(1) as f32;
+        let mut draw = || -> NcResult<u32> {
+            let mut drawn = 0;
+            for (i, (y, x)) in line_points(y1, x1, y2, x2).enumerate() {
+                if y < 0 || x < 0 || y as u32 >= rows || x as u32 >= cols {
+                    continue;
+                }
+                cell.set_channels(lerp_channels(c1, c2, i as f32 / steps));
+                self.putc_yx(y as u32, x as u32, &cell)?;
+                drawn += 1;
+            }
+            Ok(drawn)
+        };
+        let res = draw();
+        cell.release(self);
+        res
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -2607,6 +2972,81 @@ impl NcPlane {
         error![res, "", res as u32]
     }
 
+    /// Fills the `target` channels of the specified region with an
+    /// [`NcGradient`], keeping content, attributes and alpha unchanged.
+    ///
+    /// The upper left corner is at `y`, `x`, and `None` may be
+    /// specified to indicate the cursor's position in that dimension.
+    ///
+    /// The area is specified by `len_y`, `len_x`, and `None` may be specified
+    /// to indicate everything remaining to the right and below, respectively.
+    ///
+    /// Cells are considered to be twice as tall as they are wide, so that
+    /// angles and circles look right on most terminals.
+    ///
+    /// It is an error for any coordinate to be outside the plane.
+    ///
+    /// Returns the number of cells filled.
+    pub fn gradient_fill(
+        &mut self,
+        gradient: &NcGradient,
+        target: NcGradientTarget,
+        y: Option<u32>,
+        x: Option<u32>,
+        len_y: Option<u32>,
+        len_x: Option<u32>,
+    ) -> NcResult<u32> {
+        let (rows, cols) = self.dim_yx();
+        let (cur_y, cur_x) = self.cursor_yx();
+        let (y, x) = (y.unwrap_or(cur_y), x.unwrap_or(cur_x));
+        if y >= rows || x >= cols {
+            return Err(NcError::with_msg(
+                c_api::NCRESULT_ERR,
+                &format!("NcPlane.gradient_fill({:?}, {}, {}, ..)", target, y, x),
+            ));
+        }
+        let len_y = len_y.filter(|l| *l > 0).unwrap_or(rows - y);
+        let len_x = len_x.filter(|l| *l > 0).unwrap_or(cols - x);
+        if len_y > rows - y || len_x > cols - x {
+            return Err(NcError::with_msg(
+                c_api::NCRESULT_ERR,
+                &format!(
+                    "NcPlane.gradient_fill({:?}, {}, {}, {}, {})",
+                    target, y, x, len_y, len_x
+                ),
+            ));
+        }
+
+        let (height, width) = (len_y as f32 * 2.0, len_x as f32);
+        let mut cell = NcCell::new();
+        for row in 0..len_y {
+            for col in 0..len_x {
+                let color = gradient
+                    .sample(row as f32 * 2.0 + 1.0, col as f32 + 0.5, height, width)
+                    .to_rgb();
+                self.at_yx_cell(y + row, x + col, &mut cell)?;
+                let mut channels = cell.channels();
+                cell.release(self);
+                match target {
+                    NcGradientTarget::Foreground => channels.set_fg_rgb(color),
+                    NcGradientTarget::Background => channels.set_bg_rgb(color),
+                    NcGradientTarget::Both => channels.set_fg_rgb(color).set_bg_rgb(color),
+                };
+                self.stain(
+                    Some(y + row),
+                    Some(x + col),
+                    Some(1),
+                    Some(1),
+                    channels,
+                    channels,
+                    channels,
+                    channels,
+                )?;
+            }
+        }
+        Ok(len_y * len_x)
+    }
+
     /// Converts this `NcPlane`'s content to greyscale.
     ///
     /// *C style function: [ncplane_greyscale()][c_api::ncplane_greyscale].*
@@ -43,7 +43,7 @@
 //W  ncplane_format
 //W  ncplane_gradient2x1
 //W  ncplane_greyscale
-//   ncplane_hline_interp
+//W  ncplane_hline_interp
 //W# ncplane_home
 //W  ncplane_mergedown
 //W  ncplane_mergedown_simple
@@ -101,7 +101,7 @@
 //W  ncplane_translate
 //W  ncplane_translate_abs
 //   ncplane_userptr
-//   ncplane_vline_interp
+//W  ncplane_vline_interp
 //W  ncplane_x
 //W  ncplane_y
 //W  ncplane_yx
@@ -185,14 +185,23 @@
 //   ncplane_putwstr_yx
 //   ncplane_vprintf
 
+mod cells;
+mod diff;
 pub(crate) mod helpers;
+pub(crate) mod layout;
+pub(crate) mod line;
 mod methods;
 pub(crate) mod options;
 pub(crate) mod reimplemented;
+mod scrollback;
 #[cfg(test)]
 pub(crate) mod test;
 
+pub use cells::{NcPlaneCells, NcPlaneCellsIter, NcPlaneRows};
+pub use diff::{NcCellChange, NcCellContent, NcPlaneDiff};
+pub use layout::{NcConstraint, NcLayout, NcPanes, NcSplit};
 pub use options::{NcPlaneFlag, NcPlaneOptions, NcPlaneOptionsBuilder};
+pub use scrollback::NcScrollback;
 
 // NcPlane
 //
@@ -0,0 +1,404 @@
+//! `NcScrollback`
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::VecDeque, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::VecDeque;
+
+use crate::{
+    NcChannels, NcGraphemes, NcMarkupSpan, NcPlane, NcPlaneCells, NcResult, NcStyle, NcTextLayout,
+};
+
+/// A plane that keeps a ring buffer of the styled lines scrolled off its top.
+///
+/// The C library discards the rows of a scrolling plane once they scroll off
+/// the top. The scrollback borrows the plane for as long as it lives, so that
+/// every write and scroll goes through its [`putstr`], [`scrollup`] and
+/// [`scrollup_child`] methods, and captures each row right before it's lost,
+/// keeping up to [`capacity`] lines.
+///
+/// The history can be [`search`]ed, and temporarily repainted over the plane
+/// with [`view_scrollback`]. Writing through the scrollback returns the plane
+/// to its live contents first.
+///
+/// Trailing blank cells without a background color are not kept.
+///
+/// # Example
+/// ```no_run
+/// # use libnotcurses_sys::*;
+/// # fn main() -> NcResult<()> {
+/// # let nc = unsafe { Nc::new()? };
+/// # let plane = unsafe { nc.stdplane() };
+/// plane.set_scrolling(true);
+/// let mut log = NcScrollback::new(plane, 1000);
+/// for n in 0..500 {
+///     log.putstr(&format!["line {}\n", n])?;
+/// }
+/// if let Some(&(index, _)) = log.search("line 42").first() {
+///     log.view_scrollback(log.scrollback_len() - index)?;
+/// }
+/// nc.render()?;
+/// # unsafe { nc.stop()? };
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`putstr`]: NcScrollback#method.putstr
+/// [`scrollup`]: NcScrollback#method.scrollup
+/// [`scrollup_child`]: NcScrollback#method.scrollup_child
+/// [`capacity`]: NcScrollback#method.capacity
+/// [`search`]: NcScrollback#method.search
+/// [`view_scrollback`]: NcScrollback#method.view_scrollback
+pub struct NcScrollback<'a> {
+    plane: &'a mut NcPlane,
+    history: History,
+    /// The number of lines the view is scrolled back.
+    offset: usize,
+    /// The live rows and cursor of the plane, while viewing the history.
+    live: Option<(Vec<Line>, (u32, u32))>,
+}
+
+/// The lines scrolled off, from the oldest to the newest.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct History {
+    lines: VecDeque<Line>,
+    capacity: usize,
+}
+
+/// A line of styled text.
+type Line = Vec<NcMarkupSpan>;
+
+/// # `NcScrollback` constructors
+impl<'a> NcScrollback<'a> {
+    /// New `NcScrollback` for the `plane`, that keeps up to `capacity` lines.
+    pub fn new(plane: &'a mut NcPlane, capacity: usize) -> Self {
+        Self { plane, history: History::new(capacity), offset: 0, live: None }
+    }
+
+    /// Returns the plane with its live contents, releasing it.
+    pub fn into_plane(mut self) -> NcResult<&'a mut NcPlane> {
+        self.view_scrollback(0)?;
+        Ok(self.plane)
+    }
+}
+
+/// # `NcScrollback` methods: history
+impl<'a> NcScrollback<'a> {
+    /// Returns the maximum number of lines kept.
+    pub fn capacity(&self) -> usize {
+        self.history.capacity
+    }
+
+    /// Sets the maximum number of lines kept, discarding the oldest ones
+    /// that don't fit.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.history.set_capacity(capacity);
+    }
+
+    /// Returns the number of lines in the history.
+    pub fn scrollback_len(&self) -> usize {
+        self.history.lines.len()
+    }
+
+    /// Returns the line at `index`, counting from the oldest one.
+    pub fn line(&self, index: usize) -> Option<&[NcMarkupSpan]> {
+        self.history.lines.get(index).map(|line| line.as_slice())
+    }
+
+    /// Returns the text of the line at `index`, counting from the oldest one.
+    pub fn line_text(&self, index: usize) -> Option<String> {
+        self.line(index).map(line_text)
+    }
+
+    /// Discards all the lines.
+    pub fn clear(&mut self) {
+        self.history.lines.clear();
+    }
+
+    /// Adds a line to the history, discarding the oldest one if it's full.
+    pub fn push_line(&mut self, line: Vec<NcMarkupSpan>) {
+        self.history.push(line);
+    }
+
+    /// Returns the matches of `pattern` in the history, from the oldest to
+    /// the newest, as `(index, byte_offset)` in the text of each line.
+    ///
+    /// The view offset that shows a matching line at the top of the plane is
+    /// [`scrollback_len`] minus its index.
+    ///
+    /// [`scrollback_len`]: NcScrollback#method.scrollback_len
+    pub fn search(&self, pattern: &str) -> Vec<(usize, usize)> {
+        self.history.search(pattern)
+    }
+}
+
+/// # `NcScrollback` methods: plane
+impl<'a> NcScrollback<'a> {
+    /// Returns a shared reference to the plane.
+    pub fn plane(&self) -> &NcPlane {
+        self.plane
+    }
+
+    /// Sets the styles used for the next writes.
+    ///
+    /// See [`NcPlane.set_styles`][NcPlane#method.set_styles].
+    pub fn set_styles(&mut self, styles: impl Into<NcStyle>) {
+        self.plane.set_styles(styles);
+    }
+
+    /// Sets the channels used for the next writes.
+    ///
+    /// See [`NcPlane.set_channels`][NcPlane#method.set_channels].
+    pub fn set_channels(&mut self, channels: impl Into<NcChannels>) {
+        self.plane.set_channels(channels);
+    }
+
+    /// Moves the cursor of the plane to the specified `y`, `x` coordinates,
+    /// returning it to its live contents first.
+    ///
+    /// See [`NcPlane.cursor_move_yx`][NcPlane#method.cursor_move_yx].
+    pub fn cursor_move_yx(&mut self, y: u32, x: u32) -> NcResult<()> {
+        self.view_scrollback(0)?;
+        self.plane.cursor_move_yx(y, x)
+    }
+
+    /// Returns the number of lines the view is scrolled back, 0 when the plane
+    /// shows its live contents.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Captures the top `n` rows of the plane into the history.
+    pub fn capture(&mut self, n: u32) -> NcResult<()> {
+        let (dim_y, dim_x) = self.plane.dim_yx();
+        let cells = NcPlaneCells::read(self.plane, 0, 0, n.min(dim_y), dim_x)?;
+        for (_, line) in cells.rows() {
+            self.history.push(line);
+        }
+        Ok(())
+    }
+
+    /// Writes a string to the current location of the plane, like
+    /// [`NcPlane.putstr`], capturing the rows that scroll off.
+    ///
+    /// [`NcPlane.putstr`]: NcPlane#method.putstr
+    pub fn putstr(&mut self, string: &str) -> NcResult<u32> {
+        self.view_scrollback(0)?;
+        let capturing = self.plane.scrolling_p() && !self.plane.autogrow_p();
+        let (dim_y, dim_x) = self.plane.dim_yx();
+        let mut cols = 0;
+        for egc in NcGraphemes::new(string) {
+            let (y, x) = self.plane.cursor_yx();
+            if capturing && y + 1 >= dim_y {
+                let newline = egc.contains('\n');
+                let width = if newline { 0 } else { NcTextLayout::strwidth(egc) };
+                if newline || (width > 0 && x + width > dim_x) {
+                    self.capture(1)?;
+                }
+            }
+            cols += self.plane.putstr(egc)?;
+        }
+        Ok(cols)
+    }
+
+    /// Same as [`putstr`], but it also puts a newline character at the end.
+    ///
+    /// [`putstr`]: NcScrollback#method.putstr
+    pub fn putstrln(&mut self, string: &str) -> NcResult<u32> {
+        let mut cols = self.putstr(string)?;
+        cols += self.putstr("\n")?;
+        Ok(cols)
+    }
+
+    /// Scrolls the plane up `n` rows, like [`NcPlane.scrollup`],
+    /// capturing them.
+    ///
+    /// [`NcPlane.scrollup`]: NcPlane#method.scrollup
+    pub fn scrollup(&mut self, n: u32) -> NcResult<u32> {
+        self.view_scrollback(0)?;
+        if self.plane.scrolling_p() {
+            self.capture(n)?;
+        }
+        self.plane.scrollup(n)
+    }
+
+    /// Scrolls the plane until `child` is no longer hidden beneath it,
+    /// like [`NcPlane.scrollup_child`], capturing the rows that scroll off.
+    ///
+    /// [`NcPlane.scrollup_child`]: NcPlane#method.scrollup_child
+    pub fn scrollup_child(&mut self, child: &NcPlane) -> NcResult<u32> {
+        self.view_scrollback(0)?;
+        let plane = &*self.plane;
+        let below = (child.abs_y() + child.dim_y() as i32) - (plane.abs_y() + plane.dim_y() as i32);
+        if plane.scrolling_p() && below > 0 {
+            self.capture(below as u32)?;
+        }
+        self.plane.scrollup_child(child)
+    }
+
+    /// Repaints the plane scrolled back `offset` lines into the history,
+    /// or with its live contents again when `offset` is 0.
+    ///
+    /// The offset is limited to the length of the history, and returned.
+    /// Lines wider than the plane are cut.
+    pub fn view_scrollback(&mut self, offset: usize) -> NcResult<usize> {
+        let lines = &self.history.lines;
+        let offset = offset.min(lines.len());
+        if offset == self.offset {
+            return Ok(offset);
+        }
+        let (dim_y, dim_x) = self.plane.dim_yx();
+        let (cursor, live) = match self.live.take() {
+            Some((live, cursor)) => (cursor, live),
+            None => {
+                let cells = NcPlaneCells::read(self.plane, 0, 0, dim_y, dim_x)?;
+                (
+                    self.plane.cursor_yx(),
+                    cells.rows().map(|(_, row)| row).collect(),
+                )
+            }
+        };
+
+        let top = lines.len() - offset;
+        let rows: Vec<&Line> = lines
+            .iter()
+            .chain(live.iter())
+            .skip(top)
+            .take(dim_y as usize)
+            .collect();
+        paint(self.plane, &rows, cursor)?;
+
+        self.offset = offset;
+        if offset > 0 {
+            self.live = Some((live, cursor));
+        }
+        Ok(offset)
+    }
+}
+
+impl History {
+    fn new(capacity: usize) -> Self {
+        Self { capacity, ..Default::default() }
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.lines.len() > capacity {
+            self.lines.pop_front();
+        }
+    }
+
+    fn push(&mut self, mut line: Line) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.lines.len() == self.capacity {
+            self.lines.pop_front();
+        }
+        trim_end(&mut line);
+        self.lines.push_back(line);
+    }
+
+    fn search(&self, pattern: &str) -> Vec<(usize, usize)> {
+        let mut matches = Vec::new();
+        if pattern.is_empty() {
+            return matches;
+        }
+        for (index, line) in self.lines.iter().enumerate() {
+            let text = line_text(line);
+            matches.extend(
+                text.match_indices(pattern)
+                    .map(|(offset, _)| (index, offset)),
+            );
+        }
+        matches
+    }
+}
+
+/// Erases the `plane` and writes the `rows` from the top, without scrolling,
+/// restoring its `cursor`, styles and channels afterwards.
+fn paint(plane: &mut NcPlane, rows: &[&Line], cursor: (u32, u32)) -> NcResult<()> {
+    let (styles, channels) = (plane.styles(), plane.channels());
+    let scrolling = plane.set_scrolling(false);
+    plane.erase();
+    let mut res = Ok(());
+    for (y, row) in rows.iter().enumerate() {
+        res = plane.cursor_move_yx(y as u32, 0);
+        if res.is_err() {
+            break;
+        }
+        for span in row.iter() {
+            plane.set_styles(span.styles);
+            plane.set_channels(span.channels);
+            // the rest of the line doesn't fit
+            if plane.putstr(&span.text).is_err() {
+                break;
+            }
+        }
+    }
+    plane.set_scrolling(scrolling);
+    plane.set_styles(styles);
+    plane.set_channels(channels);
+    res.and_then(|_| plane.cursor_move_yx(cursor.0, cursor.1))
+}
+
+/// Removes the trailing spaces without a background color.
+fn trim_end(line: &mut Line) {
+    while let Some(span) = line.last_mut() {
+        if !span.channels.bg_default_p() {
+            break;
+        }
+        let len = span.text.trim_end_matches(' ').len();
+        span.text.truncate(len);
+        if !span.text.is_empty() {
+            break;
+        }
+        line.pop();
+    }
+}
+
+/// Returns the text of a line.
+fn line_text(line: &[NcMarkupSpan]) -> String {
+    line.iter().map(|span| span.text.as_str()).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, vec};
+
+    fn span(text: &str, bg: Option<u32>) -> NcMarkupSpan {
+        let mut channels = NcChannels::default();
+        if let Some(bg) = bg {
+            channels.set_bg_rgb(bg);
+        }
+        NcMarkupSpan { text: text.into(), styles: NcStyle::None, channels }
+    }
+
+    #[test]
+    fn history() {
+        let mut history = History::new(3);
+        for n in 0..5 {
+            history.push(vec![span(&format!["line {n}"], None), span("   ", None)]);
+        }
+        assert_eq![history.lines.len(), 3];
+        assert_eq![line_text(&history.lines[0]), "line 2"];
+        assert_eq![history.lines[2].len(), 1];
+
+        history.push(vec![span("red ", None), span("  ", Some(0xFF0000))]);
+        assert_eq![line_text(&history.lines[2]), "red   "];
+
+        history.set_capacity(2);
+        assert_eq![line_text(&history.lines[0]), "line 4"];
+        history.push(vec![span("line 5", None), span("x", None)]);
+        assert_eq![history.search("line"), [(1, 0)]];
+        assert_eq![history.search("ne 5x"), [(1, 2)]];
+        assert![history.search("").is_empty()];
+
+        history.set_capacity(0);
+        history.push(vec![span("lost", None)]);
+        assert_eq![history.lines.len(), 0];
+    }
+}
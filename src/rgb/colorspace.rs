@@ -0,0 +1,317 @@
+//! Color spaces other than sRGB.
+
+use crate::{math, NcRgb};
+
+/// A color in the HSL (hue, saturation, lightness) color space.
+///
+/// - `h` is the hue angle in degrees, in the range `0.0..360.0`.
+/// - `s` is the saturation, in the range `0.0..=1.0`.
+/// - `l` is the lightness, in the range `0.0..=1.0`.
+///
+/// See also: [`NcHsv`], [`NcRgb::to_hsl`] and [`NcRgb::from_hsl`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NcHsl {
+    /// Hue, in degrees.
+    pub h: f32,
+    /// Saturation.
+    pub s: f32,
+    /// Lightness.
+    pub l: f32,
+}
+
+/// A color in the HSV (hue, saturation, value) color space.
+///
+/// - `h` is the hue angle in degrees, in the range `0.0..360.0`.
+/// - `s` is the saturation, in the range `0.0..=1.0`.
+/// - `v` is the value, in the range `0.0..=1.0`.
+///
+/// See also: [`NcHsl`], [`NcRgb::to_hsv`] and [`NcRgb::from_hsv`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NcHsv {
+    /// Hue, in degrees.
+    pub h: f32,
+    /// Saturation.
+    pub s: f32,
+    /// Value.
+    pub v: f32,
+}
+
+/// A color in the linear-light sRGB color space.
+///
+/// Unlike the gamma-encoded [`NcRgb`] components, these are proportional to
+/// the emitted light, which makes them suitable for physically correct mixing.
+///
+/// The components are in the range `0.0..=1.0`.
+///
+/// See also: [`NcRgb::to_linear`] and [`NcRgb::from_linear`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NcLinearRgb {
+    /// Red.
+    pub r: f32,
+    /// Green.
+    pub g: f32,
+    /// Blue.
+    pub b: f32,
+}
+
+/// A color in the [OKLab] perceptual color space.
+///
+/// Euclidean distances and linear interpolations in this space correspond
+/// closely to perceived differences, so it's the space used for blending.
+///
+/// - `l` is the perceived lightness, in the range `0.0..=1.0`.
+/// - `a` is the green-red axis, roughly in the range `-0.4..=0.4`.
+/// - `b` is the blue-yellow axis, roughly in the range `-0.4..=0.4`.
+///
+/// See also: [`NcRgb::to_oklab`] and [`NcRgb::from_oklab`].
+///
+/// [OKLab]: https://bottosson.github.io/posts/oklab/
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NcOklab {
+    /// Lightness.
+    pub l: f32,
+    /// Green-red axis.
+    pub a: f32,
+    /// Blue-yellow axis.
+    pub b: f32,
+}
+
+/// The color space used for interpolating between colors.
+///
+/// # Default
+/// *[`NcColorSpace::Oklab`]*
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NcColorSpace {
+    /// Gamma-encoded sRGB, the same as [`NcRgb`] components.
+    Srgb,
+    /// Linear-light sRGB ([`NcLinearRgb`]).
+    LinearRgb,
+    /// HSL ([`NcHsl`]), interpolating the hue through the shortest arc.
+    Hsl,
+    /// HSV ([`NcHsv`]), interpolating the hue through the shortest arc.
+    Hsv,
+    /// OKLab ([`NcOklab`]), perceptually uniform.
+    #[default]
+    Oklab,
+}
+
+impl NcHsl {
+    /// New `NcHsl` color.
+    pub const fn new(h: f32, s: f32, l: f32) -> Self {
+        Self { h, s, l }
+    }
+}
+
+impl NcHsv {
+    /// New `NcHsv` color.
+    pub const fn new(h: f32, s: f32, v: f32) -> Self {
+        Self { h, s, v }
+    }
+}
+
+impl NcLinearRgb {
+    /// New `NcLinearRgb` color.
+    pub const fn new(r: f32, g: f32, b: f32) -> Self {
+        Self { r, g, b }
+    }
+}
+
+impl NcOklab {
+    /// New `NcOklab` color.
+    pub const fn new(l: f32, a: f32, b: f32) -> Self {
+        Self { l, a, b }
+    }
+}
+
+// conversion functions ---------------------------------------------------------
+
+/// Converts a component in the range `0.0..=1.0` to `u8`, clamping it.
+pub(crate) fn unit_to_u8(c: f64) -> u8 {
+    math::round(c.clamp(0.0, 1.0) * 255.0) as u8
+}
+
+/// Decodes a gamma-encoded sRGB component into linear light.
+pub(crate) fn srgb_to_linear(c: u8) -> f64 {
+    let c = c as f64 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        math::powf((c + 0.055) / 1.055, 2.4)
+    }
+}
+
+/// Encodes a linear light component into a gamma-encoded sRGB component.
+pub(crate) fn linear_to_srgb(c: f64) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+    unit_to_u8(if c <= 0.003_130_8 { c * 12.92 } else { 1.055 * math::powf(c, 1.0 / 2.4) - 0.055 })
+}
+
+/// Normalizes a hue angle to the range `0.0..360.0`.
+pub(crate) fn normalize_hue(h: f64) -> f64 {
+    let h = h - 360.0 * math::floor(h / 360.0);
+    if h >= 360.0 {
+        0.0
+    } else {
+        h
+    }
+}
+
+/// Returns the hue in degrees, plus the max & min components, of an rgb color.
+fn hue_max_min(rgb: NcRgb) -> (f64, f64, f64) {
+    let (r, g, b) = rgb.into();
+    let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let d = max - min;
+    let h = if d == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / d)
+    } else if max == g {
+        60.0 * ((b - r) / d + 2.0)
+    } else {
+        60.0 * ((r - g) / d + 4.0)
+    };
+    (normalize_hue(h), max, min)
+}
+
+/// Returns the rgb components of a hue in degrees, with the given chroma and
+/// offset added to each component.
+fn hue_to_rgb(h: f64, chroma: f64, m: f64) -> NcRgb {
+    let h = normalize_hue(h) / 60.0;
+    let x = chroma * (1.0 - math::abs(h - 2.0 * math::floor(h / 2.0) - 1.0));
+    let (r, g, b) = match h as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    NcRgb::new(unit_to_u8(r + m), unit_to_u8(g + m), unit_to_u8(b + m))
+}
+
+pub(crate) fn rgb_to_hsl(rgb: NcRgb) -> NcHsl {
+    let (h, max, min) = hue_max_min(rgb);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+    let s = if d == 0.0 { 0.0 } else { d / (1.0 - math::abs(2.0 * l - 1.0)) };
+    NcHsl::new(h as f32, s as f32, l as f32)
+}
+
+pub(crate) fn hsl_to_rgb(hsl: NcHsl) -> NcRgb {
+    let (s, l) = (
+        (hsl.s as f64).clamp(0.0, 1.0),
+        (hsl.l as f64).clamp(0.0, 1.0),
+    );
+    let chroma = (1.0 - math::abs(2.0 * l - 1.0)) * s;
+    hue_to_rgb(hsl.h as f64, chroma, l - chroma / 2.0)
+}
+
+pub(crate) fn rgb_to_hsv(rgb: NcRgb) -> NcHsv {
+    let (h, max, min) = hue_max_min(rgb);
+    let s = if max == 0.0 { 0.0 } else { (max - min) / max };
+    NcHsv::new(h as f32, s as f32, max as f32)
+}
+
+pub(crate) fn hsv_to_rgb(hsv: NcHsv) -> NcRgb {
+    let (s, v) = (
+        (hsv.s as f64).clamp(0.0, 1.0),
+        (hsv.v as f64).clamp(0.0, 1.0),
+    );
+    let chroma = v * s;
+    hue_to_rgb(hsv.h as f64, chroma, v - chroma)
+}
+
+pub(crate) fn rgb_to_linear(rgb: NcRgb) -> NcLinearRgb {
+    let (r, g, b) = rgb.into();
+    NcLinearRgb::new(
+        srgb_to_linear(r) as f32,
+        srgb_to_linear(g) as f32,
+        srgb_to_linear(b) as f32,
+    )
+}
+
+pub(crate) fn linear_to_rgb(lin: NcLinearRgb) -> NcRgb {
+    NcRgb::new(
+        linear_to_srgb(lin.r as f64),
+        linear_to_srgb(lin.g as f64),
+        linear_to_srgb(lin.b as f64),
+    )
+}
+
+pub(crate) fn rgb_to_oklab(rgb: NcRgb) -> NcOklab {
+    let (r, g, b) = rgb.into();
+    let (r, g, b) = (srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b));
+
+    let l = math::cbrt(0.412_221_470_8 * r + 0.536_332_536_3 * g + 0.051_445_992_9 * b);
+    let m = math::cbrt(0.211_903_498_2 * r + 0.680_699_545_1 * g + 0.107_396_956_6 * b);
+    let s = math::cbrt(0.088_302_461_9 * r + 0.281_718_837_6 * g + 0.629_978_700_5 * b);
+
+    NcOklab::new(
+        (0.210_454_255_3 * l + 0.793_617_785_0 * m - 0.004_072_046_8 * s) as f32,
+        (1.977_998_495_1 * l - 2.428_592_205_0 * m + 0.450_593_709_9 * s) as f32,
+        (0.025_904_037_1 * l + 0.782_771_766_2 * m - 0.808_675_766_0 * s) as f32,
+    )
+}
+
+pub(crate) fn oklab_to_rgb(lab: NcOklab) -> NcRgb {
+    let (cl, ca, cb) = (lab.l as f64, lab.a as f64, lab.b as f64);
+
+    let l = cl + 0.396_337_777_4 * ca + 0.215_803_757_3 * cb;
+    let m = cl - 0.105_561_345_8 * ca - 0.063_854_172_8 * cb;
+    let s = cl - 0.089_484_177_5 * ca - 1.291_485_548_0 * cb;
+    let (l, m, s) = (l * l * l, m * m * m, s * s * s);
+
+    NcRgb::new(
+        linear_to_srgb(4.076_741_662_1 * l - 3.307_711_591_3 * m + 0.230_969_929_2 * s),
+        linear_to_srgb(-1.268_438_004_6 * l + 2.609_757_401_1 * m - 0.341_319_396_5 * s),
+        linear_to_srgb(-0.004_196_086_3 * l - 0.703_418_614_7 * m + 1.707_614_701_0 * s),
+    )
+}
+
+/// Returns the WCAG relative luminance of an rgb color, in the range `0.0..=1.0`.
+pub(crate) fn relative_luminance(rgb: NcRgb) -> f64 {
+    let (r, g, b) = rgb.into();
+    0.2126 * srgb_to_linear(r) + 0.7152 * srgb_to_linear(g) + 0.0722 * srgb_to_linear(b)
+}
+
+/// Interpolates a hue angle in degrees through the shortest arc.
+fn lerp_hue(h0: f32, h1: f32, t: f32) -> f32 {
+    let mut d = h1 - h0;
+    if d > 180.0 {
+        d -= 360.0;
+    } else if d < -180.0 {
+        d += 360.0;
+    }
+    normalize_hue((h0 + d * t) as f64) as f32
+}
+
+/// Interpolates between two rgb colors in the given color space.
+pub(crate) fn lerp_rgb(c0: NcRgb, c1: NcRgb, t: f32, space: NcColorSpace) -> NcRgb {
+    let l = |a: f32, b: f32| a + (b - a) * t;
+    match space {
+        NcColorSpace::Srgb => {
+            let (r0, g0, b0) = c0.into();
+            let (r1, g1, b1) = c1.into();
+            let c = |a: u8, b: u8| unit_to_u8(l(a as f32, b as f32) as f64 / 255.0);
+            NcRgb::new(c(r0, r1), c(g0, g1), c(b0, b1))
+        }
+        NcColorSpace::LinearRgb => {
+            let (a, b) = (rgb_to_linear(c0), rgb_to_linear(c1));
+            linear_to_rgb(NcLinearRgb::new(l(a.r, b.r), l(a.g, b.g), l(a.b, b.b)))
+        }
+        NcColorSpace::Hsl => {
+            let (a, b) = (rgb_to_hsl(c0), rgb_to_hsl(c1));
+            hsl_to_rgb(NcHsl::new(lerp_hue(a.h, b.h, t), l(a.s, b.s), l(a.l, b.l)))
+        }
+        NcColorSpace::Hsv => {
+            let (a, b) = (rgb_to_hsv(c0), rgb_to_hsv(c1));
+            hsv_to_rgb(NcHsv::new(lerp_hue(a.h, b.h, t), l(a.s, b.s), l(a.v, b.v)))
+        }
+        NcColorSpace::Oklab => {
+            let (a, b) = (rgb_to_oklab(c0), rgb_to_oklab(c1));
+            oklab_to_rgb(NcOklab::new(l(a.l, b.l), l(a.a, b.a), l(a.b, b.b)))
+        }
+    }
+}
@@ -0,0 +1,319 @@
+//! `NcRgb` & `NcRgba` methods and associated functions.
+
+use super::colorspace::{
+    hsl_to_rgb, hsv_to_rgb, lerp_rgb, linear_to_rgb, oklab_to_rgb, relative_luminance, rgb_to_hsl,
+    rgb_to_hsv, rgb_to_linear, rgb_to_oklab, unit_to_u8,
+};
+use crate::{NcColorSpace, NcHsl, NcHsv, NcLinearRgb, NcOklab, NcRgb, NcRgba};
+
+/// # `NcRgb` color space conversions
+impl NcRgb {
+    /// Converts to the HSL color space.
+    pub fn to_hsl(&self) -> NcHsl {
+        rgb_to_hsl(*self)
+    }
+
+    /// Converts from the HSL color space.
+    pub fn from_hsl(hsl: NcHsl) -> Self {
+        hsl_to_rgb(hsl)
+    }
+
+    /// Converts to the HSV color space.
+    pub fn to_hsv(&self) -> NcHsv {
+        rgb_to_hsv(*self)
+    }
+
+    /// Converts from the HSV color space.
+    pub fn from_hsv(hsv: NcHsv) -> Self {
+        hsv_to_rgb(hsv)
+    }
+
+    /// Converts to the linear-light sRGB color space.
+    pub fn to_linear(&self) -> NcLinearRgb {
+        rgb_to_linear(*self)
+    }
+
+    /// Converts from the linear-light sRGB color space.
+    pub fn from_linear(linear: NcLinearRgb) -> Self {
+        linear_to_rgb(linear)
+    }
+
+    /// Converts to the OKLab color space.
+    pub fn to_oklab(&self) -> NcOklab {
+        rgb_to_oklab(*self)
+    }
+
+    /// Converts from the OKLab color space.
+    ///
+    /// Colors outside of the sRGB gamut are clamped.
+    pub fn from_oklab(oklab: NcOklab) -> Self {
+        oklab_to_rgb(oklab)
+    }
+}
+
+/// # `NcRgb` blending & adjustments
+impl NcRgb {
+    /// Interpolates perceptually between `self` and `other`.
+    ///
+    /// `t` is clamped to the range `0.0..=1.0`, where `0.0` returns `self`
+    /// and `1.0` returns `other`. The interpolation happens in [`NcOklab`].
+    pub fn lerp(&self, other: impl Into<NcRgb>, t: f32) -> NcRgb {
+        self.lerp_in(other, t, NcColorSpace::Oklab)
+    }
+
+    /// Interpolates between `self` and `other` in the chosen color `space`.
+    ///
+    /// `t` is clamped to the range `0.0..=1.0`, where `0.0` returns `self`
+    /// and `1.0` returns `other`.
+    pub fn lerp_in(&self, other: impl Into<NcRgb>, t: f32, space: NcColorSpace) -> NcRgb {
+        lerp_rgb(*self, other.into(), t.clamp(0.0, 1.0), space)
+    }
+
+    /// Returns the WCAG relative luminance, in the range `0.0..=1.0`.
+    pub fn luminance(&self) -> f32 {
+        relative_luminance(*self) as f32
+    }
+
+    /// Returns the WCAG contrast ratio between `self` and `other`,
+    /// in the range `1.0..=21.0`.
+    ///
+    /// The WCAG recommends a ratio of at least `4.5` for normal text.
+    pub fn contrast_ratio(&self, other: impl Into<NcRgb>) -> f32 {
+        let (l1, l2) = (relative_luminance(*self), relative_luminance(other.into()));
+        let (hi, lo) = if l1 > l2 { (l1, l2) } else { (l2, l1) };
+        ((hi + 0.05) / (lo + 0.05)) as f32
+    }
+
+    /// Returns the color with its HSL lightness increased by `amount`.
+    pub fn lighten(&self, amount: f32) -> NcRgb {
+        let mut hsl = self.to_hsl();
+        hsl.l = (hsl.l + amount).clamp(0.0, 1.0);
+        Self::from_hsl(hsl)
+    }
+
+    /// Returns the color with its HSL lightness decreased by `amount`.
+    pub fn darken(&self, amount: f32) -> NcRgb {
+        self.lighten(-amount)
+    }
+
+    /// Returns the color with its HSL saturation increased by `amount`.
+    pub fn saturate(&self, amount: f32) -> NcRgb {
+        let mut hsl = self.to_hsl();
+        hsl.s = (hsl.s + amount).clamp(0.0, 1.0);
+        Self::from_hsl(hsl)
+    }
+
+    /// Returns the color with its HSL saturation decreased by `amount`.
+    pub fn desaturate(&self, amount: f32) -> NcRgb {
+        self.saturate(-amount)
+    }
+}
+
+/// # `NcRgba` color space conversions
+///
+/// The alpha component is not part of the color spaces,
+/// and it's kept apart.
+impl NcRgba {
+    /// Returns the rgb components, without the alpha.
+    pub fn to_rgb(&self) -> NcRgb {
+        let (r, g, b, _) = (*self).into();
+        NcRgb::new(r, g, b)
+    }
+
+    /// Returns the alpha component.
+    pub fn alpha(&self) -> u8 {
+        let (_, _, _, a) = (*self).into();
+        a
+    }
+
+    /// New `NcRgba` from rgb components and an `alpha`.
+    pub fn from_rgb(rgb: impl Into<NcRgb>, alpha: u8) -> Self {
+        let (r, g, b) = rgb.into().into();
+        (r, g, b, alpha).into()
+    }
+
+    /// Converts to the HSL color space, returning the alpha apart.
+    pub fn to_hsl(&self) -> (NcHsl, u8) {
+        (self.to_rgb().to_hsl(), self.alpha())
+    }
+
+    /// Converts from the HSL color space, with the provided `alpha`.
+    pub fn from_hsl(hsl: NcHsl, alpha: u8) -> Self {
+        Self::from_rgb(NcRgb::from_hsl(hsl), alpha)
+    }
+
+    /// Converts to the HSV color space, returning the alpha apart.
+    pub fn to_hsv(&self) -> (NcHsv, u8) {
+        (self.to_rgb().to_hsv(), self.alpha())
+    }
+
+    /// Converts from the HSV color space, with the provided `alpha`.
+    pub fn from_hsv(hsv: NcHsv, alpha: u8) -> Self {
+        Self::from_rgb(NcRgb::from_hsv(hsv), alpha)
+    }
+
+    /// Converts to the linear-light sRGB color space, returning the alpha apart.
+    pub fn to_linear(&self) -> (NcLinearRgb, u8) {
+        (self.to_rgb().to_linear(), self.alpha())
+    }
+
+    /// Converts from the linear-light sRGB color space, with the provided `alpha`.
+    pub fn from_linear(linear: NcLinearRgb, alpha: u8) -> Self {
+        Self::from_rgb(NcRgb::from_linear(linear), alpha)
+    }
+
+    /// Converts to the OKLab color space, returning the alpha apart.
+    pub fn to_oklab(&self) -> (NcOklab, u8) {
+        (self.to_rgb().to_oklab(), self.alpha())
+    }
+
+    /// Converts from the OKLab color space, with the provided `alpha`.
+    pub fn from_oklab(oklab: NcOklab, alpha: u8) -> Self {
+        Self::from_rgb(NcRgb::from_oklab(oklab), alpha)
+    }
+}
+
+/// # `NcRgba` blending & adjustments
+impl NcRgba {
+    /// Interpolates perceptually between `self` and `other`.
+    ///
+    /// The color is interpolated as in [`NcRgb::lerp`],
+    /// and the alpha is interpolated linearly.
+    pub fn lerp(&self, other: impl Into<NcRgba>, t: f32) -> NcRgba {
+        self.lerp_in(other, t, NcColorSpace::Oklab)
+    }
+
+    /// Interpolates between `self` and `other` in the chosen color `space`.
+    ///
+    /// The alpha is interpolated linearly.
+    pub fn lerp_in(&self, other: impl Into<NcRgba>, t: f32, space: NcColorSpace) -> NcRgba {
+        let other = other.into();
+        let t = t.clamp(0.0, 1.0);
+        let (a0, a1) = (self.alpha() as f32, other.alpha() as f32);
+        let alpha = a0 + (a1 - a0) * t;
+        Self::from_rgb(
+            self.to_rgb().lerp_in(other.to_rgb(), t, space),
+            (alpha + 0.5) as u8,
+        )
+    }
+
+    /// Composites `self` over the `background` color (Porter-Duff *over*).
+    pub fn over(&self, background: impl Into<NcRgba>) -> NcRgba {
+        let (sr, sg, sb, sa) = (*self).into();
+        let (dr, dg, db, da) = background.into().into();
+        let (sa, da) = (sa as f64 / 255.0, da as f64 / 255.0);
+
+        let out_a = sa + da * (1.0 - sa);
+        if out_a <= 0.0 {
+            return (0, 0, 0, 0).into();
+        }
+        let blend = |s: u8, d: u8| {
+            let (s, d) = (s as f64 / 255.0, d as f64 / 255.0);
+            unit_to_u8((s * sa + d * da * (1.0 - sa)) / out_a)
+        };
+        (
+            blend(sr, dr),
+            blend(sg, dg),
+            blend(sb, db),
+            unit_to_u8(out_a),
+        )
+            .into()
+    }
+
+    /// Returns the WCAG contrast ratio between the rgb components of `self`
+    /// and `other`, ignoring the alpha.
+    pub fn contrast_ratio(&self, other: impl Into<NcRgba>) -> f32 {
+        self.to_rgb().contrast_ratio(other.into().to_rgb())
+    }
+
+    /// Returns the color with its HSL lightness increased by `amount`.
+    pub fn lighten(&self, amount: f32) -> NcRgba {
+        Self::from_rgb(self.to_rgb().lighten(amount), self.alpha())
+    }
+
+    /// Returns the color with its HSL lightness decreased by `amount`.
+    pub fn darken(&self, amount: f32) -> NcRgba {
+        Self::from_rgb(self.to_rgb().darken(amount), self.alpha())
+    }
+
+    /// Returns the color with its HSL saturation increased by `amount`.
+    pub fn saturate(&self, amount: f32) -> NcRgba {
+        Self::from_rgb(self.to_rgb().saturate(amount), self.alpha())
+    }
+
+    /// Returns the color with its HSL saturation decreased by `amount`.
+    pub fn desaturate(&self, amount: f32) -> NcRgba {
+        Self::from_rgb(self.to_rgb().desaturate(amount), self.alpha())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{math::abs, NcHsl, NcHsv, NcRgb, NcRgba};
+
+    #[test]
+    fn hsl_hsv_roundtrip() {
+        let orange = NcRgb(0xff8800);
+
+        let hsl = orange.to_hsl();
+        assert![abs(hsl.h as f64 - 32.0) < 0.1];
+        assert_eq![hsl.s, 1.0];
+        assert_eq![orange, NcRgb::from_hsl(hsl)];
+        assert_eq![
+            NcRgb(0x00ff00),
+            NcRgb::from_hsl(NcHsl::new(120.0, 1.0, 0.5))
+        ];
+
+        let hsv = orange.to_hsv();
+        assert_eq![hsv.v, 1.0];
+        assert_eq![orange, NcRgb::from_hsv(hsv)];
+        assert_eq![
+            NcRgb(0x0000ff),
+            NcRgb::from_hsv(NcHsv::new(240.0, 1.0, 1.0))
+        ];
+    }
+
+    #[test]
+    fn linear_oklab_roundtrip() {
+        for rgb in [0x000000, 0xffffff, 0x123456, 0xff8800, 0x7f7f7f] {
+            let rgb = NcRgb(rgb);
+            assert_eq![rgb, NcRgb::from_linear(rgb.to_linear())];
+            assert_eq![rgb, NcRgb::from_oklab(rgb.to_oklab())];
+        }
+        let white = NcRgb(0xffffff).to_oklab();
+        assert![abs(white.l as f64 - 1.0) < 1e-4 && abs(white.a as f64) < 1e-4];
+    }
+
+    #[test]
+    fn blending() {
+        let (black, white) = (NcRgb(0x000000), NcRgb(0xffffff));
+        assert_eq![black, black.lerp(white, 0.0)];
+        assert_eq![white, black.lerp(white, 1.0)];
+        // perceptual midpoint is lighter than the sRGB average
+        let (r, _, _) = black.lerp(white, 0.5).into();
+        assert![r > 0x60 && r < 0x7f];
+
+        assert![abs(black.contrast_ratio(white) as f64 - 21.0) < 0.01];
+        assert_eq![1.0, white.contrast_ratio(white)];
+
+        let half_red = NcRgba::from((0xff, 0, 0, 0x80));
+        let blue = NcRgba::from((0, 0, 0xff, 0xff));
+        assert_eq![NcRgba::from((0x80, 0, 0x7f, 0xff)), half_red.over(blue)];
+        assert_eq![blue, NcRgba::from((0, 0, 0, 0)).over(blue)];
+    }
+
+    #[test]
+    fn adjustments() {
+        let grey = NcRgb(0x808080);
+        assert_eq![NcRgb(0xffffff), grey.lighten(1.0)];
+        assert_eq![NcRgb(0x000000), grey.darken(1.0)];
+        let (r, g, b) = NcRgb(0xff8800).desaturate(1.0).into();
+        assert![r == g && g == b];
+        let (r, g, _) = grey.saturate(0.5).into();
+        assert![r > g];
+        assert_eq![
+            0x40,
+            NcRgba::from((0x80, 0x80, 0x80, 0x40)).lighten(0.1).alpha()
+        ];
+    }
+}
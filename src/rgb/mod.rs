@@ -1,7 +1,12 @@
-//!
+//! `NcRgb`, `NcRgba` and other color spaces.
 
 use c_api::{NcRgb_u32, NcRgba_u32};
 
+mod colorspace;
+mod methods;
+
+pub use colorspace::{NcColorSpace, NcHsl, NcHsv, NcLinearRgb, NcOklab};
+
 /// 24 bits broken into 3x RGB components.
 ///
 /// Unlike with [`NcChannel`], operations involving `NcRgb`
@@ -32,7 +37,7 @@ impl NcRgb {
 /// ## Diagram
 ///
 /// ```txt
-/// AAAAAAAA RRRRRRRR GGGGGGGG BBBBBBBB
+/// RRRRRRRR GGGGGGGG BBBBBBBB AAAAAAAA
 /// ```
 /// `type in C: no data type`
 ///
@@ -47,17 +52,17 @@ impl NcRgba {
     /// New const RGBA color.
     pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
         Self(
-            (a as NcRgba_u32) << 24
-                | (r as NcRgba_u32) << 16
-                | (g as NcRgba_u32) << 8
-                | b as NcRgba_u32,
+            (r as NcRgba_u32) << 24
+                | (g as NcRgba_u32) << 16
+                | (b as NcRgba_u32) << 8
+                | a as NcRgba_u32,
         )
     }
 }
 mod core_impls {
     use super::{
         c_api::{NcRgb_u32, NcRgba_u32},
-        NcRgb, NcRgba,
+        NcHsl, NcHsv, NcLinearRgb, NcOklab, NcRgb, NcRgba,
     };
     use core::fmt;
 
@@ -121,6 +126,47 @@ mod core_impls {
         }
     }
 
+    impl From<NcHsl> for NcRgb {
+        fn from(hsl: NcHsl) -> Self {
+            Self::from_hsl(hsl)
+        }
+    }
+    impl From<NcRgb> for NcHsl {
+        fn from(rgb: NcRgb) -> Self {
+            rgb.to_hsl()
+        }
+    }
+    impl From<NcHsv> for NcRgb {
+        fn from(hsv: NcHsv) -> Self {
+            Self::from_hsv(hsv)
+        }
+    }
+    impl From<NcRgb> for NcHsv {
+        fn from(rgb: NcRgb) -> Self {
+            rgb.to_hsv()
+        }
+    }
+    impl From<NcLinearRgb> for NcRgb {
+        fn from(linear: NcLinearRgb) -> Self {
+            Self::from_linear(linear)
+        }
+    }
+    impl From<NcRgb> for NcLinearRgb {
+        fn from(rgb: NcRgb) -> Self {
+            rgb.to_linear()
+        }
+    }
+    impl From<NcOklab> for NcRgb {
+        fn from(oklab: NcOklab) -> Self {
+            Self::from_oklab(oklab)
+        }
+    }
+    impl From<NcRgb> for NcOklab {
+        fn from(rgb: NcRgb) -> Self {
+            rgb.to_oklab()
+        }
+    }
+
     //
 
     crate::from_primitive![NcRgba, NcRgba_u32];
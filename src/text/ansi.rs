@@ -0,0 +1,362 @@
+//! `NcAnsi`
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use crate::{NcChannels, NcMarkupSpan, NcRgb, NcStyle};
+
+/// A parser of text with ANSI escape sequences, as written to a terminal by
+/// programs like `cargo`, `git diff --color` or `ls --color`.
+///
+/// SGR sequences (*Select Graphic Rendition*) are mapped onto [`NcStyle`]s
+/// and [`NcChannels`]: the 16 basic colors and the 256 color palette become
+/// palette indices, and truecolor becomes RGB. Bold, italic, underline,
+/// undercurl, struck and reverse video are supported, along with their resets.
+///
+/// Every other control sequence, like cursor movements, window titles or
+/// hyperlinks, is stripped, together with the control characters other than
+/// newlines, tabs and carriage returns.
+///
+/// The parser keeps the current styles between calls to [`parse`], and also
+/// any incomplete escape sequence at the end of the text, so that output can
+/// be fed in chunks as it arrives.
+///
+/// # Example
+/// ```
+/// # use libnotcurses_sys::*;
+/// let mut ansi = NcAnsi::new(NcStyle::None, NcChannels::default());
+/// let spans = ansi.parse("\x1b[1;32m   Compiling\x1b[0m foo v0.1.0\x1b[");
+/// assert_eq![spans.len(), 2];
+/// assert_eq![spans[0].styles, NcStyle::Bold];
+/// assert_eq![spans[0].channels.fg_palindex(), 2];
+/// assert_eq![spans[1].text, " foo v0.1.0"];
+///
+/// let spans = ansi.parse("4mdone");
+/// assert_eq![spans[0].styles, NcStyle::Underline];
+/// ```
+///
+/// [`parse`]: NcAnsi#method.parse
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NcAnsi {
+    styles: NcStyle,
+    channels: NcChannels,
+    reverse: bool,
+    /// An incomplete escape sequence at the end of the last text parsed.
+    pending: String,
+}
+
+/// # `NcAnsi` constructors
+impl NcAnsi {
+    /// New `NcAnsi` parser, starting with the given `styles` and `channels`.
+    pub fn new(styles: impl Into<NcStyle>, channels: impl Into<NcChannels>) -> Self {
+        Self {
+            styles: styles.into(),
+            channels: channels.into(),
+            reverse: false,
+            pending: String::new(),
+        }
+    }
+}
+
+/// # `NcAnsi` methods
+impl NcAnsi {
+    /// Returns the current styles.
+    pub fn styles(&self) -> NcStyle {
+        self.styles
+    }
+
+    /// Returns the current channels, reversed if reverse video is enabled.
+    pub fn channels(&self) -> NcChannels {
+        let mut channels = self.channels;
+        if self.reverse {
+            channels.reverse();
+        }
+        channels
+    }
+
+    /// Returns `true` if there's an incomplete escape sequence pending.
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Parses the `text`, returning the spans of styled text without escapes.
+    pub fn parse(&mut self, text: &str) -> Vec<NcMarkupSpan> {
+        let mut input = core::mem::take(&mut self.pending);
+        input.push_str(text);
+
+        let mut spans: Vec<NcMarkupSpan> = Vec::new();
+        let mut run = String::new();
+        let mut i = 0;
+        while let Some(c) = input[i..].chars().next() {
+            if c != '\x1b' {
+                if !c.is_control() || matches![c, '\n' | '\t' | '\r'] {
+                    run.push(c);
+                }
+                i += c.len_utf8();
+                continue;
+            }
+            match sequence(&input[i..]) {
+                None => {
+                    self.pending = input[i..].into();
+                    break;
+                }
+                Some((len, sgr)) => {
+                    if let Some(params) = sgr {
+                        self.push(&mut spans, &mut run);
+                        self.sgr(params);
+                    }
+                    i += len;
+                }
+            }
+        }
+        self.push(&mut spans, &mut run);
+        spans
+    }
+
+    /// Returns the `text` without escape sequences nor control characters,
+    /// other than newlines, tabs and carriage returns.
+    pub fn strip(text: &str) -> String {
+        Self::default()
+            .parse(text)
+            .into_iter()
+            .map(|s| s.text)
+            .collect()
+    }
+
+    /// Adds the `run` of text to the spans with the current styles, emptying it.
+    fn push(&self, spans: &mut Vec<NcMarkupSpan>, run: &mut String) {
+        if run.is_empty() {
+            return;
+        }
+        let (styles, channels) = (self.styles(), self.channels());
+        match spans.last_mut() {
+            Some(last) if last.styles == styles && last.channels == channels => {
+                last.text.push_str(run);
+                run.clear();
+            }
+            _ => spans.push(NcMarkupSpan { text: core::mem::take(run), styles, channels }),
+        }
+    }
+
+    /// Applies the parameters of an SGR sequence.
+    fn sgr(&mut self, params: &str) {
+        let params: Vec<&str> = params.split(';').collect();
+        let num = |i: usize| params.get(i).and_then(|p| p.parse::<u32>().ok());
+
+        let mut i = 0;
+        while i < params.len() {
+            let sub: Vec<u32> = params[i]
+                .split(':')
+                .map(|n| n.parse().unwrap_or(0))
+                .collect();
+            i += 1;
+            match sub[0] {
+                0 => {
+                    self.styles = NcStyle::None;
+                    self.channels = NcChannels::default();
+                    self.reverse = false;
+                }
+                1 => self.styles |= NcStyle::Bold,
+                3 => self.styles |= NcStyle::Italic,
+                4 => {
+                    self.styles &= !(NcStyle::Underline | NcStyle::Undercurl);
+                    match sub.get(1) {
+                        Some(0) => (),
+                        Some(3) => self.styles |= NcStyle::Undercurl,
+                        _ => self.styles |= NcStyle::Underline,
+                    }
+                }
+                7 => self.reverse = true,
+                9 => self.styles |= NcStyle::Struck,
+                22 => self.styles &= !NcStyle::Bold,
+                23 => self.styles &= !NcStyle::Italic,
+                24 => self.styles &= !(NcStyle::Underline | NcStyle::Undercurl),
+                27 => self.reverse = false,
+                29 => self.styles &= !NcStyle::Struck,
+                n @ (30..=37 | 90..=97) => _ = self.channels.set_fg_palindex(basic(n) as u8),
+                n @ (40..=47 | 100..=107) => _ = self.channels.set_bg_palindex(basic(n) as u8),
+                39 => _ = self.channels.set_fg_default(),
+                49 => _ = self.channels.set_bg_default(),
+                n @ (38 | 48 | 58) => {
+                    // either `38:5:n`, `38:2:[cs:]r:g:b`, `38;5;n` or `38;2;r;g;b`
+                    let color = if sub.len() > 1 {
+                        match (sub[1], sub.len()) {
+                            (5, 3) => Some(Color::Palette(sub[2])),
+                            (2, 5) => Some(Color::Rgb(sub[2], sub[3], sub[4])),
+                            (2, 6..) => Some(Color::Rgb(sub[3], sub[4], sub[5])),
+                            _ => None,
+                        }
+                    } else {
+                        match num(i) {
+                            Some(5) => {
+                                i += 2;
+                                num(i - 1).map(Color::Palette)
+                            }
+                            Some(2) => {
+                                i += 4;
+                                match (num(i - 3), num(i - 2), num(i - 1)) {
+                                    (Some(r), Some(g), Some(b)) => Some(Color::Rgb(r, g, b)),
+                                    _ => None,
+                                }
+                            }
+                            _ => None,
+                        }
+                    };
+                    // the underline color (58) is not supported
+                    if n != 58 {
+                        if let Some(color) = color {
+                            color.set(&mut self.channels, n == 38);
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Returns the palette index of one of the 16 basic SGR colors.
+fn basic(n: u32) -> u32 {
+    match n {
+        30..=37 => n - 30,
+        40..=47 => n - 40,
+        90..=97 => n - 90 + 8,
+        _ => n - 100 + 8,
+    }
+}
+
+/// An extended SGR color.
+#[derive(Clone, Copy, Debug)]
+enum Color {
+    Palette(u32),
+    Rgb(u32, u32, u32),
+}
+
+impl Color {
+    fn set(self, channels: &mut NcChannels, fg: bool) {
+        match (self, fg) {
+            (Color::Palette(n), _) if n > 255 => (),
+            (Color::Palette(n), true) => _ = channels.set_fg_palindex(n as u8),
+            (Color::Palette(n), false) => _ = channels.set_bg_palindex(n as u8),
+            (Color::Rgb(r, g, b), _) if r > 255 || g > 255 || b > 255 => (),
+            (Color::Rgb(r, g, b), fg) => {
+                let rgb = NcRgb(r << 16 | g << 8 | b);
+                if fg {
+                    channels.set_fg_rgb(rgb);
+                } else {
+                    channels.set_bg_rgb(rgb);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the length of the escape sequence at the start of `s`, and its
+/// parameters if it's an SGR sequence, or `None` if it's incomplete.
+fn sequence(s: &str) -> Option<(usize, Option<&str>)> {
+    let bytes = s.as_bytes();
+    match *bytes.get(1)? {
+        // CSI: parameter bytes, intermediate bytes, and a final byte
+        b'[' => {
+            for (j, &b) in bytes.iter().enumerate().skip(2) {
+                match b {
+                    0x20..=0x3F => (),
+                    0x40..=0x7E => {
+                        let params = &s[2..j];
+                        let sgr = b == b'm'
+                            && params
+                                .bytes()
+                                .all(|p| matches![p, b'0'..=b'9' | b';' | b':']);
+                        return Some((j + 1, sgr.then_some(params)));
+                    }
+                    // malformed, drops what has been read
+                    _ => return Some((j, None)),
+                }
+            }
+            None
+        }
+        // OSC, DCS, SOS, PM & APC: strings ended by ST, or BEL
+        b']' | b'P' | b'X' | b'^' | b'_' => {
+            let body = &s[2..];
+            let end = body.find(&['\x07', '\x1b', '\u{9c}'][..])?;
+            let len = match body[end..].chars().next()? {
+                '\x1b' if body[end + 1..].is_empty() => return None,
+                '\x1b' if body[end + 1..].starts_with('\\') => 2,
+                // another escape sequence starts
+                '\x1b' => 0,
+                c => c.len_utf8(),
+            };
+            Some((2 + end + len, None))
+        }
+        // nF sequences: intermediate bytes and a final byte
+        0x20..=0x2F => {
+            let j = 2 + bytes[2..]
+                .iter()
+                .take_while(|b| (0x20..=0x2F).contains(*b))
+                .count();
+            match bytes.get(j)? {
+                0x30..=0x7E => Some((j + 1, None)),
+                _ => Some((j, None)),
+            }
+        }
+        0x30..=0x7E => Some((2, None)),
+        // a lone escape
+        _ => Some((1, None)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    fn texts(spans: &[NcMarkupSpan]) -> Vec<&str> {
+        spans.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    #[test]
+    fn sgr() {
+        let mut ansi = NcAnsi::new(NcStyle::None, NcChannels::default());
+        let spans = ansi.parse("\x1b[1;31mred\x1b[22;39m, \x1b[38;5;208m256\x1b[m");
+        assert_eq![texts(&spans), ["red", ", ", "256"]];
+        assert_eq![spans[0].styles, NcStyle::Bold];
+        assert_eq![spans[0].channels.fg_palindex(), 1];
+        assert_eq![spans[1].styles, NcStyle::None];
+        assert![spans[1].channels.fg_default_p()];
+        assert_eq![spans[2].channels.fg_palindex(), 208];
+        assert_eq![ansi.channels(), NcChannels::default()];
+
+        let spans = ansi.parse("\x1b[48;2;1;2;3;97ma\x1b[38:2::4:5:6;4:3mb\x1b[24;7mc");
+        assert_eq![spans[0].channels.bg_rgb(), NcRgb(0x010203)];
+        assert_eq![spans[0].channels.fg_palindex(), 15];
+        assert_eq![spans[1].channels.fg_rgb(), NcRgb(0x040506)];
+        assert_eq![spans[1].styles, NcStyle::Undercurl];
+        assert_eq![spans[2].styles, NcStyle::None];
+        assert_eq![spans[2].channels.fg_rgb(), NcRgb(0x010203)];
+        assert_eq![spans[2].channels.bg_rgb(), NcRgb(0x040506)];
+    }
+
+    #[test]
+    fn strip() {
+        assert_eq![
+            NcAnsi::strip("\x1b[2K\x1b[1Ga\x1b]8;;http://x\x1b\\b\x1b]8;;\x07\x1b(Bc\x07\x1b[?25l\r\n\td\x1bx"),
+            "abc\r\n\td"
+        ];
+        assert_eq![NcAnsi::strip("\x1b[\u{1F600}x"), "\u{1F600}x"];
+        assert_eq![NcAnsi::strip("\x1b]0;title\x1b[1mx"), "x"];
+    }
+
+    #[test]
+    fn chunks() {
+        let mut ansi = NcAnsi::default();
+        assert![ansi.parse("a\x1b[3").len() == 1 && ansi.is_pending()];
+        let spans = ansi.parse("2mb\x1b]0;title");
+        assert_eq![texts(&spans), ["b"]];
+        assert_eq![spans[0].channels.fg_palindex(), 2];
+        assert![ansi.parse("\x1b").is_empty() && ansi.is_pending()];
+        assert_eq![texts(&ansi.parse("\\c")), ["c"]];
+        assert![!ansi.is_pending()];
+    }
+}
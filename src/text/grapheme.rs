@@ -0,0 +1,176 @@
+//! `NcGraphemes`
+
+/// An iterator over the grapheme clusters of a string.
+///
+/// It follows the extended grapheme cluster rules of [UAX #29] closely
+/// enough for laying out text in a terminal: it keeps together `CR LF`,
+/// base characters with their combining marks, variation selectors and
+/// emoji modifiers, emoji joined by `ZWJ`, pairs of regional indicators
+/// (flags), and Hangul syllables made of conjoining jamo.
+///
+/// [UAX #29]: https://www.unicode.org/reports/tr29/
+///
+/// # Example
+/// ```
+/// # use libnotcurses_sys::NcGraphemes;
+/// let g: Vec<&str> = NcGraphemes::new("e\u{301}🇪🇸👩‍🔬!").collect();
+/// assert_eq![g, ["e\u{301}", "🇪🇸", "👩‍🔬", "!"]];
+/// ```
+#[derive(Clone, Debug)]
+pub struct NcGraphemes<'a> {
+    rest: &'a str,
+}
+
+impl<'a> NcGraphemes<'a> {
+    /// New `NcGraphemes` iterator over `string`.
+    pub fn new(string: &'a str) -> Self {
+        Self { rest: string }
+    }
+
+    /// Returns the part of the string not iterated yet.
+    pub fn as_str(&self) -> &'a str {
+        self.rest
+    }
+}
+
+impl<'a> Iterator for NcGraphemes<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let mut chars = self.rest.char_indices();
+        let (_, first) = chars.next()?;
+        let mut end = first.len_utf8();
+
+        if first == '\r' && self.rest[end..].starts_with('\n') {
+            end += 1;
+        } else if !first.is_control() {
+            let mut prev = first;
+            let mut regional = is_regional(first) as u32;
+            for (i, c) in chars {
+                let joins = !c.is_control()
+                    && (is_extend(c)
+                        || c == ZWJ
+                        || prev == ZWJ
+                        || hangul_joins(prev, c)
+                        || (is_regional(c) && regional % 2 == 1));
+                if !joins {
+                    break;
+                }
+                regional = if is_regional(c) { regional + 1 } else { 0 };
+                prev = c;
+                end = i + c.len_utf8();
+            }
+        }
+        let (egc, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some(egc)
+    }
+}
+
+const ZWJ: char = '\u{200D}';
+
+/// Returns `true` for characters that extend the previous grapheme cluster.
+fn is_extend(c: char) -> bool {
+    let c = c as u32;
+    // combining marks in the Indic blocks
+    if (0x0900..=0x0DFF).contains(&c) {
+        return matches!(c & 0x7F, 0x00..=0x03 | 0x3A..=0x3C | 0x3E..=0x4F | 0x51..=0x57 | 0x62..=0x63);
+    }
+    EXTEND
+        .binary_search_by(|&(lo, hi)| {
+            if hi < c {
+                core::cmp::Ordering::Less
+            } else if lo > c {
+                core::cmp::Ordering::Greater
+            } else {
+                core::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Sorted ranges of combining marks, variation selectors, emoji modifiers…
+#[rustfmt::skip]
+const EXTEND: &[(u32, u32)] = &[
+    (0x0300, 0x036F), (0x0483, 0x0489), (0x0591, 0x05BD), (0x05BF, 0x05BF),
+    (0x05C1, 0x05C2), (0x05C4, 0x05C5), (0x05C7, 0x05C7), (0x0610, 0x061A),
+    (0x064B, 0x065F), (0x0670, 0x0670), (0x06D6, 0x06DC), (0x06DF, 0x06E4),
+    (0x06E7, 0x06E8), (0x06EA, 0x06ED), (0x0711, 0x0711), (0x0730, 0x074A),
+    (0x07A6, 0x07B0), (0x07EB, 0x07F3), (0x0E31, 0x0E31), (0x0E34, 0x0E3A),
+    (0x0E47, 0x0E4E), (0x0EB1, 0x0EB1), (0x0EB4, 0x0EBC), (0x0EC8, 0x0ECE),
+    (0x0F18, 0x0F19), (0x0F35, 0x0F35), (0x0F37, 0x0F37), (0x0F39, 0x0F39),
+    (0x0F71, 0x0F84), (0x0F86, 0x0F87), (0x0F8D, 0x0FBC), (0x102B, 0x103E),
+    (0x1056, 0x1059), (0x135D, 0x135F), (0x1712, 0x1715), (0x17B4, 0x17D3),
+    (0x180B, 0x180D), (0x1AB0, 0x1AFF), (0x1B00, 0x1B04), (0x1B34, 0x1B44),
+    (0x1DC0, 0x1DFF), (0x200C, 0x200C), (0x20D0, 0x20F0), (0x2CEF, 0x2CF1),
+    (0x2DE0, 0x2DFF), (0x302A, 0x302F), (0x3099, 0x309A), (0xA66F, 0xA672),
+    (0xA674, 0xA67D), (0xA69E, 0xA69F), (0xA8E0, 0xA8F1), (0xFB1E, 0xFB1E),
+    (0xFE00, 0xFE0F), (0xFE20, 0xFE2F), (0xFF9E, 0xFF9F), (0x1F3FB, 0x1F3FF),
+    (0xE0020, 0xE007F), (0xE0100, 0xE01EF),
+];
+
+fn is_regional(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Hangul {
+    L,
+    V,
+    T,
+    Lv,
+    Lvt,
+}
+
+fn hangul(c: char) -> Option<Hangul> {
+    match c as u32 {
+        0x1100..=0x115F | 0xA960..=0xA97C => Some(Hangul::L),
+        0x1160..=0x11A7 | 0xD7B0..=0xD7C6 => Some(Hangul::V),
+        0x11A8..=0x11FF | 0xD7CB..=0xD7FB => Some(Hangul::T),
+        c @ 0xAC00..=0xD7A3 if (c - 0xAC00) % 28 == 0 => Some(Hangul::Lv),
+        0xAC00..=0xD7A3 => Some(Hangul::Lvt),
+        _ => None,
+    }
+}
+
+/// Returns `true` if the Hangul jamo `c` continues the syllable ending in `prev`.
+fn hangul_joins(prev: char, c: char) -> bool {
+    use Hangul::*;
+    matches!(
+        (hangul(prev), hangul(c)),
+        (Some(L), Some(L | V | Lv | Lvt)) | (Some(Lv | V), Some(V | T)) | (Some(Lvt | T), Some(T))
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::NcGraphemes;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    fn split(s: &str) -> Vec<&str> {
+        NcGraphemes::new(s).collect()
+    }
+
+    #[test]
+    fn clusters() {
+        assert_eq![split(""), [""; 0]];
+        assert_eq![split("ab\r\nc"), ["a", "b", "\r\n", "c"]];
+        assert_eq![
+            split("n\u{303}o\u{308}\u{301}"),
+            ["n\u{303}", "o\u{308}\u{301}"]
+        ];
+        assert_eq![
+            split("\u{1F44D}\u{1F3FD}\u{2764}\u{FE0F}"),
+            ["\u{1F44D}\u{1F3FD}", "\u{2764}\u{FE0F}"]
+        ];
+        assert_eq![split("👨‍👩‍👧x"), ["👨‍👩‍👧", "x"]];
+        assert_eq![split("🇪🇸🇫🇷🇩"), ["🇪🇸", "🇫🇷", "🇩"]];
+        assert_eq![
+            split("\u{1112}\u{1161}\u{11AB}한"),
+            ["\u{1112}\u{1161}\u{11AB}", "한"]
+        ];
+        assert_eq![split("\t\u{301}"), ["\t", "\u{301}"]];
+    }
+}
@@ -0,0 +1,603 @@
+//! `NcTextLayout`
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec, vec::Vec};
+
+use super::NcGraphemes;
+use crate::NcCell;
+
+/// Lays out text in lines of a maximum number of columns.
+///
+/// The text is segmented into grapheme clusters with [`NcGraphemes`], and
+/// their widths are measured with [`ncstrwidth`] by default.
+///
+/// Newlines always break the text into paragraphs. Then each paragraph can
+/// be word wrapped ([`NcTextWrap`]), breaking lines at spaces and optionally
+/// after hyphens and at soft hyphens (`U+00AD`). Words longer than a line
+/// are broken between grapheme clusters.
+///
+/// Lines that still don't fit are cut, or truncated with an ellipsis
+/// ([`NcTextTruncate`]), and then aligned ([`NcTextAlign`]).
+///
+/// The resulting [`NcTextLine`]s can be placed with [`NcPlane.putstr_yx`],
+/// or written directly with [`NcPlane.puttext_layout`].
+///
+/// # Example
+/// ```
+/// # use libnotcurses_sys::*;
+/// let layout = NcTextLayout::new(12).align(NcTextAlign::Right);
+/// let lines = layout.lines("The quick brown fox jumps");
+/// assert_eq![lines[0].text, "The quick"];
+/// assert_eq![lines[0].x, 3];
+/// assert_eq![lines.len(), 3];
+/// ```
+///
+/// [`ncstrwidth`]: crate::c_api::ncstrwidth
+/// [`NcPlane.putstr_yx`]: crate::NcPlane#method.putstr_yx
+/// [`NcPlane.puttext_layout`]: crate::NcPlane#method.puttext_layout
+#[derive(Clone, Debug)]
+pub struct NcTextLayout {
+    width: u32,
+    wrap: NcTextWrap,
+    align: NcTextAlign,
+    truncate: NcTextTruncate,
+    hyphenate: bool,
+    max_lines: Option<u32>,
+    ellipsis: String,
+    measure: fn(&str) -> u32,
+}
+
+/// A laid out line of text, returned by [`NcTextLayout.lines`].
+///
+/// [`NcTextLayout.lines`]: NcTextLayout#method.lines
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NcTextLine {
+    /// The text of the line, without a newline.
+    pub text: String,
+    /// The column where the line starts, relative to the layout region.
+    pub x: u32,
+    /// The number of columns occupied by the text.
+    pub cols: u32,
+}
+
+/// How to wrap paragraphs longer than a line.
+///
+/// # Default
+/// *[`NcTextWrap::Greedy`]*
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NcTextWrap {
+    /// Doesn't wrap, one line per paragraph.
+    None,
+    /// Fits as many words as possible in each line.
+    #[default]
+    Greedy,
+    /// Minimizes the differences in length between lines.
+    Balanced,
+}
+
+/// The horizontal alignment of the lines.
+///
+/// # Default
+/// *[`NcTextAlign::Left`]*
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NcTextAlign {
+    /// Aligned to the left.
+    #[default]
+    Left,
+    /// Aligned to the right.
+    Right,
+    /// Centered.
+    Center,
+    /// Stretched to both sides by widening the spaces, except the last line
+    /// of each paragraph, which is aligned to the left.
+    Justify,
+}
+
+/// Where to remove text from lines that don't fit, replacing it with an
+/// ellipsis.
+///
+/// # Default
+/// *[`NcTextTruncate::None`]*
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NcTextTruncate {
+    /// The line is just cut, without an ellipsis.
+    #[default]
+    None,
+    /// Removes text from the beginning.
+    Head,
+    /// Removes text from the middle.
+    Middle,
+    /// Removes text from the end.
+    Tail,
+}
+
+/// # `NcTextLayout` constructors
+impl NcTextLayout {
+    /// New `NcTextLayout` with lines of up to `width` columns.
+    ///
+    /// A width of 0 means the lines are never wrapped nor truncated.
+    pub fn new(width: u32) -> Self {
+        Self {
+            width,
+            wrap: NcTextWrap::default(),
+            align: NcTextAlign::default(),
+            truncate: NcTextTruncate::default(),
+            hyphenate: true,
+            max_lines: None,
+            ellipsis: "…".into(),
+            measure: Self::strwidth,
+        }
+    }
+
+    /// Sets the maximum number of columns of each line.
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets the wrapping mode.
+    pub fn wrap(mut self, wrap: NcTextWrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Sets the alignment of the lines.
+    pub fn align(mut self, align: NcTextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Sets where to truncate lines that don't fit.
+    pub fn truncate(mut self, truncate: NcTextTruncate) -> Self {
+        self.truncate = truncate;
+        self
+    }
+
+    /// Sets whether lines may break after hyphens and at soft hyphens.
+    ///
+    /// A soft hyphen is shown as `-` only when a line breaks there.
+    ///
+    /// Default: *true*.
+    pub fn hyphenate(mut self, hyphenate: bool) -> Self {
+        self.hyphenate = hyphenate;
+        self
+    }
+
+    /// Sets the maximum number of lines.
+    ///
+    /// When there are more lines, the last one ends with the ellipsis,
+    /// unless the truncation is [`NcTextTruncate::None`].
+    pub fn max_lines(mut self, max_lines: Option<u32>) -> Self {
+        self.max_lines = max_lines;
+        self
+    }
+
+    /// Sets the ellipsis used for truncating.
+    ///
+    /// Default: *`"…"`*.
+    pub fn ellipsis(mut self, ellipsis: &str) -> Self {
+        self.ellipsis = ellipsis.into();
+        self
+    }
+
+    /// Sets the function used for measuring the columns of a grapheme cluster.
+    ///
+    /// Default: *[`NcTextLayout::strwidth`]*.
+    pub fn measure(mut self, measure: fn(&str) -> u32) -> Self {
+        self.measure = measure;
+        self
+    }
+}
+
+/// # `NcTextLayout` methods
+impl NcTextLayout {
+    /// Returns the number of columns of a grapheme cluster, as measured by
+    /// [`NcCell::strwidth_valid`], with control characters occupying none.
+    pub fn strwidth(egc: &str) -> u32 {
+        if egc.chars().any(char::is_control) {
+            0
+        } else {
+            NcCell::strwidth_valid(egc).1
+        }
+    }
+
+    /// Returns the number of columns of `text`, as measured by the layout.
+    pub fn cols(&self, text: &str) -> u32 {
+        NcGraphemes::new(text).map(self.measure).sum()
+    }
+
+    /// Lays out the `text` in lines.
+    pub fn lines(&self, text: &str) -> Vec<NcTextLine> {
+        let paragraphs: Vec<Vec<Piece>> = text
+            .split('\n')
+            .map(|p| self.pieces(p.strip_suffix('\r').unwrap_or(p)))
+            .collect();
+
+        // the ranges of pieces of each line: (paragraph, start, end)
+        let mut ranges = Vec::new();
+        for (p, pieces) in paragraphs.iter().enumerate() {
+            let breaks = match self.wrap {
+                _ if self.width == 0 => vec![pieces.len()],
+                NcTextWrap::None => vec![pieces.len()],
+                NcTextWrap::Greedy => self.greedy(pieces),
+                NcTextWrap::Balanced => self.balanced(pieces),
+            };
+            let mut start = 0;
+            for end in breaks {
+                ranges.push((p, start, end));
+                start = end;
+            }
+        }
+        let overflow = match self.max_lines {
+            Some(max) if ranges.len() > max as usize => {
+                ranges.truncate(max as usize);
+                true
+            }
+            _ => false,
+        };
+
+        let count = ranges.len();
+        let mut lines = Vec::with_capacity(count);
+        for (i, &(p, start, end)) in ranges.iter().enumerate() {
+            let pieces = &paragraphs[p][start..end];
+            let last_of_paragraph = end == paragraphs[p].len() || (overflow && i + 1 == count);
+            let justify = self.align == NcTextAlign::Justify && !last_of_paragraph;
+            let (mut text, mut cols) = self.render(pieces, justify);
+
+            if overflow && i + 1 == count && self.truncate != NcTextTruncate::None {
+                let ecols = self.cols(&self.ellipsis);
+                if self.width != 0 && cols + ecols > self.width {
+                    (text, cols) = self.cut(&text, self.width.saturating_sub(ecols));
+                }
+                text.push_str(&self.ellipsis);
+                cols += ecols;
+            }
+            if self.width != 0 && cols > self.width {
+                (text, cols) = self.truncated(&text);
+            }
+
+            let room = self.width.saturating_sub(cols);
+            let x = match self.align {
+                NcTextAlign::Left | NcTextAlign::Justify => 0,
+                NcTextAlign::Right => room,
+                NcTextAlign::Center => room / 2,
+            };
+            lines.push(NcTextLine { text, x, cols });
+        }
+        lines
+    }
+
+    /// Splits a paragraph into pieces separated by break opportunities,
+    /// none of them wider than a line.
+    fn pieces(&self, paragraph: &str) -> Vec<Piece> {
+        let mut pieces = Vec::new();
+        let mut piece = Piece::default();
+        let mut spaces = 0;
+
+        for egc in NcGraphemes::new(paragraph) {
+            if egc == " " || egc == "\t" {
+                spaces += 1;
+                continue;
+            }
+            if spaces > 0 {
+                piece.gap = Gap::Space(spaces);
+                pieces.push(core::mem::take(&mut piece));
+                spaces = 0;
+            }
+            if egc == "\u{AD}" {
+                if self.hyphenate && !piece.text.is_empty() {
+                    piece.gap = Gap::Soft;
+                    pieces.push(core::mem::take(&mut piece));
+                }
+                continue;
+            }
+            piece.text.push_str(egc);
+            piece.cols += (self.measure)(egc);
+            if self.hyphenate && egc == "-" && piece.text.len() > 1 {
+                piece.gap = Gap::Hard;
+                pieces.push(core::mem::take(&mut piece));
+            }
+        }
+        match pieces.last_mut() {
+            Some(last) if piece.text.is_empty() => last.gap = Gap::End,
+            _ => pieces.push(piece),
+        }
+
+        if self.width == 0 || self.wrap == NcTextWrap::None {
+            return pieces;
+        }
+        // break the pieces that are too wide
+        let mut fitting = Vec::with_capacity(pieces.len());
+        for piece in pieces {
+            if piece.cols + piece.gap.hyphen_cols() <= self.width {
+                fitting.push(piece);
+                continue;
+            }
+            let mut chunk = Piece::default();
+            for egc in NcGraphemes::new(&piece.text) {
+                let cols = (self.measure)(egc);
+                if chunk.cols + cols > self.width && !chunk.text.is_empty() {
+                    chunk.gap = Gap::Join;
+                    fitting.push(core::mem::take(&mut chunk));
+                }
+                chunk.text.push_str(egc);
+                chunk.cols += cols;
+            }
+            chunk.gap = piece.gap;
+            fitting.push(chunk);
+        }
+        fitting
+    }
+
+    /// Returns the end of each line, fitting as many pieces as possible.
+    fn greedy(&self, pieces: &[Piece]) -> Vec<usize> {
+        let mut breaks = Vec::new();
+        let mut start = 0;
+        while start < pieces.len() {
+            let mut end = start + 1;
+            while end < pieces.len() && line_cols(&pieces[start..=end]) <= self.width {
+                end += 1;
+            }
+            breaks.push(end);
+            start = end;
+        }
+        breaks
+    }
+
+    /// Returns the end of each line, minimizing the sum of the squares of the
+    /// free columns of each line but the last.
+    fn balanced(&self, pieces: &[Piece]) -> Vec<usize> {
+        let n = pieces.len();
+        // cost[i]: the minimum cost of laying out pieces[i..], and its first break
+        let mut cost = vec![(0_u64, n); n + 1];
+        for i in (0..n).rev() {
+            cost[i] = (u64::MAX, i + 1);
+            for j in i + 1..=n {
+                let cols = line_cols(&pieces[i..j]);
+                if cols > self.width && j > i + 1 {
+                    break;
+                }
+                let free = self.width.saturating_sub(cols) as u64;
+                let line = if j == n { 0 } else { free * free };
+                let total = line.saturating_add(cost[j].0);
+                if total < cost[i].0 {
+                    cost[i] = (total, j);
+                }
+            }
+        }
+        let mut breaks = Vec::new();
+        let mut i = 0;
+        while i < n {
+            i = cost[i].1;
+            breaks.push(i);
+        }
+        breaks
+    }
+
+    /// Joins the pieces of a line, widening the spaces if `justify`.
+    fn render(&self, pieces: &[Piece], justify: bool) -> (String, u32) {
+        let cols = line_cols(pieces);
+        let gaps = pieces[..pieces.len().saturating_sub(1)]
+            .iter()
+            .filter(|p| matches![p.gap, Gap::Space(_)])
+            .count() as u32;
+        let extra = if justify && gaps > 0 { self.width.saturating_sub(cols) } else { 0 };
+
+        let mut text = String::new();
+        let mut gap = 0;
+        for (i, piece) in pieces.iter().enumerate() {
+            text.push_str(&piece.text);
+            if i + 1 == pieces.len() {
+                if piece.gap == Gap::Soft {
+                    text.push('-');
+                }
+            } else if let Gap::Space(n) = piece.gap {
+                let widen = extra / gaps + (gap < extra % gaps) as u32;
+                text.extend(core::iter::repeat(' ').take((n + widen) as usize));
+                gap += 1;
+            }
+        }
+        (text, cols + extra)
+    }
+
+    /// Returns the longest prefix of `text` that fits in `width` columns.
+    fn cut(&self, text: &str, width: u32) -> (String, u32) {
+        let mut cut = String::new();
+        let mut cols = 0;
+        for egc in NcGraphemes::new(text) {
+            let w = (self.measure)(egc);
+            if cols + w > width {
+                break;
+            }
+            cut.push_str(egc);
+            cols += w;
+        }
+        (cut, cols)
+    }
+
+    /// Returns the longest suffix of `text` that fits in `width` columns.
+    fn cut_head(&self, text: &str, width: u32) -> (String, u32) {
+        let egcs: Vec<&str> = NcGraphemes::new(text).collect();
+        let mut cols = 0;
+        let mut start = egcs.len();
+        while start > 0 {
+            let w = (self.measure)(egcs[start - 1]);
+            if cols + w > width {
+                break;
+            }
+            cols += w;
+            start -= 1;
+        }
+        (egcs[start..].concat(), cols)
+    }
+
+    /// Truncates a line that doesn't fit, as configured.
+    fn truncated(&self, text: &str) -> (String, u32) {
+        let ecols = self.cols(&self.ellipsis);
+        if self.truncate == NcTextTruncate::None || ecols > self.width {
+            return self.cut(text, self.width);
+        }
+        let room = self.width - ecols;
+        match self.truncate {
+            NcTextTruncate::Head => {
+                let (tail, cols) = self.cut_head(text, room);
+                (self.ellipsis.clone() + &tail, cols + ecols)
+            }
+            NcTextTruncate::Middle => {
+                let (head, hcols) = self.cut(text, room - room / 2);
+                let (tail, tcols) = self.cut_head(text, room / 2);
+                (head + &self.ellipsis + &tail, hcols + ecols + tcols)
+            }
+            _ => {
+                let (head, cols) = self.cut(text, room);
+                (head + &self.ellipsis, cols + ecols)
+            }
+        }
+    }
+}
+
+/// A piece of text that can't be broken, followed by a break opportunity.
+#[derive(Clone, Debug, Default)]
+struct Piece {
+    text: String,
+    cols: u32,
+    gap: Gap,
+}
+
+/// What follows a [`Piece`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Gap {
+    /// The end of the paragraph.
+    #[default]
+    End,
+    /// A number of spaces, dropped when breaking the line.
+    Space(u32),
+    /// A hyphen, which is the end of the piece.
+    Hard,
+    /// A soft hyphen, shown only when breaking the line.
+    Soft,
+    /// Nothing, inside a word too long for a line.
+    Join,
+}
+
+impl Gap {
+    /// The columns added at the end of a line that breaks here.
+    fn hyphen_cols(self) -> u32 {
+        (self == Gap::Soft) as u32
+    }
+}
+
+/// Returns the columns of a line made of `pieces`.
+fn line_cols(pieces: &[Piece]) -> u32 {
+    let mut cols = 0;
+    for (i, piece) in pieces.iter().enumerate() {
+        cols += piece.cols;
+        if i + 1 == pieces.len() {
+            cols += piece.gap.hyphen_cols();
+        } else if let Gap::Space(n) = piece.gap {
+            cols += n;
+        }
+    }
+    cols
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    // two columns for the Hangul jamo and the CJK characters and above, and
+    // one for the rest, avoiding the C library
+    fn chars(egc: &str) -> u32 {
+        if egc.starts_with(|c: char| ('\u{1100}'..='\u{115F}').contains(&c) || c > '\u{2E80}') {
+            2
+        } else {
+            1
+        }
+    }
+
+    fn texts(layout: &NcTextLayout, text: &str) -> Vec<String> {
+        layout.lines(text).into_iter().map(|l| l.text).collect()
+    }
+
+    #[test]
+    fn wrapping() {
+        let l = NcTextLayout::new(10).measure(chars);
+        assert_eq![
+            texts(&l, "aaa bb cc dddd\n\nee"),
+            ["aaa bb cc", "dddd", "", "ee"]
+        ];
+        assert_eq![texts(&l, "abcdefghijklmn op"), ["abcdefghij", "klmn op"]];
+        assert_eq![texts(&l, "日本語のテキスト"), ["日本語のテ", "キスト"]];
+        assert_eq![texts(&l, "  indented  "), ["  indented"]];
+
+        let l = NcTextLayout::new(6)
+            .measure(chars)
+            .wrap(NcTextWrap::Balanced);
+        assert_eq![texts(&l, "aaa bb cc dd"), ["aaa bb", "cc dd"]];
+        let l = l.wrap(NcTextWrap::Greedy).width(7);
+        assert_eq![
+            texts(&l, "aaaa bb cc ddddddd"),
+            ["aaaa bb", "cc", "ddddddd"]
+        ];
+        let l = l.wrap(NcTextWrap::Balanced);
+        assert_eq![
+            texts(&l, "aaaa bb cc ddddddd"),
+            ["aaaa", "bb cc", "ddddddd"]
+        ];
+    }
+
+    #[test]
+    fn hyphenation() {
+        let l = NcTextLayout::new(8).measure(chars);
+        assert_eq![texts(&l, "well-known fact"), ["well-", "known", "fact"]];
+        assert_eq![texts(&l, "hyphen\u{AD}ation"), ["hyphen-", "ation"]];
+        assert_eq![texts(&l, "hy\u{AD}phen"), ["hyphen"]];
+        assert_eq![texts(&l, "-5 -6"), ["-5 -6"]];
+
+        let l = l.hyphenate(false);
+        assert_eq![texts(&l, "well-known fact"), ["well-kno", "wn fact"]];
+    }
+
+    #[test]
+    fn alignment() {
+        let l = NcTextLayout::new(9)
+            .measure(chars)
+            .align(NcTextAlign::Center);
+        let lines = l.lines("ab cd\nefg");
+        assert_eq![(lines[0].x, lines[0].cols), (2, 5)];
+        assert_eq![(lines[1].x, lines[1].cols), (3, 3)];
+
+        let l = l.align(NcTextAlign::Right);
+        assert_eq![l.lines("abc")[0].x, 6];
+
+        let l = l.align(NcTextAlign::Justify);
+        let lines = l.lines("a bb c ddddd ee");
+        assert_eq![lines[0].text, "a   bb  c"];
+        assert_eq![lines[0].cols, 9];
+        assert_eq![lines[1].text, "ddddd ee"];
+    }
+
+    #[test]
+    fn truncation() {
+        let l = NcTextLayout::new(7).measure(chars).wrap(NcTextWrap::None);
+        assert_eq![texts(&l, "abcdefghij"), ["abcdefg"]];
+
+        let l = l.truncate(NcTextTruncate::Tail);
+        assert_eq![texts(&l, "abcdefghij"), ["abcdef…"]];
+        assert_eq![texts(&l, "abcdefg"), ["abcdefg"]];
+        let l = l.truncate(NcTextTruncate::Head);
+        assert_eq![texts(&l, "abcdefghij"), ["…efghij"]];
+        let l = l.truncate(NcTextTruncate::Middle).ellipsis("..");
+        assert_eq![texts(&l, "abcdefghij"), ["abc..ij"]];
+
+        let l = NcTextLayout::new(7)
+            .measure(chars)
+            .truncate(NcTextTruncate::Tail)
+            .max_lines(Some(2));
+        assert_eq![texts(&l, "one two three four"), ["one two", "three…"]];
+        assert_eq![texts(&l, "one two threeee four"), ["one two", "threee…"]];
+    }
+}
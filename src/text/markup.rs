@@ -0,0 +1,397 @@
+//! `NcMarkup`
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, vec::Vec};
+
+use crate::{c_api, NcChannels, NcError, NcResult, NcRgb, NcStyle, NcTextLayout};
+
+/// A string with inline style markup, parsed into spans of styled text.
+///
+/// Tags are enclosed in square brackets, and contain any number of
+/// space-separated attributes:
+///
+/// - `b` or `bold`, `i` or `italic`, `u` or `underline`, `uc` or `undercurl`,
+///   and `s` or `struck`, which add that [`NcStyle`].
+/// - `fg=` and `bg=`, followed by a color in the form `#rrggbb` or `#rgb`,
+///   a palette index from `0` to `255`, or `default`.
+///
+/// `[/]` closes the last open tag, and `[/attribute]` closes the last open
+/// tag containing that attribute, e.g. `[/b]` or `[/fg]`. Tags still open at
+/// the end are closed implicitly. A literal `[` is written as `[[`.
+///
+/// The attributes are applied over the base styles and channels, which are
+/// the plane's current ones when printing with [`NcPlane.putstr_markup`].
+///
+/// # Example
+/// ```
+/// # use libnotcurses_sys::*;
+/// # fn main() -> NcResult<()> {
+/// let markup = NcMarkup::parse(
+///     "[b fg=#ff8800]Warning:[/] disk [i]almost[/i] full",
+///     NcStyle::None,
+///     NcChannels::new(),
+/// )?;
+/// assert_eq![markup.spans().len(), 4];
+/// assert_eq![markup.spans()[0].styles, NcStyle::Bold];
+/// assert_eq![markup.spans()[0].channels.fg_rgb(), NcRgb(0xFF8800)];
+/// assert_eq![markup.text(), "Warning: disk almost full"];
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`NcPlane.putstr_markup`]: crate::NcPlane#method.putstr_markup
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NcMarkup {
+    spans: Vec<NcMarkupSpan>,
+}
+
+/// A run of text sharing the same styles and channels, part of an [`NcMarkup`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NcMarkupSpan {
+    /// The text, without markup.
+    pub text: String,
+    /// The styles of the text.
+    pub styles: NcStyle,
+    /// The channels of the text.
+    pub channels: NcChannels,
+}
+
+/// # `NcMarkup` constructors
+impl NcMarkup {
+    /// Parses the `markup`, applying its attributes over the base `styles`
+    /// and `channels`.
+    ///
+    /// It is an error if a tag is not closed, if it contains an unknown
+    /// attribute or color, or if a closing tag doesn't match any open tag.
+    pub fn parse(
+        markup: &str,
+        styles: impl Into<NcStyle>,
+        channels: impl Into<NcChannels>,
+    ) -> NcResult<Self> {
+        let (base_styles, base_channels) = (styles.into(), channels.into());
+        let (mut styles, mut channels) = (base_styles, base_channels);
+        let mut groups: Vec<Vec<Attr>> = Vec::new();
+        let mut spans = Vec::new();
+        let mut text = String::new();
+
+        let mut rest = markup;
+        while let Some(open) = rest.find('[') {
+            text.push_str(&rest[..open]);
+            rest = &rest[open + 1..];
+            if let Some(after) = rest.strip_prefix('[') {
+                text.push('[');
+                rest = after;
+                continue;
+            }
+            let close = rest
+                .find(']')
+                .ok_or_else(|| markup_error("unclosed tag", rest))?;
+            let tag = rest[..close].trim();
+            rest = &rest[close + 1..];
+
+            if let Some(name) = tag.strip_prefix('/') {
+                let name = name.trim();
+                let index = if name.is_empty() {
+                    groups.len().checked_sub(1)
+                } else {
+                    let key = Attr::parse_key(name)
+                        .ok_or_else(|| markup_error("unknown attribute", name))?;
+                    groups
+                        .iter()
+                        .rposition(|g| g.iter().any(|a| a.key() == key))
+                };
+                groups.remove(index.ok_or_else(|| markup_error("unmatched closing tag", tag))?);
+            } else {
+                groups.push(
+                    tag.split_whitespace()
+                        .map(Attr::parse)
+                        .collect::<NcResult<_>>()?,
+                );
+            }
+
+            push_span(&mut spans, &mut text, styles, channels);
+            (styles, channels) = (base_styles, base_channels);
+            for attr in groups.iter().flatten() {
+                attr.apply(&mut styles, &mut channels);
+            }
+        }
+        text.push_str(rest);
+        push_span(&mut spans, &mut text, styles, channels);
+
+        Ok(Self { spans })
+    }
+}
+
+/// # `NcMarkup` methods
+impl NcMarkup {
+    /// Returns the spans of styled text.
+    pub fn spans(&self) -> &[NcMarkupSpan] {
+        &self.spans
+    }
+
+    /// Returns the text without markup.
+    pub fn text(&self) -> String {
+        self.spans.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    /// Lays out the text in lines, returning the horizontal offset and the
+    /// spans of each line.
+    ///
+    /// The `layout` must not justify nor truncate the lines, and won't
+    /// hyphenate them.
+    pub(crate) fn lines(&self, layout: NcTextLayout) -> Vec<(u32, Vec<NcMarkupSpan>)> {
+        let plain = self.text();
+        let layout = layout.hyphenate(false);
+
+        // the byte offset in `plain` where each span ends
+        let ends: Vec<usize> = self
+            .spans
+            .iter()
+            .scan(0, |end, s| {
+                *end += s.text.len();
+                Some(*end)
+            })
+            .collect();
+
+        let mut pos = 0;
+        let mut lines = Vec::new();
+        for (i, line) in layout.lines(&plain).into_iter().enumerate() {
+            if i > 0 {
+                // skip the spaces dropped at the break, and the newline
+                let rest = &plain[pos..];
+                let trimmed = rest.trim_start_matches(&[' ', '\t'][..]);
+                pos += rest.len() - trimmed.len();
+                if trimmed.starts_with("\r\n") {
+                    pos += 2;
+                } else if trimmed.starts_with('\n') {
+                    pos += 1;
+                }
+            }
+
+            let mut spans: Vec<NcMarkupSpan> = Vec::new();
+            let mut current = None;
+            for c in line.text.chars() {
+                while plain[pos..].starts_with('\u{AD}') {
+                    pos += '\u{AD}'.len_utf8();
+                }
+                let index = ends.iter().position(|&e| e > pos).unwrap_or(ends.len() - 1);
+                match plain[pos..].chars().next() {
+                    Some(p) if p == c || (p == '\t' && c == ' ') => pos += p.len_utf8(),
+                    _ => (),
+                }
+                match spans.last_mut() {
+                    Some(span) if current == Some(index) => span.text.push(c),
+                    _ => {
+                        let span = &self.spans[index];
+                        spans.push(NcMarkupSpan {
+                            text: c.into(),
+                            styles: span.styles,
+                            channels: span.channels,
+                        });
+                        current = Some(index);
+                    }
+                }
+            }
+            lines.push((line.x, spans));
+        }
+        lines
+    }
+}
+
+/// Adds the `text` to the spans, emptying it, merging it with the last span
+/// if they share the same `styles` and `channels`.
+fn push_span(
+    spans: &mut Vec<NcMarkupSpan>,
+    text: &mut String,
+    styles: NcStyle,
+    channels: NcChannels,
+) {
+    if text.is_empty() {
+        return;
+    }
+    match spans.last_mut() {
+        Some(last) if last.styles == styles && last.channels == channels => {
+            last.text.push_str(text);
+            text.clear();
+        }
+        _ => spans.push(NcMarkupSpan { text: core::mem::take(text), styles, channels }),
+    }
+}
+
+fn markup_error(reason: &str, at: &str) -> NcError {
+    NcError::with_msg(
+        c_api::NCRESULT_ERR,
+        &format!["NcMarkup::parse(): {} {:?}", reason, at],
+    )
+}
+
+/// An attribute inside of a markup tag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Attr {
+    Style(NcStyle),
+    Fg(Color),
+    Bg(Color),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Color {
+    Default,
+    Rgb(NcRgb),
+    Palette(u8),
+}
+
+impl Attr {
+    fn parse(attr: &str) -> NcResult<Self> {
+        if let Some((key, value)) = attr.split_once('=') {
+            let color = Color::parse(value).ok_or_else(|| markup_error("invalid color", value))?;
+            match key {
+                "fg" => Ok(Attr::Fg(color)),
+                "bg" => Ok(Attr::Bg(color)),
+                _ => Err(markup_error("unknown attribute", key)),
+            }
+        } else {
+            Self::parse_key(attr)
+                .filter(|a| matches![a, Attr::Style(_)])
+                .ok_or_else(|| markup_error("unknown attribute", attr))
+        }
+    }
+
+    /// Parses the name of an attribute, as used in closing tags.
+    fn parse_key(name: &str) -> Option<Self> {
+        Some(match name {
+            "b" | "bold" => Attr::Style(NcStyle::Bold),
+            "i" | "italic" => Attr::Style(NcStyle::Italic),
+            "u" | "underline" => Attr::Style(NcStyle::Underline),
+            "uc" | "undercurl" => Attr::Style(NcStyle::Undercurl),
+            "s" | "struck" => Attr::Style(NcStyle::Struck),
+            "fg" => Attr::Fg(Color::Default),
+            "bg" => Attr::Bg(Color::Default),
+            _ => return None,
+        })
+    }
+
+    /// Returns the attribute with any color argument removed.
+    fn key(self) -> Self {
+        match self {
+            Attr::Style(_) => self,
+            Attr::Fg(_) => Attr::Fg(Color::Default),
+            Attr::Bg(_) => Attr::Bg(Color::Default),
+        }
+    }
+
+    fn apply(self, styles: &mut NcStyle, channels: &mut NcChannels) {
+        match self {
+            Attr::Style(style) => *styles |= style,
+            Attr::Fg(Color::Default) => _ = channels.set_fg_default(),
+            Attr::Fg(Color::Rgb(rgb)) => _ = channels.set_fg_rgb(rgb),
+            Attr::Fg(Color::Palette(index)) => _ = channels.set_fg_palindex(index),
+            Attr::Bg(Color::Default) => _ = channels.set_bg_default(),
+            Attr::Bg(Color::Rgb(rgb)) => _ = channels.set_bg_rgb(rgb),
+            Attr::Bg(Color::Palette(index)) => _ = channels.set_bg_palindex(index),
+        }
+    }
+}
+
+impl Color {
+    fn parse(value: &str) -> Option<Self> {
+        if value == "default" {
+            return Some(Color::Default);
+        }
+        let Some(hex) = value.strip_prefix('#') else {
+            return value.parse().ok().map(Color::Palette);
+        };
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let rgb = u32::from_str_radix(hex, 16).ok()?;
+        match hex.len() {
+            6 => Some(Color::Rgb(NcRgb(rgb))),
+            3 => {
+                let (r, g, b) = ((rgb >> 8) & 0xF, (rgb >> 4) & 0xF, rgb & 0xF);
+                Some(Color::Rgb(NcRgb(
+                    ((r * 0x11) << 16) | ((g * 0x11) << 8) | (b * 0x11),
+                )))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::NcTextAlign;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    fn parse(markup: &str) -> NcResult<NcMarkup> {
+        NcMarkup::parse(markup, NcStyle::None, NcChannels::new())
+    }
+
+    fn texts(markup: &NcMarkup) -> Vec<&str> {
+        markup.spans().iter().map(|s| s.text.as_str()).collect()
+    }
+
+    #[test]
+    fn spans() {
+        let m = parse("[b fg=#ff8800]Warning:[/] disk [i]almost[/i] full").unwrap();
+        assert_eq![texts(&m), ["Warning:", " disk ", "almost", " full"]];
+        assert_eq![m.spans()[0].styles, NcStyle::Bold];
+        assert_eq![m.spans()[0].channels.fg_rgb(), NcRgb(0xFF8800)];
+        assert_eq![m.spans()[1].styles, NcStyle::None];
+        assert_eq![m.spans()[1].channels, NcChannels::new()];
+        assert_eq![m.spans()[3].channels, NcChannels::new()];
+        assert_eq![m.spans()[2].styles, NcStyle::Italic];
+
+        // closing by name, nesting and merging
+        let m = parse("[b][u bg=#123]a[/b]b[/]c[i][/i]c").unwrap();
+        assert_eq![texts(&m), ["a", "b", "cc"]];
+        assert_eq![m.spans()[0].styles, NcStyle::Bold | NcStyle::Underline];
+        assert_eq![m.spans()[1].styles, NcStyle::Underline];
+        assert_eq![m.spans()[1].channels.bg_rgb(), NcRgb(0x112233)];
+        assert_eq![m.spans()[2].styles, NcStyle::None];
+
+        let m = NcMarkup::parse(
+            "[fg=default bg=7]x[[y]",
+            NcStyle::Struck,
+            NcChannels::from_rgb(1, 2),
+        )
+        .unwrap();
+        assert_eq![m.text(), "x[y]"];
+        assert_eq![m.spans()[0].styles, NcStyle::Struck];
+        assert![m.spans()[0].channels.fg_default_p()];
+        assert_eq![m.spans()[0].channels.bg_palindex(), 7];
+
+        assert![parse("").unwrap().spans().is_empty()];
+        assert![parse("[b").is_err()];
+        assert![parse("[bold blink]").is_err()];
+        assert![parse("[fg=#12345]").is_err()];
+        assert![parse("[b]x[/i]").is_err()];
+        assert![parse("x[/]").is_err()];
+    }
+
+    #[test]
+    fn lines() {
+        let m = parse("one [b]two three[/b]\tfour\n\n  five").unwrap();
+        let layout = NcTextLayout::new(10)
+            .align(NcTextAlign::Right)
+            .measure(|_| 1);
+        let lines = m.lines(layout);
+        let texts: Vec<(u32, Vec<&str>)> = lines
+            .iter()
+            .map(|(x, spans)| (*x, spans.iter().map(|s| s.text.as_str()).collect()))
+            .collect();
+        assert_eq![
+            texts,
+            [
+                (3, vec!["one ", "two"]),
+                (0, vec!["three", " four"]),
+                (10, vec![]),
+                (4, vec!["  five"]),
+            ]
+        ];
+        assert_eq![lines[0].1[1].styles, NcStyle::Bold];
+        assert_eq![lines[1].1[1].styles, NcStyle::None];
+    }
+}
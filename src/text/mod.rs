@@ -0,0 +1,11 @@
+//! `NcTextLayout`, `NcGraphemes`, `NcMarkup`, `NcAnsi` & associated types.
+
+mod ansi;
+mod grapheme;
+mod layout;
+mod markup;
+
+pub use ansi::NcAnsi;
+pub use grapheme::NcGraphemes;
+pub use layout::{NcTextAlign, NcTextLayout, NcTextLine, NcTextTruncate, NcTextWrap};
+pub use markup::{NcMarkup, NcMarkupSpan};
@@ -23,4 +23,27 @@ impl NcTime {
     pub fn new(seconds: time_t, nanoseconds: c_long) -> Self {
         Self { tv_sec: seconds, tv_nsec: nanoseconds }
     }
+
+    /// New `NcTime` from a total number of nanoseconds.
+    pub fn from_nanos(nanoseconds: u64) -> Self {
+        Self::new(
+            (nanoseconds / NANOS_PER_SEC) as time_t,
+            (nanoseconds % NANOS_PER_SEC) as c_long,
+        )
+    }
+
+    /// Returns the total number of nanoseconds.
+    ///
+    /// Pre-epoch times return 0.
+    pub fn as_nanos(&self) -> u64 {
+        if self.tv_sec < 0 {
+            0
+        } else {
+            (self.tv_sec as u64)
+                .saturating_mul(NANOS_PER_SEC)
+                .saturating_add(self.tv_nsec as u64)
+        }
+    }
 }
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
@@ -0,0 +1,348 @@
+//! `NcCanvas`
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    c_api, plane::line::line_points, Nc, NcBlitter, NcError, NcPlane, NcResult, NcRgba, NcVisual,
+    NcVisualGeometry, NcVisualOptions,
+};
+
+/// A grid of pixels for drawing vector shapes with sub-cell resolution.
+///
+/// Each cell of the plane it's sized for holds as many pixels as the chosen
+/// [`NcBlitter`] can represent: 2×4 for [`Braille`], 2×3 for [`Sextant`],
+/// 2×2 for [`Quadrant`], and so on.
+///
+/// The coordinates of the primitives are in pixels, relative to the top left
+/// corner, and whatever falls outside of the canvas is clipped. The pixels
+/// are transparent until they're drawn, and the canvas is converted to an
+/// [`NcVisual`] each time it's [`blit`]ted.
+///
+/// # Example
+/// ```no_run
+/// # use libnotcurses_sys::*;
+/// # fn main() -> NcResult<()> {
+/// let nc = unsafe { Nc::new()? };
+/// let plane = unsafe { nc.stdplane() };
+///
+/// let mut canvas = NcCanvas::new(nc, plane, NcBlitter::Braille)?;
+/// canvas.circle(20, 20, 15, NcRgba::from([0xFF, 0x80, 0x00, 0xFF]));
+/// canvas.line(0, 0, 39, 79, [0x00, 0xFF, 0x00, 0xFF]);
+/// canvas.blit(nc, plane)?;
+/// nc.render()?;
+/// # unsafe { nc.stop()? };
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Braille`]: NcBlitter#variant.Braille
+/// [`Sextant`]: NcBlitter#variant.Sextant
+/// [`Quadrant`]: NcBlitter#variant.Quadrant
+/// [`blit`]: NcCanvas#method.blit
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NcCanvas {
+    /// The height in pixels.
+    height: u32,
+    /// The width in pixels.
+    width: u32,
+    blitter: NcBlitter,
+    pixels: Vec<NcRgba>,
+}
+
+/// # `NcCanvas` constructors
+impl NcCanvas {
+    /// New transparent canvas covering the whole `plane`, with as many pixels
+    /// per cell as the `blitter` supports in this terminal.
+    ///
+    /// *C style function: [ncvisual_geom()][c_api::ncvisual_geom].*
+    pub fn new(nc: &Nc, plane: &mut NcPlane, blitter: impl Into<NcBlitter>) -> NcResult<Self> {
+        let (rows, cols) = plane.dim_yx();
+        let options = NcVisualOptions::builder()
+            .plane(plane)
+            .blitter(blitter)
+            .build();
+        let geometry = nc.visual_geom(None, Some(&options))?;
+        Self::with_geometry(rows, cols, &geometry)
+    }
+
+    /// New transparent canvas of `rows` × `cols` cells, using the blitter and
+    /// scale of the provided `geometry`.
+    ///
+    /// Returns an error if the `geometry` has no `scale_yx`.
+    pub fn with_geometry(rows: u32, cols: u32, geometry: &NcVisualGeometry) -> NcResult<Self> {
+        let (scale_y, scale_x) = geometry.scale_yx.ok_or_else(|| {
+            NcError::with_msg(c_api::NCRESULT_ERR, "NcCanvas: the geometry has no scale")
+        })?;
+        Ok(Self::with_size(
+            rows * scale_y,
+            cols * scale_x,
+            geometry.blitter,
+        ))
+    }
+
+    /// New transparent canvas of `height` × `width` pixels,
+    /// to be blitted with `blitter`.
+    pub fn with_size(height: u32, width: u32, blitter: impl Into<NcBlitter>) -> Self {
+        Self {
+            height,
+            width,
+            blitter: blitter.into(),
+            pixels: vec![NcRgba(0); (height * width) as usize],
+        }
+    }
+}
+
+/// # `NcCanvas` methods
+impl NcCanvas {
+    /// Returns the height in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the width in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the blitter.
+    pub fn blitter(&self) -> NcBlitter {
+        self.blitter
+    }
+
+    /// Returns the color of the pixel at `y`, `x`,
+    /// or `None` if it's outside of the canvas.
+    pub fn pixel(&self, y: i32, x: i32) -> Option<NcRgba> {
+        self.index(y, x).map(|i| self.pixels[i])
+    }
+
+    /// Makes all the pixels transparent.
+    pub fn clear(&mut self) {
+        self.pixels.fill(NcRgba(0));
+    }
+
+    /// Returns the pixels as a buffer of `height` rows of `width` RGBA pixels,
+    /// 4 bytes each.
+    pub fn to_rgba(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|&p| <[u8; 4]>::from(p))
+            .collect()
+    }
+
+    /// Draws the canvas onto the `plane`, starting from its origin.
+    ///
+    /// *C style functions: [ncvisual_from_rgba()][c_api::ncvisual_from_rgba],
+    /// [ncvisual_blit()][c_api::ncvisual_blit].*
+    pub fn blit(&self, nc: &mut Nc, plane: &mut NcPlane) -> NcResult<()> {
+        let visual = NcVisual::from_rgba(&self.to_rgba(), self.height, self.width * 4, self.width)?;
+        let options = NcVisualOptions::builder()
+            .plane(plane)
+            .blitter(self.blitter)
+            .build();
+        let res = unsafe { visual.blit(nc, Some(&options)) }.map(|_| ());
+        visual.destroy();
+        res
+    }
+
+    /// Sets the pixel at `y`, `x` to `color`.
+    pub fn point(&mut self, y: i32, x: i32, color: impl Into<NcRgba>) {
+        if let Some(i) = self.index(y, x) {
+            self.pixels[i] = color.into();
+        }
+    }
+
+    /// Draws a line from `y1`, `x1` to `y2`, `x2`, both ends included.
+    pub fn line(&mut self, y1: i32, x1: i32, y2: i32, x2: i32, color: impl Into<NcRgba>) {
+        let color = color.into();
+        for (y, x) in line_points(y1, x1, y2, x2) {
+            self.point(y, x, color);
+        }
+    }
+
+    /// Draws the outline of a rectangle of `len_y` × `len_x` pixels
+    /// starting at `y`, `x`.
+    pub fn rect(&mut self, y: i32, x: i32, len_y: u32, len_x: u32, color: impl Into<NcRgba>) {
+        if len_y == 0 || len_x == 0 {
+            return;
+        }
+        let color = color.into();
+        let (y2, x2) = (y + len_y as i32 - 1, x + len_x as i32 - 1);
+        self.line(y, x, y, x2, color);
+        self.line(y2, x, y2, x2, color);
+        self.line(y, x, y2, x, color);
+        self.line(y, x2, y2, x2, color);
+    }
+
+    /// Fills a rectangle of `len_y` × `len_x` pixels starting at `y`, `x`.
+    pub fn fill_rect(&mut self, y: i32, x: i32, len_y: u32, len_x: u32, color: impl Into<NcRgba>) {
+        let color = color.into();
+        for py in y..y + len_y as i32 {
+            for px in x..x + len_x as i32 {
+                self.point(py, px, color);
+            }
+        }
+    }
+
+    /// Draws the outline of a circle centered at `y`, `x`.
+    pub fn circle(&mut self, y: i32, x: i32, radius: u32, color: impl Into<NcRgba>) {
+        self.ellipse(y, x, radius, radius, color);
+    }
+
+    /// Draws the outline of an ellipse centered at `y`, `x`.
+    pub fn ellipse(
+        &mut self,
+        y: i32,
+        x: i32,
+        radius_y: u32,
+        radius_x: u32,
+        color: impl Into<NcRgba>,
+    ) {
+        let color = color.into();
+        let (a, b) = (radius_x as i64, radius_y as i64);
+        let (a2, b2) = (a * a, b * b);
+        let (mut dx, mut dy) = (-a, 0_i64);
+        let mut err = dx * (2 * b2 + dx) + b2;
+        loop {
+            for (sy, sx) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+                self.point(y + (sy * dy) as i32, x + (sx * dx) as i32, color);
+            }
+            let e2 = 2 * err;
+            if e2 >= (dx * 2 + 1) * b2 {
+                dx += 1;
+                err += (dx * 2 + 1) * b2;
+            }
+            if e2 <= (dy * 2 + 1) * a2 {
+                dy += 1;
+                err += (dy * 2 + 1) * a2;
+            }
+            if dx > 0 {
+                break;
+            }
+        }
+        // finishes the tips of very flat ellipses
+        while dy < b {
+            dy += 1;
+            self.point(y + dy as i32, x, color);
+            self.point(y - dy as i32, x, color);
+        }
+    }
+
+    /// Draws the outline of a closed polygon through the `points`,
+    /// as `(y, x)` pairs.
+    pub fn polygon(&mut self, points: &[(i32, i32)], color: impl Into<NcRgba>) {
+        let color = color.into();
+        let Some(&last) = points.last() else {
+            return;
+        };
+        let mut prev = last;
+        for &(y, x) in points {
+            self.line(prev.0, prev.1, y, x, color);
+            prev = (y, x);
+        }
+    }
+
+    /// Flood fills with `color` the area of pixels of the same color
+    /// as the one at `y`, `x`, connected horizontally and vertically.
+    ///
+    /// Returns the number of pixels filled.
+    pub fn fill(&mut self, y: i32, x: i32, color: impl Into<NcRgba>) -> usize {
+        let color = color.into();
+        let Some(target) = self.pixel(y, x) else {
+            return 0;
+        };
+        if target == color {
+            return 0;
+        }
+        let mut filled = 0;
+        let mut stack = vec![(y, x)];
+        while let Some((y, x)) = stack.pop() {
+            match self.index(y, x) {
+                Some(i) if self.pixels[i] == target => {
+                    self.pixels[i] = color;
+                    filled += 1;
+                    stack.extend([(y - 1, x), (y + 1, x), (y, x - 1), (y, x + 1)]);
+                }
+                _ => (),
+            }
+        }
+        filled
+    }
+
+    /// Returns the index of the pixel at `y`, `x`, if it's inside the canvas.
+    fn index(&self, y: i32, x: i32) -> Option<usize> {
+        if y < 0 || x < 0 || y as u32 >= self.height || x as u32 >= self.width {
+            return None;
+        }
+        Some(y as usize * self.width as usize + x as usize)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::{string::String, vec::Vec};
+
+    const INK: NcRgba = NcRgba(0xFFFFFFFF);
+
+    /// Returns the canvas as rows of `#` for the painted pixels.
+    fn ascii(canvas: &NcCanvas) -> Vec<String> {
+        (0..canvas.height() as i32)
+            .map(|y| {
+                (0..canvas.width() as i32)
+                    .map(|x| if canvas.pixel(y, x) == Some(NcRgba(0)) { '.' } else { '#' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn primitives() {
+        let mut canvas = NcCanvas::with_size(5, 6, NcBlitter::Braille);
+        assert_eq![canvas.to_rgba().len(), 5 * 6 * 4];
+
+        canvas.rect(0, 0, 3, 4, INK);
+        canvas.point(4, 5, INK);
+        canvas.point(9, -1, INK);
+        assert_eq![
+            ascii(&canvas),
+            ["####..", "#..#..", "####..", "......", ".....#"]
+        ];
+
+        canvas.clear();
+        canvas.circle(2, 2, 2, INK);
+        assert_eq![
+            ascii(&canvas),
+            [".###..", "#...#.", "#...#.", "#...#.", ".###.."]
+        ];
+
+        canvas.clear();
+        canvas.polygon(&[(0, 0), (4, 0), (4, 4)], INK);
+        assert_eq![
+            ascii(&canvas),
+            ["#.....", "##....", "#.#...", "#..#..", "#####."]
+        ];
+
+        canvas.clear();
+        canvas.ellipse(2, 2, 0, 2, INK);
+        assert_eq![ascii(&canvas)[2], "#####."];
+    }
+
+    #[test]
+    fn fill() {
+        let mut canvas = NcCanvas::with_size(5, 6, NcBlitter::Sextant);
+        canvas.rect(0, 0, 5, 5, INK);
+        assert_eq![canvas.fill(2, 2, NcRgba(0x12345678)), 9];
+        assert_eq![canvas.pixel(1, 3), Some(NcRgba(0x12345678))];
+        assert_eq![canvas.pixel(0, 5), Some(NcRgba(0))];
+        assert_eq![canvas.fill(2, 2, NcRgba(0x12345678)), 0];
+        assert_eq![canvas.fill(0, 5, INK), 5];
+        assert_eq![canvas.fill(-1, 0, INK), 0];
+
+        canvas.fill_rect(1, 1, 2, 2, NcRgba(0));
+        assert_eq![canvas.pixel(2, 2), Some(NcRgba(0))];
+        assert_eq![&canvas.to_rgba()[..4], &[0xFF; 4]];
+    }
+}
@@ -609,6 +609,8 @@ impl NcVisual {
     /// [1]: NcVisual#method.ncdirectf_render
     /// [2]: NcVisual#method.ncdirectf_free
     ///
+    /// See also [`NcDirectF`][crate::NcDirectF], which frees the frame automatically when dropped.
+    ///
     /// *C style function: [ncdirectf_from_file()][c_api::ncdirectf_from_file].*
     pub fn ncdirectf_from_file<'a>(ncd: &mut NcDirect, file: &str) -> NcResult<&'a mut NcVisual> {
         let cs = cstring![file];
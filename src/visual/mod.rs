@@ -5,8 +5,8 @@
 // -----------------------------------------
 //W  ncdirectf_free
 //W  ncdirectf_from_file
-//   ncdirectf_geom
-//   ncdirectf_render
+//W  ncdirectf_geom
+//W  ncdirectf_render
 //W  ncvisual_at_yx
 //W  ncvisual_blit
 //W  ncvisual_decode
@@ -40,13 +40,19 @@
 #[allow(unused_imports)] // for doc comments
 use crate::{c_api::NcResult_i32, NcBlitter, NcChannel, NcPlane, NcScale, NcTime};
 
+mod canvas;
 mod geometry;
 mod methods;
 pub(crate) mod options;
+mod player;
 mod reimplemented;
+mod sprite;
 
+pub use canvas::NcCanvas;
 pub use geometry::NcVisualGeometry;
 pub use options::{NcVisualFlag, NcVisualOptions, NcVisualOptionsBuilder};
+pub use player::NcPlayer;
+pub use sprite::NcSprite;
 
 /// A visual bit of multimedia.
 ///
@@ -0,0 +1,735 @@
+//! `NcPlayer`
+
+use core::{
+    ffi::{c_int, c_void},
+    ptr::NonNull,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+
+use crate::{c_api, Nc, NcError, NcPlane, NcResult, NcTime, NcVisual, NcVisualOptions};
+
+/// A frame-accurate playback controller, blitting an [`NcVisual`] into a
+/// target [`NcPlane`].
+///
+/// The frames can come from a media file decoded with [`NcVisual.decode`],
+/// or from any Rust iterator of frames and their timestamps.
+///
+/// The player doesn't keep its own clock. Instead, [`update`] must be
+/// called with the current time, and it will blit the frame due at that
+/// time, if it's not already shown. The pile must then be rendered as usual.
+/// With the `std` feature [`run`] does all of that until the end.
+///
+/// Unlike most of the types in this library, the player owns the visual it
+/// decodes a media file into, and destroys it when it goes out of scope.
+/// The frames yielded by an iterator are only borrowed.
+///
+/// # Example
+/// ```no_run
+/// # use libnotcurses_sys::*;
+/// # fn main() -> NcResult<()> {
+/// let nc = unsafe { Nc::new()? };
+/// let plane = unsafe { nc.stdplane() };
+///
+/// // 25 frames of growing gray squares, one each 40 ms.
+/// let mut visuals = (0..25_u8)
+///     .map(|i| NcVisual::from_rgba(&vec![i * 10; 32 * 32 * 4], 32, 32 * 4, 32))
+///     .collect::<NcResult<Vec<_>>>()?;
+/// let frames = visuals
+///     .iter_mut()
+///     .enumerate()
+///     .map(|(i, visual)| (&mut **visual, NcTime::from_nanos(i as u64 * 40_000_000)));
+///
+/// let mut player = NcPlayer::from_frames(frames, plane);
+/// player.set_looping(true);
+/// player.on_frame(|plane, index| {
+///     plane.putstr_yx(Some(0), Some(0), &format!["frame {index}"])?;
+///     Ok(())
+/// });
+/// player.update(nc, NcTime::from_nanos(0))?;
+/// drop(player);
+///
+/// for visual in visuals {
+///     visual.destroy();
+/// }
+/// # unsafe { nc.stop()? };
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`NcVisual.decode`]: NcVisual#method.decode
+/// [`update`]: NcPlayer#method.update
+/// [`run`]: NcPlayer#method.run
+pub struct NcPlayer<'a> {
+    source: Source<'a>,
+    plane: &'a mut NcPlane,
+    options: NcVisualOptions,
+    overlays: Vec<Overlay<'a>>,
+    clock: Clock,
+    shown: Option<u32>,
+    looping: bool,
+    finished: bool,
+}
+
+/// A closure called after each frame is blitted.
+type Overlay<'a> = Box<dyn FnMut(&mut NcPlane, u32) -> NcResult<()> + 'a>;
+
+/// Where the frames come from.
+enum Source<'a> {
+    /// A media file, decoded one frame at a time.
+    Media {
+        path: String,
+        visual: NonNull<NcVisual>,
+        /// The index of the currently decoded frame.
+        decoded: u32,
+        timeline: Timeline,
+    },
+    /// Frames yielded by an iterator, kept for seeking and looping.
+    Frames {
+        iter: Box<dyn Iterator<Item = (&'a mut NcVisual, NcTime)> + 'a>,
+        visuals: Vec<&'a mut NcVisual>,
+        timeline: Timeline,
+    },
+}
+
+/// The timestamps of the frames loaded so far.
+#[derive(Debug, Default)]
+struct Timeline {
+    pts: Vec<u64>,
+    exhausted: bool,
+    /// The duration of every frame, when overriding their timestamps.
+    frame_ns: Option<u64>,
+}
+
+/// The playback position in media time.
+#[derive(Debug)]
+struct Clock {
+    position: u64,
+    last_tick: Option<u64>,
+    speed: f32,
+    paused: bool,
+}
+
+/// # `NcPlayer` constructors
+impl<'a> NcPlayer<'a> {
+    /// New `NcPlayer` for the media file at `path`, blitting into `plane`.
+    ///
+    /// Each frame is shown for 1/`fps` seconds if provided, and the frames
+    /// are then decoded as they are needed.
+    ///
+    /// Otherwise each frame is shown at its own timestamp. In order to read
+    /// them, the whole file is decoded up front, blitting every frame into a
+    /// private scratch pile, which can take a while for long media.
+    ///
+    /// It is an error to supply an `fps` less than or equal to 0, or `None`
+    /// if the media can't be streamed.
+    ///
+    /// *C style functions: [ncvisual_from_file()][c_api::ncvisual_from_file],
+    /// [ncvisual_stream()][c_api::ncvisual_stream].*
+    pub fn from_file(
+        nc: &mut Nc,
+        path: &str,
+        plane: &'a mut NcPlane,
+        fps: Option<f32>,
+    ) -> NcResult<Self> {
+        let timeline = match fps {
+            Some(fps) if fps > 0.0 && fps.is_finite() => {
+                let frame_ns = ((1e9 / fps as f64) as u64).max(1);
+                Timeline { pts: [0].into(), exhausted: false, frame_ns: Some(frame_ns) }
+            }
+            Some(_) => {
+                return Err(NcError::with_msg(
+                    c_api::NCRESULT_ERR,
+                    &format!["NcPlayer::from_file(nc, {}, plane, {:?})", path, fps],
+                ))
+            }
+            None => scan(nc, path)?,
+        };
+        let visual = NcVisual::from_file(path)?;
+        let source = Source::Media {
+            path: path.into(),
+            visual: NonNull::from(visual),
+            decoded: 0,
+            timeline,
+        };
+        Ok(Self::with_source(source, plane))
+    }
+
+    /// New `NcPlayer` for the `frames` yielded by an iterator, blitting into
+    /// `plane`.
+    ///
+    /// Each frame comes with its presentation timestamp, relative to the
+    /// start of the playback, and the timestamps must not decrease.
+    /// The last frame lasts as long as the one before it.
+    ///
+    /// The frames are only taken from the iterator when they are needed, and
+    /// stay borrowed by the player. They must be destroyed by the caller once
+    /// the player is dropped.
+    pub fn from_frames<I>(frames: I, plane: &'a mut NcPlane) -> Self
+    where
+        I: IntoIterator<Item = (&'a mut NcVisual, NcTime)>,
+        I::IntoIter: 'a,
+    {
+        let source = Source::Frames {
+            iter: Box::new(frames.into_iter()),
+            visuals: Vec::new(),
+            timeline: Timeline::default(),
+        };
+        Self::with_source(source, plane)
+    }
+
+    fn with_source(source: Source<'a>, plane: &'a mut NcPlane) -> Self {
+        Self {
+            source,
+            plane,
+            options: NcVisualOptions::builder().build(),
+            overlays: Vec::new(),
+            clock: Clock::new(),
+            shown: None,
+            looping: false,
+            finished: false,
+        }
+    }
+}
+
+/// # `NcPlayer` methods
+impl<'a> NcPlayer<'a> {
+    /// Sets the options used for blitting each frame.
+    ///
+    /// The plane is always the target plane of the player, so the
+    /// [`ChildPlane`] flag is ignored.
+    ///
+    /// [`ChildPlane`]: crate::NcVisualFlag#associatedconstant.ChildPlane
+    pub fn set_options(&mut self, options: NcVisualOptions) {
+        self.options = options;
+        self.shown = None;
+    }
+
+    /// Adds a closure to be called after each frame is blitted, with the
+    /// target plane and the index of the frame, e.g. to draw an overlay.
+    ///
+    /// If it returns an error, [`update`] returns it.
+    ///
+    /// [`update`]: NcPlayer#method.update
+    pub fn on_frame(&mut self, overlay: impl FnMut(&mut NcPlane, u32) -> NcResult<()> + 'a) {
+        self.overlays.push(Box::new(overlay));
+    }
+
+    /// Resumes the playback.
+    pub fn play(&mut self) {
+        self.clock.paused = false;
+    }
+
+    /// Pauses the playback.
+    pub fn pause(&mut self) {
+        self.clock.paused = true;
+    }
+
+    /// Pauses the playback if playing, or resumes it if paused.
+    pub fn toggle(&mut self) {
+        self.clock.paused = !self.clock.paused;
+    }
+
+    /// Returns `true` if the playback is paused.
+    pub fn is_paused(&self) -> bool {
+        self.clock.paused
+    }
+
+    /// Sets whether to start over after the last frame.
+    pub fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+    }
+
+    /// Returns `true` if the playback starts over after the last frame.
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Sets the playback speed multiplier.
+    ///
+    /// `2.0` plays twice as fast, and `0.5` at half the speed.
+    /// It is an error to supply a `speed` less than or equal to 0.
+    pub fn set_speed(&mut self, speed: f32) -> NcResult<()> {
+        if speed > 0.0 && speed.is_finite() {
+            self.clock.speed = speed;
+            Ok(())
+        } else {
+            Err(NcError::with_msg(
+                c_api::NCRESULT_ERR,
+                &format!["NcPlayer.set_speed({})", speed],
+            ))
+        }
+    }
+
+    /// Returns the playback speed multiplier.
+    pub fn speed(&self) -> f32 {
+        self.clock.speed
+    }
+
+    /// Returns the current playback position, in media time.
+    pub fn position(&self) -> NcTime {
+        NcTime::from_nanos(self.clock.position)
+    }
+
+    /// Returns the index of the frame currently shown, if any.
+    pub fn frame(&self) -> Option<u32> {
+        self.shown
+    }
+
+    /// Returns `true` if the playback reached the end without looping.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Moves the playback to the start of the frame at `index`.
+    ///
+    /// It will be blitted on the next [`update`].
+    ///
+    /// It is an error if there's no such frame.
+    ///
+    /// [`update`]: NcPlayer#method.update
+    pub fn seek(&mut self, index: u32) -> NcResult<()> {
+        match self.source.pts(index)? {
+            Some(pts) => {
+                self.clock.position = pts;
+                self.shown = None;
+                self.finished = false;
+                Ok(())
+            }
+            None => Err(NcError::with_msg(
+                c_api::NCRESULT_ERR,
+                &format!["NcPlayer.seek({})", index],
+            )),
+        }
+    }
+
+    /// Advances the playback to the time `now`, and blits the frame due at
+    /// that time into the target plane, if it's not already there.
+    ///
+    /// `now` can be any monotonic time, like the one returned by
+    /// `clock_gettime(CLOCK_MONOTONIC)`. The time elapsed between calls,
+    /// multiplied by the speed, is added to the playback position unless
+    /// paused.
+    ///
+    /// Returns `true` if a new frame was blitted, in which case the pile
+    /// should be rendered.
+    pub fn update(&mut self, nc: &mut Nc, now: NcTime) -> NcResult<bool> {
+        self.clock.tick(now.as_nanos());
+        if self.finished {
+            return Ok(false);
+        }
+        loop {
+            if let Some(index) = self.source.index_at(self.clock.position)? {
+                if self.shown == Some(index) {
+                    return Ok(false);
+                }
+                if self.show(nc, index)? {
+                    return Ok(true);
+                }
+                // the media ended before that frame, and its end is known now
+                continue;
+            }
+            match self.source.end() {
+                Some(end) if self.looping && end > 0 => self.clock.position %= end,
+                _ => {
+                    self.finished = true;
+                    return Ok(false);
+                }
+            }
+        }
+    }
+
+    /// Returns how long until the next frame is due, in wall time.
+    ///
+    /// Returns `None` if paused, finished, or if it's not known yet.
+    pub fn next_in(&self) -> Option<NcTime> {
+        if self.clock.paused || self.finished {
+            return None;
+        }
+        let next = self.source.next_pts(self.shown?)?;
+        let wait = next.saturating_sub(self.clock.position) as f64 / self.clock.speed as f64;
+        Some(NcTime::from_nanos(wait as u64))
+    }
+
+    /// Plays until the end, rendering the pile of the target plane after
+    /// each new frame. Blocking.
+    ///
+    /// Never returns if looping.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "std")))]
+    pub fn run(&mut self, nc: &mut Nc) -> NcResult<()> {
+        use std::{thread::sleep, time::Duration, time::Instant};
+
+        let start = Instant::now();
+        while !self.finished {
+            let now = NcTime::from_nanos(start.elapsed().as_nanos() as u64);
+            if self.update(nc, now)? {
+                self.plane.render_raster()?;
+            }
+            let wait = self
+                .next_in()
+                .map_or(10_000_000, |t| t.as_nanos().max(1_000_000));
+            sleep(Duration::from_nanos(wait));
+        }
+        Ok(())
+    }
+
+    /// Blits the frame at `index` and calls the overlays.
+    ///
+    /// Returns `false` if there's no such frame.
+    fn show(&mut self, nc: &mut Nc, index: u32) -> NcResult<bool> {
+        let Some(visual) = self.source.load(index)? else {
+            return Ok(false);
+        };
+        self.plane.erase();
+        self.options.n = self.plane as *mut NcPlane;
+        self.options.flags &= !c_api::NCVISUAL_OPTION_CHILDPLANE;
+        unsafe { visual.blit(nc, Some(&self.options))? };
+        for overlay in self.overlays.iter_mut() {
+            overlay(self.plane, index)?;
+        }
+        self.shown = Some(index);
+        Ok(true)
+    }
+}
+
+impl Drop for NcPlayer<'_> {
+    /// Destroys the visual decoding a media file.
+    ///
+    /// *C style function: [ncvisual_destroy()][c_api::ncvisual_destroy].*
+    fn drop(&mut self) {
+        if let Source::Media { visual, .. } = &mut self.source {
+            unsafe { c_api::ncvisual_destroy(visual.as_ptr()) };
+        }
+    }
+}
+
+impl Source<'_> {
+    fn timeline(&self) -> &Timeline {
+        match self {
+            Source::Media { timeline, .. } | Source::Frames { timeline, .. } => timeline,
+        }
+    }
+
+    /// Returns the timestamp of the frame at `index`, if it exists.
+    fn pts(&mut self, index: u32) -> NcResult<Option<u64>> {
+        self.discover(index as usize + 1)?;
+        Ok(self.timeline().pts.get(index as usize).copied())
+    }
+
+    /// Returns the timestamp of the frame following `index`, if known.
+    fn next_pts(&self, index: u32) -> Option<u64> {
+        let timeline = self.timeline();
+        let next = timeline.pts.get(index as usize + 1).copied();
+        match timeline.frame_ns {
+            Some(frame_ns) if !timeline.exhausted => next.or(Some((index as u64 + 1) * frame_ns)),
+            _ => next,
+        }
+    }
+
+    /// Returns the index of the frame to show at `position`, or `None` if
+    /// it's past the end.
+    ///
+    /// With a fixed frame rate the frame may turn out not to exist once
+    /// it's decoded.
+    fn index_at(&mut self, position: u64) -> NcResult<Option<u32>> {
+        loop {
+            let timeline = self.timeline();
+            if timeline.exhausted
+                || timeline.frame_ns.is_some()
+                || timeline.pts.last().map_or(false, |l| *l > position)
+            {
+                return Ok(timeline.index_at(position));
+            }
+            let count = timeline.pts.len() + 1;
+            self.discover(count)?;
+        }
+    }
+
+    /// Returns the total duration, once known.
+    fn end(&self) -> Option<u64> {
+        self.timeline().end()
+    }
+
+    /// Makes sure the timestamps of the first `count` frames are known, if
+    /// they exist.
+    fn discover(&mut self, count: usize) -> NcResult<()> {
+        let timeline = self.timeline();
+        if timeline.exhausted || timeline.pts.len() >= count {
+            return Ok(());
+        }
+        match self {
+            Source::Media { .. } => self.load(count as u32 - 1).map(|_| ()),
+            Source::Frames { .. } => {
+                self.pull(count);
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the frame at `index`, decoding or pulling it if needed,
+    /// or `None` if there's no such frame.
+    fn load(&mut self, index: u32) -> NcResult<Option<&mut NcVisual>> {
+        match self {
+            Source::Media { path, visual, decoded, timeline } => {
+                if timeline.exhausted && index as usize >= timeline.pts.len() {
+                    return Ok(None);
+                }
+                let (rewind, count) = decode_steps(*decoded, index);
+                if rewind {
+                    unsafe { c_api::ncvisual_destroy(visual.as_ptr()) };
+                    *visual = NonNull::from(NcVisual::from_file(path)?);
+                    *decoded = 0;
+                }
+                for _ in 0..count {
+                    let res = unsafe { visual.as_mut() }.decode()?;
+                    if res == 1 {
+                        timeline.pts.truncate(*decoded as usize + 1);
+                        timeline.exhausted = true;
+                        // the visual is left without a frame, start over
+                        unsafe { c_api::ncvisual_destroy(visual.as_ptr()) };
+                        *visual = NonNull::from(NcVisual::from_file(path)?);
+                        *decoded = 0;
+                        return Ok(None);
+                    }
+                    *decoded += 1;
+                    timeline.fill(*decoded as usize + 1);
+                }
+                Ok(Some(unsafe { visual.as_mut() }))
+            }
+            Source::Frames { .. } => {
+                self.pull(index as usize + 1);
+                match self {
+                    Source::Frames { visuals, .. } => {
+                        Ok(visuals.get_mut(index as usize).map(|v| &mut **v))
+                    }
+                    _ => unreachable!(),
+                }
+            }
+        }
+    }
+
+    /// Pulls frames from the iterator until there are `count`, or it's over.
+    fn pull(&mut self, count: usize) {
+        if let Source::Frames { iter, visuals, timeline } = self {
+            while !timeline.exhausted && visuals.len() < count {
+                match iter.next() {
+                    Some((visual, pts)) => {
+                        visuals.push(visual);
+                        timeline.push(pts.as_nanos());
+                    }
+                    None => timeline.exhausted = true,
+                }
+            }
+        }
+    }
+}
+
+/// Returns whether reaching the frame at `index` from the `decoded` one needs
+/// reopening the file, and how many frames must be decoded after that.
+///
+/// Only seeking backwards rewinds, since the frames can only be decoded in order.
+fn decode_steps(decoded: u32, index: u32) -> (bool, u32) {
+    if index < decoded {
+        (true, index)
+    } else {
+        (false, index - decoded)
+    }
+}
+
+/// Returns the timestamps of the frames of the media file at `path`, relative
+/// to the first one, by streaming it once without displaying it.
+///
+/// The frames are blitted into a new 1x1 pile, destroyed afterwards.
+fn scan(nc: &mut Nc, path: &str) -> NcResult<Timeline> {
+    /// Records the display time of each frame, and asks for the next one.
+    unsafe extern "C" fn record(
+        _visual: *mut NcVisual,
+        _options: *mut NcVisualOptions,
+        time: *const NcTime,
+        curry: *mut c_void,
+    ) -> c_int {
+        (*(curry as *mut Vec<u64>)).push((*time).as_nanos());
+        0
+    }
+
+    let visual = NcVisual::from_file(path)?;
+    let scratch = match NcPlane::new_pile_sized(nc, 0, 0, 1, 1) {
+        Ok(plane) => plane,
+        Err(e) => {
+            visual.destroy();
+            return Err(e);
+        }
+    };
+    let options = NcVisualOptions::builder().plane(scratch).build();
+    let mut times: Vec<u64> = Vec::new();
+    let res = unsafe {
+        c_api::ncvisual_stream(
+            nc,
+            visual,
+            1.0,
+            Some(record),
+            &options,
+            &mut times as *mut _ as *mut c_void,
+        )
+    };
+    visual.destroy();
+    scratch.destroy()?;
+    if res < 0 || times.is_empty() {
+        return Err(NcError::with_msg(
+            c_api::NCRESULT_ERR,
+            &format!["NcPlayer::from_file(nc, {}, plane, None)", path],
+        ));
+    }
+    let mut timeline = Timeline::default();
+    for time in &times {
+        timeline.push(time.saturating_sub(times[0]));
+    }
+    timeline.exhausted = true;
+    Ok(timeline)
+}
+
+impl Timeline {
+    /// Adds the timestamp of the next frame, never earlier than the last.
+    fn push(&mut self, pts: u64) {
+        let last = self.pts.last().copied().unwrap_or(0);
+        self.pts.push(pts.max(last));
+    }
+
+    /// Adds the timestamps of the frames up to `count`, with a fixed frame rate.
+    fn fill(&mut self, count: usize) {
+        if let Some(frame_ns) = self.frame_ns {
+            while self.pts.len() < count {
+                self.push(self.pts.len() as u64 * frame_ns);
+            }
+        }
+    }
+
+    /// Returns the end time, once all the frames are known.
+    ///
+    /// The last frame lasts as long as the one before it, and at least 1ns,
+    /// unless the frame rate is fixed.
+    fn end(&self) -> Option<u64> {
+        if !self.exhausted {
+            return None;
+        }
+        let last = *self.pts.last()?;
+        if let Some(frame_ns) = self.frame_ns {
+            return Some(last + frame_ns);
+        }
+        let before = self.pts.len().checked_sub(2).map_or(last, |i| self.pts[i]);
+        Some(last + (last - before).max(1))
+    }
+
+    /// Returns the index of the last frame starting at or before `position`,
+    /// or `None` if it's past the end.
+    ///
+    /// With a fixed frame rate, it's computed before all the frames are known.
+    fn index_at(&self, position: u64) -> Option<u32> {
+        if self.end().map_or(false, |end| position >= end) || self.pts.is_empty() {
+            return None;
+        }
+        if let (Some(frame_ns), false) = (self.frame_ns, self.exhausted) {
+            return Some((position / frame_ns).min(u32::MAX as u64) as u32);
+        }
+        let index = self.pts.partition_point(|pts| *pts <= position);
+        Some(index.saturating_sub(1) as u32)
+    }
+}
+
+impl Clock {
+    fn new() -> Self {
+        Self { position: 0, last_tick: None, speed: 1.0, paused: false }
+    }
+
+    /// Advances the position by the time elapsed since the last tick.
+    fn tick(&mut self, now: u64) {
+        if let Some(last) = self.last_tick {
+            if !self.paused {
+                let elapsed = now.saturating_sub(last) as f64 * self.speed as f64;
+                self.position = self.position.saturating_add(elapsed as u64);
+            }
+        }
+        self.last_tick = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode_steps, Clock, Timeline};
+
+    #[test]
+    fn timeline() {
+        let mut t = Timeline::default();
+        assert_eq![t.index_at(0), None];
+        for pts in [0, 40, 80, 70] {
+            t.push(pts);
+        }
+        assert_eq![t.pts, [0, 40, 80, 80]];
+        assert_eq![t.end(), None];
+        assert_eq![t.index_at(39), Some(0)];
+        assert_eq![t.index_at(40), Some(1)];
+        assert_eq![t.index_at(1000), Some(3)];
+
+        t.exhausted = true;
+        assert_eq![t.end(), Some(81)];
+        assert_eq![t.index_at(80), Some(3)];
+        assert_eq![t.index_at(81), None];
+
+        let t = Timeline { pts: [5].into(), exhausted: true, frame_ns: None };
+        assert_eq![t.index_at(0), Some(0)];
+        assert_eq![t.index_at(6), None];
+    }
+
+    #[test]
+    fn fixed_rate() {
+        let mut t = Timeline { pts: [0].into(), exhausted: false, frame_ns: Some(40) };
+        assert_eq![t.index_at(1000), Some(25)];
+
+        // playing in order only decodes forward, once per frame
+        let (mut decoded, mut decodes, mut rewinds) = (0, 0, 0);
+        for position in (0..4000).step_by(15) {
+            let index = t.index_at(position).unwrap();
+            let (rewind, count) = decode_steps(decoded, index);
+            rewinds += rewind as u32;
+            decodes += count;
+            decoded = index;
+            t.fill(decoded as usize + 1);
+        }
+        assert_eq![(decoded, decodes, rewinds), (99, 99, 0)];
+        assert_eq![t.pts.len(), 100];
+        assert_eq![t.pts[99], 3960];
+
+        // seeking backwards rewinds
+        assert_eq![decode_steps(decoded, 10), (true, 10)];
+
+        let single = Timeline { pts: [0].into(), exhausted: true, frame_ns: Some(40) };
+        assert_eq![single.index_at(39), Some(0)];
+        assert_eq![single.index_at(40), None];
+
+        t.exhausted = true;
+        assert_eq![t.end(), Some(4000)];
+        assert_eq![t.index_at(4000), None];
+    }
+
+    #[test]
+    fn clock() {
+        let mut c = Clock::new();
+        c.tick(1000);
+        assert_eq![c.position, 0];
+        c.tick(1100);
+        assert_eq![c.position, 100];
+
+        c.paused = true;
+        c.tick(1500);
+        assert_eq![c.position, 100];
+
+        c.paused = false;
+        c.speed = 2.0;
+        c.tick(1600);
+        assert_eq![c.position, 300];
+        c.tick(1550);
+        assert_eq![c.position, 300];
+    }
+}
@@ -0,0 +1,293 @@
+//! `NcSprite`
+
+use core::ptr::NonNull;
+
+#[cfg(not(feature = "std"))]
+use alloc::format;
+
+use crate::{c_api, Nc, NcError, NcPlane, NcResult, NcVisual, NcVisualOptions};
+
+/// An animated bitmap, made of the frames of a sprite sheet, blitted with
+/// [`NcBlitter::Pixel`] into its own child plane.
+///
+/// The sheet is a grid of frames of the same size in pixels, indexed left to
+/// right and top to bottom.
+///
+/// The sprite is positioned in pixels relative to the origin of its parent
+/// plane, and the remainder inside of a cell is applied with
+/// [`NcVisualOptionsBuilder.cell_offset`]. Moving it by whole cells just
+/// moves its plane, while the bitmap is only blitted again when the frame or
+/// the offset within the cell changes, always reusing the same plane.
+///
+/// Changes are applied by [`draw`], after which the pile must be rendered.
+///
+/// Unlike most of the types in this library, the sprite owns its sheet and
+/// its plane, and destroys them when it goes out of scope. It must therefore
+/// be dropped before calling [`Nc.stop`], which frees every plane.
+///
+/// # Example
+/// ```no_run
+/// # use libnotcurses_sys::*;
+/// # fn main() -> NcResult<()> {
+/// let nc = unsafe { Nc::new()? };
+/// let stdplane = unsafe { nc.stdplane() };
+///
+/// // a sheet of 4×2 frames of 16×16 pixels each
+/// let mut sprite = NcSprite::from_file(nc, stdplane, "walk.png", 16, 16)?;
+/// for _ in 0..40 {
+///     sprite.move_rel(0, 3);
+///     sprite.next_frame();
+///     sprite.draw(nc)?;
+///     nc.render()?;
+/// }
+/// # drop(sprite);
+/// # unsafe { nc.stop()? };
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`NcBlitter::Pixel`]: crate::NcBlitter::Pixel
+/// [`NcVisualOptionsBuilder.cell_offset`]: crate::NcVisualOptionsBuilder#method.cell_offset
+/// [`draw`]: NcSprite#method.draw
+/// [`Nc.stop`]: crate::Nc#method.stop
+#[derive(Debug)]
+pub struct NcSprite {
+    sheet: NonNull<NcVisual>,
+    plane: NonNull<NcPlane>,
+    /// The size of a frame in pixels.
+    frame_yx: (u32, u32),
+    /// The number of frames in each column and row of the sheet.
+    grid_yx: (u32, u32),
+    /// The size of a cell in pixels.
+    cell_yx: (u32, u32),
+    /// The position relative to the parent plane, in pixels.
+    pos_yx: (i32, i32),
+    index: u32,
+    /// The frame and cell offset currently blitted.
+    blitted: Option<(u32, u32, u32)>,
+}
+
+/// # `NcSprite` constructors
+impl NcSprite {
+    /// New `NcSprite` from a sprite `sheet` made of frames of
+    /// `frame_height` × `frame_width` pixels, as a child of `parent`.
+    ///
+    /// The sprite takes ownership of the `sheet`, which will be destroyed
+    /// together with the sprite.
+    ///
+    /// It is an error if the terminal doesn't support pixel graphics, or if
+    /// the sheet is smaller than a single frame.
+    ///
+    /// *C style function: [notcurses_canpixel()][c_api::notcurses_canpixel].*
+    pub fn new(
+        nc: &mut Nc,
+        parent: &mut NcPlane,
+        sheet: &mut NcVisual,
+        frame_height: u32,
+        frame_width: u32,
+    ) -> NcResult<Self> {
+        let errmsg = format![
+            "NcSprite::new(nc, parent, sheet, {}, {})",
+            frame_height, frame_width
+        ];
+        let err = || NcError::with_msg(c_api::NCRESULT_ERR, &errmsg);
+        if !nc.canpixel() {
+            return Err(err());
+        }
+
+        let (sheet_y, sheet_x) = nc
+            .visual_geom_with_pixel(Some(sheet))?
+            .pix_yx
+            .ok_or_else(err)?;
+        let geom = parent.pixel_geom();
+        if frame_height == 0 || frame_width == 0 || geom.cell_y == 0 || geom.cell_x == 0 {
+            return Err(err());
+        }
+        let grid_yx = (sheet_y / frame_height, sheet_x / frame_width);
+        if grid_yx.0 == 0 || grid_yx.1 == 0 {
+            return Err(err());
+        }
+
+        // big enough for a frame at any offset within a cell
+        let rows = (frame_height + geom.cell_y - 1 + geom.cell_y - 1) / geom.cell_y;
+        let cols = (frame_width + geom.cell_x - 1 + geom.cell_x - 1) / geom.cell_x;
+        let plane = NcPlane::new_child_sized(parent, 0, 0, rows, cols)?;
+
+        Ok(Self {
+            sheet: NonNull::from(sheet),
+            plane: NonNull::from(plane),
+            frame_yx: (frame_height, frame_width),
+            grid_yx,
+            cell_yx: (geom.cell_y, geom.cell_x),
+            pos_yx: (0, 0),
+            index: 0,
+            blitted: None,
+        })
+    }
+
+    /// New `NcSprite` from a sprite sheet loaded from the file at `path`.
+    ///
+    /// See [`new`][NcSprite#method.new].
+    pub fn from_file(
+        nc: &mut Nc,
+        parent: &mut NcPlane,
+        path: &str,
+        frame_height: u32,
+        frame_width: u32,
+    ) -> NcResult<Self> {
+        let sheet = NcVisual::from_file(path)?;
+        Self::new(nc, parent, sheet, frame_height, frame_width).map_err(|e| {
+            sheet.destroy();
+            e
+        })
+    }
+}
+
+/// # `NcSprite` methods
+impl NcSprite {
+    /// Returns the number of frames in the sheet.
+    pub fn frames(&self) -> u32 {
+        self.grid_yx.0 * self.grid_yx.1
+    }
+
+    /// Returns the index of the current frame.
+    pub fn frame(&self) -> u32 {
+        self.index
+    }
+
+    /// Selects the frame at `index`.
+    ///
+    /// It is an error if there's no such frame.
+    pub fn set_frame(&mut self, index: u32) -> NcResult<()> {
+        if index < self.frames() {
+            self.index = index;
+            Ok(())
+        } else {
+            Err(NcError::with_msg(
+                c_api::NCRESULT_ERR,
+                &format!["NcSprite.set_frame({})", index],
+            ))
+        }
+    }
+
+    /// Advances the animation to the next frame, wrapping around after the
+    /// last one, and returns its index.
+    pub fn next_frame(&mut self) -> u32 {
+        self.index = (self.index + 1) % self.frames();
+        self.index
+    }
+
+    /// Goes back to the previous frame, wrapping around before the first one,
+    /// and returns its index.
+    pub fn prev_frame(&mut self) -> u32 {
+        self.index = self.index.checked_sub(1).unwrap_or(self.frames() - 1);
+        self.index
+    }
+
+    /// Returns the position relative to the parent plane, in pixels.
+    pub fn position(&self) -> (i32, i32) {
+        self.pos_yx
+    }
+
+    /// Moves the sprite to `y`, `x` pixels relative to its parent plane.
+    pub fn move_yx(&mut self, y: i32, x: i32) {
+        self.pos_yx = (y, x);
+    }
+
+    /// Moves the sprite by `rows`, `cols` pixels.
+    pub fn move_rel(&mut self, rows: i32, cols: i32) {
+        self.pos_yx = (
+            self.pos_yx.0.saturating_add(rows),
+            self.pos_yx.1.saturating_add(cols),
+        );
+    }
+
+    /// Returns a shared reference to the plane of the sprite.
+    pub fn plane(&self) -> &NcPlane {
+        unsafe { self.plane.as_ref() }
+    }
+
+    /// Returns an exclusive reference to the plane of the sprite,
+    /// e.g. to change its z-order or its parent.
+    pub fn plane_mut(&mut self) -> &mut NcPlane {
+        unsafe { self.plane.as_mut() }
+    }
+
+    /// Applies the current frame and position to the plane of the sprite.
+    ///
+    /// The bitmap is only blitted again if the frame or the offset within the
+    /// cell changed since the last time.
+    ///
+    /// *C style function: [ncvisual_blit()][c_api::ncvisual_blit].*
+    pub fn draw(&mut self, nc: &mut Nc) -> NcResult<()> {
+        let (cell_y, off_y) = split_pixels(self.pos_yx.0, self.cell_yx.0);
+        let (cell_x, off_x) = split_pixels(self.pos_yx.1, self.cell_yx.1);
+
+        let plane = unsafe { self.plane.as_mut() };
+        plane.move_yx(cell_y, cell_x)?;
+
+        let blit = (self.index, off_y, off_x);
+        if self.blitted != Some(blit) {
+            let (beg_y, beg_x) = frame_origin(self.index, self.grid_yx.1, self.frame_yx);
+            plane.erase();
+            self.blitted = None;
+            let options = NcVisualOptions::builder()
+                .plane(plane)
+                .pixel()
+                .region(beg_y, beg_x, self.frame_yx.0, self.frame_yx.1)
+                .cell_offset(off_y, off_x)
+                .build();
+            unsafe { self.sheet.as_mut().blit(nc, Some(&options))? };
+            self.blitted = Some(blit);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for NcSprite {
+    /// Destroys the plane and the sheet.
+    ///
+    /// *C style functions: [ncplane_destroy()][c_api::ncplane_destroy],
+    /// [ncvisual_destroy()][c_api::ncvisual_destroy].*
+    fn drop(&mut self) {
+        unsafe {
+            c_api::ncplane_destroy(self.plane.as_ptr());
+            c_api::ncvisual_destroy(self.sheet.as_ptr());
+        }
+    }
+}
+
+/// Splits a position in pixels into whole cells and the remaining offset.
+fn split_pixels(pixels: i32, cell: u32) -> (i32, u32) {
+    let cell = cell as i32;
+    (pixels.div_euclid(cell), pixels.rem_euclid(cell) as u32)
+}
+
+/// Returns the origin in pixels of the frame at `index`, in a sheet with
+/// `columns` frames per row.
+fn frame_origin(index: u32, columns: u32, frame_yx: (u32, u32)) -> (u32, u32) {
+    (
+        (index / columns) * frame_yx.0,
+        (index % columns) * frame_yx.1,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::{frame_origin, split_pixels};
+
+    #[test]
+    fn pixels_to_cells() {
+        assert_eq![split_pixels(0, 8), (0, 0)];
+        assert_eq![split_pixels(17, 8), (2, 1)];
+        assert_eq![split_pixels(-3, 8), (-1, 5)];
+        assert_eq![split_pixels(-16, 8), (-2, 0)];
+    }
+
+    #[test]
+    fn sheet_frames() {
+        assert_eq![frame_origin(0, 4, (16, 24)), (0, 0)];
+        assert_eq![frame_origin(3, 4, (16, 24)), (0, 72)];
+        assert_eq![frame_origin(5, 4, (16, 24)), (16, 24)];
+    }
+}
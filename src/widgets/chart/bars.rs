@@ -0,0 +1,409 @@
+//! `NcBarChart` widget.
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, vec::Vec};
+
+use super::{detect_range, eighths, fit, fraction, gradient, HBLOCKS, VBLOCKS};
+use crate::{
+    widgets::NcWidget, NcChannels, NcInput, NcPlane, NcResult, NcStyle, NcTextAlign, NcTextLayout,
+};
+
+/// A bar of an [`NcBarChart`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct NcBar {
+    label: String,
+    value: f64,
+    channels: Option<NcChannels>,
+}
+
+/// # `NcBar` constructors
+impl NcBar {
+    /// New `NcBar` with a `label` and a `value`, colored with the gradient of
+    /// the chart.
+    pub fn new(label: &str, value: f64) -> Self {
+        Self { label: label.into(), value, channels: None }
+    }
+
+    /// Sets the channels of the bar, instead of the gradient of the chart.
+    pub fn channels(mut self, channels: impl Into<NcChannels>) -> Self {
+        self.channels = Some(channels.into());
+        self
+    }
+}
+
+/// # `NcBar` methods
+impl NcBar {
+    /// Returns the label.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the value.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Sets the value.
+    pub fn set_value(&mut self, value: f64) {
+        self.value = value;
+    }
+}
+
+/// A chart of labeled bars, horizontal or vertical, drawn with eighth blocks.
+///
+/// The bars grow from the bottom of the range, which by default spans from
+/// `0.0`, or the lowest negative value, to the highest value.
+///
+/// The cells of each bar are colored with the gradient from the `minchannels`
+/// to the `maxchannels` at their position in the range, as in
+/// [`NcPlotOptions`], unless the bar has its own channels.
+///
+/// - Horizontal bars take a row each, with the label on the left and the
+///   value on the right.
+/// - Vertical bars have the label below and the value above, truncated to the
+///   bar width.
+///
+/// # Example
+/// ```no_run
+/// # use libnotcurses_sys::{*, widgets::*};
+/// # fn main() -> NcResult<()> {
+/// # let nc = unsafe { Nc::new()? };
+/// # let stdplane = unsafe { nc.stdplane() };
+/// let plane = NcPlane::new_child_sized(stdplane, 0, 0, 5, 40)?;
+/// let mut chart = NcBarChart::new(plane)
+///     .channels(NcChannels::from_rgb(0x0000FF, 0), NcChannels::from_rgb(0x00FFFF, 0))
+///     .values(true);
+/// chart.push(NcBar::new("cpu", 63.0));
+/// chart.push(NcBar::new("mem", 41.5));
+/// chart.push(NcBar::new("swap", 97.0).channels(NcChannels::from_rgb(0xFF0000, 0)));
+/// chart.redraw()?;
+/// nc.render()?;
+/// # unsafe { nc.stop()? };
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`NcPlotOptions`]: crate::widgets::NcPlotOptions
+pub struct NcBarChart<'a> {
+    plane: &'a mut NcPlane,
+    bars: Vec<NcBar>,
+    vertical: bool,
+    range: (f64, f64),
+    minchannels: NcChannels,
+    maxchannels: NcChannels,
+    label_channels: NcChannels,
+    values: bool,
+    bar_width: u32,
+    gap: u32,
+}
+
+/// # `NcBarChart` constructors
+impl<'a> NcBarChart<'a> {
+    /// New empty `NcBarChart` over a `plane`, with horizontal bars without
+    /// gaps, and a detected range.
+    pub fn new(plane: &'a mut NcPlane) -> Self {
+        Self {
+            plane,
+            bars: Vec::new(),
+            vertical: false,
+            range: (0.0, 0.0),
+            minchannels: NcChannels::default(),
+            maxchannels: NcChannels::default(),
+            label_channels: NcChannels::default(),
+            values: false,
+            bar_width: 1,
+            gap: 0,
+        }
+    }
+
+    /// Sets whether the bars are vertical.
+    pub fn vertical(mut self, vertical: bool) -> Self {
+        self.vertical = vertical;
+        self
+    }
+
+    /// Sets the `min` and `max` values of the range.
+    ///
+    /// If both are `0.0` the range is detected from the values.
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.range = (min, max);
+        self
+    }
+
+    /// Sets the channels of the bottom and the top of the range.
+    pub fn channels(
+        mut self,
+        minchannels: impl Into<NcChannels>,
+        maxchannels: impl Into<NcChannels>,
+    ) -> Self {
+        self.minchannels = minchannels.into();
+        self.maxchannels = maxchannels.into();
+        self
+    }
+
+    /// Sets the channels of the labels and the values.
+    pub fn label_channels(mut self, channels: impl Into<NcChannels>) -> Self {
+        self.label_channels = channels.into();
+        self
+    }
+
+    /// Sets whether to show the values.
+    pub fn values(mut self, values: bool) -> Self {
+        self.values = values;
+        self
+    }
+
+    /// Sets the columns of the vertical bars.
+    pub fn bar_width(mut self, cols: u32) -> Self {
+        self.bar_width = cols.max(1);
+        self
+    }
+
+    /// Sets the rows, or columns if they're vertical, between the bars.
+    pub fn gap(mut self, gap: u32) -> Self {
+        self.gap = gap;
+        self
+    }
+}
+
+/// # `NcBarChart` methods
+impl<'a> NcBarChart<'a> {
+    /// Returns the bars.
+    pub fn bars(&self) -> &[NcBar] {
+        &self.bars
+    }
+
+    /// Returns the bars, mutably.
+    pub fn bars_mut(&mut self) -> &mut Vec<NcBar> {
+        &mut self.bars
+    }
+
+    /// Appends a `bar`.
+    pub fn push(&mut self, bar: NcBar) {
+        self.bars.push(bar);
+    }
+
+    /// Sets the value of the bar at `index`.
+    ///
+    /// Returns false if there's no such bar.
+    pub fn set_value(&mut self, index: usize, value: f64) -> bool {
+        match self.bars.get_mut(index) {
+            Some(bar) => {
+                bar.value = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes all the bars.
+    pub fn clear(&mut self) {
+        self.bars.clear();
+    }
+
+    /// Redraws the chart.
+    pub fn redraw(&mut self) -> NcResult<()> {
+        let (styles, channels) = (self.plane.styles(), self.plane.channels());
+        let res = if self.vertical { self.draw_vertical() } else { self.draw_horizontal() };
+        self.plane.set_styles(styles);
+        self.plane.set_channels(channels);
+        res
+    }
+
+    /// Destroys the chart, together with its plane.
+    pub fn destroy(&mut self) -> NcResult<()> {
+        self.plane.destroy()
+    }
+}
+
+/// # `NcBarChart` private methods
+impl<'a> NcBarChart<'a> {
+    /// Returns the range, or the detected one.
+    fn value_range(&self) -> (f64, f64) {
+        detect_range(self.bars.iter().map(|b| b.value), self.range, true)
+    }
+
+    /// Returns the channels of the `cell` of `cells` of the `bar`.
+    fn cell_channels(&self, bar: &NcBar, cell: u32, cells: u32) -> NcChannels {
+        bar.channels.unwrap_or_else(|| {
+            let t = cell as f64 / cells.saturating_sub(1).max(1) as f64;
+            gradient(self.minchannels, self.maxchannels, t)
+        })
+    }
+
+    fn draw_horizontal(&mut self) -> NcResult<()> {
+        self.plane.erase();
+        let (rows, cols) = self.plane.dim_yx();
+        let range = self.value_range();
+        let width = |text: &str| NcTextLayout::new(0).cols(text);
+        let label_cols = self.bars.iter().map(|b| width(&b.label)).max().unwrap_or(0);
+        let value_cols = if self.values {
+            self.bars
+                .iter()
+                .map(|b| width(&format_value(b.value)))
+                .max()
+                .unwrap_or(0)
+        } else {
+            0
+        };
+        let [(_, label_cols), (x0, len), (value_x, value_cols)] =
+            horizontal_columns(cols, label_cols, value_cols);
+
+        for (index, bar) in self.bars.iter().enumerate() {
+            let y = index as u32 * (1 + self.gap);
+            if y >= rows {
+                break;
+            }
+            put_text(
+                self.plane,
+                self.label_channels,
+                &bar.label,
+                y,
+                0,
+                label_cols,
+                NcTextAlign::Left,
+            )?;
+            let (full, rem) = eighths(fraction(bar.value, range), len);
+            self.plane.set_styles(NcStyle::None);
+            for x in 0..full + u32::from(rem > 0) {
+                let egc = if x < full { HBLOCKS[8] } else { HBLOCKS[rem] };
+                self.plane.set_channels(self.cell_channels(bar, x, len));
+                self.plane.putstr_yx(Some(y), Some(x0 + x), egc)?;
+            }
+            if value_cols > 0 {
+                let text = format_value(bar.value);
+                put_text(
+                    self.plane,
+                    self.label_channels,
+                    &text,
+                    y,
+                    value_x,
+                    value_cols,
+                    NcTextAlign::Right,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn draw_vertical(&mut self) -> NcResult<()> {
+        self.plane.erase();
+        let (rows, cols) = self.plane.dim_yx();
+        let range = self.value_range();
+        let labels = self.bars.iter().any(|b| !b.label.is_empty());
+        let bottom = rows.saturating_sub(u32::from(labels));
+        let top = u32::from(self.values);
+        let len = bottom.saturating_sub(top);
+
+        for (index, bar) in self.bars.iter().enumerate() {
+            let x = index as u32 * (self.bar_width + self.gap);
+            if x >= cols {
+                break;
+            }
+            let bar_cols = self.bar_width.min(cols - x);
+            if labels {
+                let y = rows - 1;
+                put_text(
+                    self.plane,
+                    self.label_channels,
+                    &bar.label,
+                    y,
+                    x,
+                    bar_cols,
+                    NcTextAlign::Center,
+                )?;
+            }
+            let (full, rem) = eighths(fraction(bar.value, range), len);
+            self.plane.set_styles(NcStyle::None);
+            for cell in 0..full + u32::from(rem > 0) {
+                let egc = if cell < full { VBLOCKS[8] } else { VBLOCKS[rem] };
+                self.plane.set_channels(self.cell_channels(bar, cell, len));
+                for col in 0..bar_cols {
+                    self.plane
+                        .putstr_yx(Some(bottom - 1 - cell), Some(x + col), egc)?;
+                }
+            }
+            if self.values && rows > 0 {
+                let text = format_value(bar.value);
+                put_text(
+                    self.plane,
+                    self.label_channels,
+                    &text,
+                    0,
+                    x,
+                    bar_cols,
+                    NcTextAlign::Center,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the first column and the width of the labels, the bars and the
+/// values of horizontal bars, fitted in the `cols` of the plane.
+///
+/// The bars shrink first, then the values, and then the labels.
+fn horizontal_columns(cols: u32, label_cols: u32, value_cols: u32) -> [(u32, u32); 3] {
+    let label_cols = label_cols.min(cols);
+    let x0 = if label_cols > 0 { (label_cols + 1).min(cols) } else { 0 };
+    let value_cols = value_cols.min(cols.saturating_sub(x0 + 1));
+    let len = cols - x0 - if value_cols > 0 { value_cols + 1 } else { 0 };
+    let value_x = (x0 + len + 1).min(cols);
+    [(0, label_cols), (x0, len), (value_x, value_cols)]
+}
+
+/// Writes a label or value `text` at `y`, `x` of the `plane`, fitted in `cols`.
+fn put_text(
+    plane: &mut NcPlane,
+    channels: NcChannels,
+    text: &str,
+    y: u32,
+    x: u32,
+    cols: u32,
+    align: NcTextAlign,
+) -> NcResult<()> {
+    if let Some(line) = fit(text, cols, align) {
+        plane.set_styles(NcStyle::None);
+        plane.set_channels(channels);
+        plane.putstr_yx(Some(y), Some(x + line.x), &line.text)?;
+    }
+    Ok(())
+}
+
+/// Formats a value of a bar.
+fn format_value(value: f64) -> String {
+    format!["{}", value]
+}
+
+/// *(No equivalent C style functions)*
+impl<'a> NcWidget for NcBarChart<'a> {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        Ok(self.plane)
+    }
+    fn offer_input(&mut self, _input: &NcInput) -> bool {
+        false
+    }
+    fn redraw(&mut self) -> NcResult<()> {
+        NcBarChart::redraw(self)
+    }
+    fn destroy(&mut self) -> NcResult<()> {
+        NcBarChart::destroy(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn columns() {
+        assert_eq![horizontal_columns(40, 5, 4), [(0, 5), (6, 29), (36, 4)]];
+        assert_eq![horizontal_columns(20, 0, 0), [(0, 0), (0, 20), (20, 0)]];
+        // the bars shrink first, then the values, and then the labels
+        assert_eq![horizontal_columns(8, 5, 4), [(0, 5), (6, 0), (7, 1)]];
+        assert_eq![horizontal_columns(6, 5, 4), [(0, 5), (6, 0), (6, 0)]];
+        assert_eq![horizontal_columns(4, 5, 4), [(0, 4), (4, 0), (4, 0)]];
+        assert_eq![horizontal_columns(0, 5, 4), [(0, 0), (0, 0), (0, 0)]];
+    }
+}
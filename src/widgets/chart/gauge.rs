@@ -0,0 +1,311 @@
+//! `NcGauge` widget.
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, vec::Vec};
+
+use super::{eighths, fit, fraction, HBLOCKS};
+use crate::{
+    math, widgets::NcWidget, NcChannels, NcInput, NcPlane, NcResult, NcStyle, NcTextAlign,
+};
+
+/// The glyph of the unfilled part of a gauge.
+const TRACK: &str = "░";
+
+/// The shape of an [`NcGauge`].
+///
+/// # Default
+/// *[`NcGaugeStyle::Linear`]*
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NcGaugeStyle {
+    /// A horizontal bar filling the plane, with the label on the last row.
+    #[default]
+    Linear,
+    /// A half ring, filled clockwise, with the label in its center.
+    Radial,
+}
+
+/// A gauge showing a value in a range, that changes its color at thresholds.
+///
+/// The filled part uses the channels of the highest threshold reached by the
+/// value, or the base channels below every threshold. The label shows the
+/// percentage of the value in the range, after an optional text.
+///
+/// # Example
+/// ```no_run
+/// # use libnotcurses_sys::{*, widgets::*};
+/// # fn main() -> NcResult<()> {
+/// # let nc = unsafe { Nc::new()? };
+/// # let stdplane = unsafe { nc.stdplane() };
+/// let plane = NcPlane::new_child_sized(stdplane, 0, 0, 6, 24)?;
+/// let mut gauge = NcGauge::new(plane)
+///     .style(NcGaugeStyle::Radial)
+///     .range(0.0, 120.0)
+///     .channels(NcChannels::from_rgb(0x00FF00, 0))
+///     .threshold(80.0, NcChannels::from_rgb(0xFFCC00, 0))
+///     .threshold(100.0, NcChannels::from_rgb(0xFF0000, 0))
+///     .label("temp");
+/// gauge.set_value(87.0);
+/// gauge.redraw()?;
+/// nc.render()?;
+/// # unsafe { nc.stop()? };
+/// # Ok(())
+/// # }
+/// ```
+pub struct NcGauge<'a> {
+    plane: &'a mut NcPlane,
+    style: NcGaugeStyle,
+    value: f64,
+    range: (f64, f64),
+    channels: NcChannels,
+    thresholds: Vec<(f64, NcChannels)>,
+    track_channels: NcChannels,
+    label: Option<String>,
+}
+
+/// # `NcGauge` constructors
+impl<'a> NcGauge<'a> {
+    /// New linear `NcGauge` over a `plane`, with a range from `0.0` to `1.0`.
+    pub fn new(plane: &'a mut NcPlane) -> Self {
+        Self {
+            plane,
+            style: NcGaugeStyle::default(),
+            value: 0.0,
+            range: (0.0, 1.0),
+            channels: NcChannels::default(),
+            thresholds: Vec::new(),
+            track_channels: NcChannels::default(),
+            label: None,
+        }
+    }
+
+    /// Sets the style.
+    pub fn style(mut self, style: NcGaugeStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the `min` and `max` values of the range.
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.range = (min, max);
+        self
+    }
+
+    /// Sets the channels of the filled part below every threshold.
+    pub fn channels(mut self, channels: impl Into<NcChannels>) -> Self {
+        self.channels = channels.into();
+        self
+    }
+
+    /// Adds a threshold, from which `value` on the filled part uses `channels`.
+    pub fn threshold(mut self, value: f64, channels: impl Into<NcChannels>) -> Self {
+        let index = self.thresholds.partition_point(|(t, _)| *t <= value);
+        self.thresholds.insert(index, (value, channels.into()));
+        self
+    }
+
+    /// Sets the channels of the unfilled part.
+    pub fn track_channels(mut self, channels: impl Into<NcChannels>) -> Self {
+        self.track_channels = channels.into();
+        self
+    }
+
+    /// Sets the text shown before the percentage.
+    pub fn label(mut self, label: &str) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+}
+
+/// # `NcGauge` methods
+impl<'a> NcGauge<'a> {
+    /// Returns the value.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Sets the value.
+    pub fn set_value(&mut self, value: f64) {
+        self.value = value;
+    }
+
+    /// Returns the channels of the filled part for the current value.
+    pub fn value_channels(&self) -> NcChannels {
+        threshold_channels(&self.thresholds, self.channels, self.value)
+    }
+
+    /// Redraws the gauge.
+    pub fn redraw(&mut self) -> NcResult<()> {
+        let (styles, channels) = (self.plane.styles(), self.plane.channels());
+        let res = self.draw();
+        self.plane.set_styles(styles);
+        self.plane.set_channels(channels);
+        res
+    }
+
+    /// Destroys the gauge, together with its plane.
+    pub fn destroy(&mut self) -> NcResult<()> {
+        self.plane.destroy()
+    }
+}
+
+/// # `NcGauge` private methods
+impl<'a> NcGauge<'a> {
+    fn draw(&mut self) -> NcResult<()> {
+        self.plane.erase();
+        let (rows, cols) = self.plane.dim_yx();
+        if rows == 0 || cols == 0 {
+            return Ok(());
+        }
+        let f = fraction(self.value, self.range);
+        let filled = self.value_channels();
+        self.plane.set_styles(NcStyle::None);
+
+        let (label_x, label_cols) = match self.style {
+            NcGaugeStyle::Linear => {
+                let bar_rows = if rows > 1 { rows - 1 } else { 1 };
+                let (full, rem) = eighths(f, cols);
+                for y in 0..bar_rows {
+                    for x in 0..cols {
+                        let (egc, channels) = if x < full {
+                            (HBLOCKS[8], filled)
+                        } else if x == full && rem > 0 {
+                            (HBLOCKS[rem], filled)
+                        } else {
+                            (TRACK, self.track_channels)
+                        };
+                        self.plane.set_channels(channels);
+                        self.plane.putstr_yx(Some(y), Some(x), egc)?;
+                    }
+                }
+                if rows > 1 {
+                    (0, cols)
+                } else {
+                    (0, 0)
+                }
+            }
+            NcGaugeStyle::Radial => {
+                for y in 0..rows {
+                    for x in 0..cols {
+                        let Some(position) = dial(y, x, rows, cols) else {
+                            continue;
+                        };
+                        let (egc, channels) = if position <= f && f > 0.0 {
+                            (HBLOCKS[8], filled)
+                        } else {
+                            (TRACK, self.track_channels)
+                        };
+                        self.plane.set_channels(channels);
+                        self.plane.putstr_yx(Some(y), Some(x), egc)?;
+                    }
+                }
+                hole(rows, cols)
+            }
+        };
+
+        let percent = math::round(f * 100.0);
+        let text = match &self.label {
+            Some(label) => format!["{} {}%", label, percent],
+            None => format!["{}%", percent],
+        };
+        if let Some(line) = fit(&text, label_cols, NcTextAlign::Center) {
+            let x = label_x + line.x;
+            self.plane.set_channels(NcChannels::default());
+            self.plane.putstr_yx(Some(rows - 1), Some(x), &line.text)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the channels of the highest of the sorted `thresholds` reached by
+/// the `value`, or the `base` channels.
+fn threshold_channels(
+    thresholds: &[(f64, NcChannels)],
+    base: NcChannels,
+    value: f64,
+) -> NcChannels {
+    thresholds
+        .iter()
+        .take_while(|(t, _)| *t <= value)
+        .last()
+        .map_or(base, |(_, channels)| *channels)
+}
+
+/// Returns the position along the half ring of a radial gauge of the cell at
+/// `y`, `x` of a plane of `rows` and `cols`, from `0.0` at the left end to
+/// `1.0` at the right end, or `None` if it's not part of the ring.
+///
+/// The ring is centered on the bottom row, and cells are taken to be twice
+/// as tall as they are wide.
+fn dial(y: u32, x: u32, rows: u32, cols: u32) -> Option<f64> {
+    let outer = (rows as f64 - 0.5).min(cols as f64 / 4.0);
+    let inner = outer - (outer * 0.4).max(1.0);
+    let dy = (rows - 1 - y) as f64;
+    let dx = (x as f64 + 0.5 - cols as f64 / 2.0) / 2.0;
+    let r = math::sqrt(dy * dy + dx * dx);
+    if r > outer || r <= inner {
+        return None;
+    }
+    Some(1.0 - math::atan2(dy, dx) / math::PI)
+}
+
+/// Returns the first column and the width of the hole of the ring of a radial
+/// gauge on the bottom row.
+fn hole(rows: u32, cols: u32) -> (u32, u32) {
+    let free = |x: u32| dial(rows - 1, x, rows, cols).is_none();
+    let center = cols / 2;
+    if !free(center) {
+        return (center, 0);
+    }
+    let start = (0..center).rev().find(|&x| !free(x)).map_or(0, |x| x + 1);
+    let end = (center..cols).find(|&x| !free(x)).unwrap_or(cols);
+    (start, end - start)
+}
+
+/// *(No equivalent C style functions)*
+impl<'a> NcWidget for NcGauge<'a> {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        Ok(self.plane)
+    }
+    fn offer_input(&mut self, _input: &NcInput) -> bool {
+        false
+    }
+    fn redraw(&mut self) -> NcResult<()> {
+        NcGauge::redraw(self)
+    }
+    fn destroy(&mut self) -> NcResult<()> {
+        NcGauge::destroy(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn thresholds() {
+        let (base, warn, crit) = (
+            NcChannels::from_rgb(0x00FF00, 0),
+            NcChannels::from_rgb(0xFFCC00, 0),
+            NcChannels::from_rgb(0xFF0000, 0),
+        );
+        let thresholds = [(80.0, warn), (100.0, crit)];
+        assert_eq![threshold_channels(&thresholds, base, 10.0), base];
+        assert_eq![threshold_channels(&thresholds, base, 80.0), warn];
+        assert_eq![threshold_channels(&thresholds, base, 99.9), warn];
+        assert_eq![threshold_channels(&thresholds, base, 120.0), crit];
+    }
+
+    #[test]
+    fn radial() {
+        let (rows, cols) = (5, 20);
+        // the ends of the ring on the bottom row
+        assert_eq![dial(4, 1, rows, cols).map(|p| p < 0.05), Some(true)];
+        assert_eq![dial(4, 18, rows, cols).map(|p| p > 0.95), Some(true)];
+        // the top of the ring, and the hole under it
+        assert![(dial(0, 10, rows, cols).unwrap() - 0.5).abs() < 0.05];
+        assert_eq![dial(4, 10, rows, cols), None];
+        assert_eq![dial(3, 10, rows, cols), None];
+        assert_eq![dial(4, 0, rows, cols), None];
+        assert_eq![hole(rows, cols), (5, 10)];
+    }
+}
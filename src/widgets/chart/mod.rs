@@ -0,0 +1,142 @@
+//! `NcSparkline`, `NcBarChart` & `NcGauge` widgets.
+//!
+//! Like [`NcPlotOptions`], they color their values with a gradient from the
+//! `minchannels` at the bottom of their range to the `maxchannels` at the top,
+//! interpolated in sRGB when both ends are RGB colors.
+//!
+//! [`NcPlotOptions`]: crate::widgets::NcPlotOptions
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::{
+    math, NcChannels, NcColorSpace, NcTextAlign, NcTextLayout, NcTextLine, NcTextTruncate,
+    NcTextWrap,
+};
+
+mod bars;
+mod gauge;
+mod sparkline;
+
+pub use bars::{NcBar, NcBarChart};
+pub use gauge::{NcGauge, NcGaugeStyle};
+pub use sparkline::NcSparkline;
+
+/// The horizontal eighth blocks, indexed by eighths.
+pub(crate) const HBLOCKS: [&str; 9] = [" ", "▏", "▎", "▍", "▌", "▋", "▊", "▉", "█"];
+
+/// The vertical eighth blocks, indexed by eighths.
+const VBLOCKS: [&str; 9] = [" ", "▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+
+/// Returns the `range`, or the one spanned by the `values` if it's `(0, 0)`,
+/// always including `0` in the latter case when `zero` is true.
+fn detect_range(values: impl Iterator<Item = f64>, range: (f64, f64), zero: bool) -> (f64, f64) {
+    if range != (0.0, 0.0) {
+        return range;
+    }
+    let start = if zero { (0.0, 0.0) } else { (f64::MAX, f64::MIN) };
+    let (min, max) = values
+        .filter(|v| v.is_finite())
+        .fold(start, |(min, max), v| (min.min(v), max.max(v)));
+    if min > max {
+        (0.0, 0.0)
+    } else {
+        (min, max)
+    }
+}
+
+/// Returns the position of `value` in the `range`, from `0.0` to `1.0`.
+///
+/// An empty range puts every value at the top.
+fn fraction(value: f64, (min, max): (f64, f64)) -> f64 {
+    if max > min {
+        ((value - min) / (max - min)).clamp(0.0, 1.0)
+    } else {
+        1.0
+    }
+}
+
+/// Returns the number of full cells and the remaining eighths needed to fill
+/// the fraction `f` of `cells` cells.
+fn eighths(f: f64, cells: u32) -> (u32, usize) {
+    let eighths = math::round(f * cells as f64 * 8.0) as u32;
+    (eighths / 8, (eighths % 8) as usize)
+}
+
+/// Returns the channels at the fraction `t` of the gradient from `min` to
+/// `max`.
+///
+/// The RGB colors are interpolated, and the rest is taken from the nearest end.
+fn gradient(min: NcChannels, max: NcChannels, t: f64) -> NcChannels {
+    let t = t.clamp(0.0, 1.0) as f32;
+    let mut channels = if t < 0.5 { min } else { max };
+    if min.fg_rgb_p() && max.fg_rgb_p() {
+        channels.set_fg_rgb(min.fg_rgb().lerp_in(max.fg_rgb(), t, NcColorSpace::Srgb));
+    }
+    if min.bg_rgb_p() && max.bg_rgb_p() {
+        channels.set_bg_rgb(min.bg_rgb().lerp_in(max.bg_rgb(), t, NcColorSpace::Srgb));
+    }
+    channels
+}
+
+/// Returns the `text` truncated to `cols` columns, and its offset once
+/// aligned, or `None` if there's no room.
+pub(crate) fn fit(text: &str, cols: u32, align: NcTextAlign) -> Option<NcTextLine> {
+    if cols == 0 {
+        return None;
+    }
+    let lines: Vec<NcTextLine> = NcTextLayout::new(cols)
+        .wrap(NcTextWrap::None)
+        .align(align)
+        .truncate(NcTextTruncate::Tail)
+        .max_lines(Some(1))
+        .lines(text);
+    lines.into_iter().next()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scale() {
+        let values = [3.0, -1.0, f64::NAN, 7.0];
+        assert_eq![
+            detect_range(values.into_iter(), (0.0, 0.0), false),
+            (-1.0, 7.0)
+        ];
+        assert_eq![
+            detect_range([2.0, 4.0].into_iter(), (0.0, 0.0), true),
+            (0.0, 4.0)
+        ];
+        assert_eq![detect_range([].into_iter(), (0.0, 0.0), false), (0.0, 0.0)];
+        assert_eq![
+            detect_range(values.into_iter(), (0.0, 10.0), false),
+            (0.0, 10.0)
+        ];
+
+        assert_eq![fraction(5.0, (0.0, 10.0)), 0.5];
+        assert_eq![fraction(-5.0, (0.0, 10.0)), 0.0];
+        assert_eq![fraction(5.0, (5.0, 5.0)), 1.0];
+
+        assert_eq![eighths(0.5, 3), (1, 4)];
+        assert_eq![eighths(1.0, 3), (3, 0)];
+        assert_eq![eighths(0.0, 3), (0, 0)];
+    }
+
+    #[test]
+    fn gradients() {
+        let (min, max) = (
+            NcChannels::from_rgb(0, 0),
+            NcChannels::from_rgb(0xFF0000, 0),
+        );
+        assert_eq![gradient(min, max, 0.0), min];
+        assert_eq![gradient(min, max, 2.0), max];
+        let mid = gradient(min, max, 0.5).fg_rgb();
+        assert![mid.0 > 0x700000 && mid.0 < 0x900000];
+
+        let default = NcChannels::default();
+        assert_eq![gradient(default, max, 0.2), default];
+        assert_eq![gradient(default, max, 0.8), max];
+    }
+}
@@ -0,0 +1,197 @@
+//! `NcSparkline` widget.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use super::{detect_range, fraction, gradient, VBLOCKS};
+use crate::{math, widgets::NcWidget, NcChannels, NcInput, NcPlane, NcResult, NcStyle};
+
+/// A compact one-row chart of the latest values, drawn with eighth blocks.
+///
+/// The newest value is at the right end of the first row of the plane, and
+/// it keeps as many values as columns the plane had when created, unless
+/// another [`capacity`] is set.
+///
+/// Each value is colored with the gradient from the `minchannels` to the
+/// `maxchannels` at its height, as in [`NcPlotOptions`].
+///
+/// # Example
+/// ```no_run
+/// # use libnotcurses_sys::{*, widgets::*};
+/// # fn main() -> NcResult<()> {
+/// # let nc = unsafe { Nc::new()? };
+/// # let stdplane = unsafe { nc.stdplane() };
+/// let plane = NcPlane::new_child_sized(stdplane, 0, 0, 1, 20)?;
+/// let mut spark = NcSparkline::new(plane)
+///     .channels(NcChannels::from_rgb(0x00FF00, 0), NcChannels::from_rgb(0xFF0000, 0));
+/// for load in [0.2, 0.5, 0.9, 0.4, 0.1] {
+///     spark.push(load);
+/// }
+/// spark.redraw()?;
+/// nc.render()?;
+/// # unsafe { nc.stop()? };
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`capacity`]: NcSparkline#method.capacity
+/// [`NcPlotOptions`]: crate::widgets::NcPlotOptions
+pub struct NcSparkline<'a> {
+    plane: &'a mut NcPlane,
+    values: Vec<f64>,
+    capacity: usize,
+    range: (f64, f64),
+    minchannels: NcChannels,
+    maxchannels: NcChannels,
+}
+
+/// # `NcSparkline` constructors
+impl<'a> NcSparkline<'a> {
+    /// New empty `NcSparkline` over a `plane`, with a detected range.
+    pub fn new(plane: &'a mut NcPlane) -> Self {
+        let capacity = plane.dim_x() as usize;
+        Self {
+            plane,
+            values: Vec::new(),
+            capacity,
+            range: (0.0, 0.0),
+            minchannels: NcChannels::default(),
+            maxchannels: NcChannels::default(),
+        }
+    }
+
+    /// Sets the number of values kept.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self.truncate();
+        self
+    }
+
+    /// Sets the `min` and `max` values of the range.
+    ///
+    /// If both are `0.0` the range spans the values shown.
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.range = (min, max);
+        self
+    }
+
+    /// Sets the channels of the bottom and the top of the range.
+    pub fn channels(
+        mut self,
+        minchannels: impl Into<NcChannels>,
+        maxchannels: impl Into<NcChannels>,
+    ) -> Self {
+        self.minchannels = minchannels.into();
+        self.maxchannels = maxchannels.into();
+        self
+    }
+}
+
+/// # `NcSparkline` methods
+impl<'a> NcSparkline<'a> {
+    /// Returns the values, from the oldest.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Appends a `value`, discarding the oldest one if it's full.
+    ///
+    /// A `NaN` leaves a gap.
+    pub fn push(&mut self, value: f64) {
+        self.values.push(value);
+        self.truncate();
+    }
+
+    /// Replaces the values, keeping the latest ones that fit.
+    pub fn set_values(&mut self, values: &[f64]) {
+        self.values = values.into();
+        self.truncate();
+    }
+
+    /// Discards all the values.
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    /// Redraws the sparkline.
+    pub fn redraw(&mut self) -> NcResult<()> {
+        let (styles, channels) = (self.plane.styles(), self.plane.channels());
+        let res = self.draw();
+        self.plane.set_styles(styles);
+        self.plane.set_channels(channels);
+        res
+    }
+
+    /// Destroys the sparkline, together with its plane.
+    pub fn destroy(&mut self) -> NcResult<()> {
+        self.plane.destroy()
+    }
+}
+
+/// # `NcSparkline` private methods
+impl<'a> NcSparkline<'a> {
+    fn truncate(&mut self) {
+        let excess = self.values.len().saturating_sub(self.capacity);
+        self.values.drain(..excess);
+    }
+
+    fn draw(&mut self) -> NcResult<()> {
+        self.plane.erase();
+        let cols = self.plane.dim_x() as usize;
+        let shown = &self.values[self.values.len().saturating_sub(cols)..];
+        let range = detect_range(shown.iter().copied(), self.range, false);
+        let x0 = cols - shown.len();
+
+        self.plane.set_styles(NcStyle::None);
+        for (x, &value) in shown.iter().enumerate() {
+            let Some(egc) = spark(value, range) else {
+                continue;
+            };
+            let channels = gradient(self.minchannels, self.maxchannels, fraction(value, range));
+            self.plane.set_channels(channels);
+            self.plane.putstr_yx(Some(0), Some((x0 + x) as u32), egc)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the block for the `value` in the `range`, from the lowest eighth
+/// for the bottom to the full block for the top, or `None` if it's `NaN`.
+fn spark(value: f64, range: (f64, f64)) -> Option<&'static str> {
+    if value.is_nan() {
+        return None;
+    }
+    let level = math::round(fraction(value, range) * 7.0) as usize;
+    Some(VBLOCKS[level + 1])
+}
+
+/// *(No equivalent C style functions)*
+impl<'a> NcWidget for NcSparkline<'a> {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        Ok(self.plane)
+    }
+    fn offer_input(&mut self, _input: &NcInput) -> bool {
+        false
+    }
+    fn redraw(&mut self) -> NcResult<()> {
+        NcSparkline::redraw(self)
+    }
+    fn destroy(&mut self) -> NcResult<()> {
+        NcSparkline::destroy(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn blocks() {
+        let range = (0.0, 7.0);
+        assert_eq![spark(0.0, range), Some("▁")];
+        assert_eq![spark(3.0, range), Some("▄")];
+        assert_eq![spark(7.0, range), Some("█")];
+        assert_eq![spark(99.0, range), Some("█")];
+        assert_eq![spark(f64::NAN, range), None];
+    }
+}
@@ -0,0 +1,550 @@
+//! `NcDialog` widget.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::{
+    widgets::{NcBorder, NcFrame, NcReader, NcReaderOptions, NcWidget},
+    Nc, NcAlign, NcChannels, NcGraphemes, NcInput, NcInputType, NcKey, NcPlane, NcReceived,
+    NcResult, NcStyle, NcTextLayout, NcTextLine,
+};
+
+/// The maximum columns of the text of a dialog.
+const MAX_WIDTH: u32 = 60;
+
+/// The minimum columns of the input field of a prompt.
+const INPUT_WIDTH: u32 = 30;
+
+/// The columns between buttons.
+const BUTTON_GAP: u32 = 2;
+
+/// The answer to an [`NcDialog`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NcDialogAnswer {
+    /// The message was acknowledged.
+    Ok,
+    /// The question was confirmed.
+    Yes,
+    /// The question was denied.
+    No,
+    /// The dialog was dismissed, e.g. with `Esc`.
+    Cancel,
+    /// The text entered in a prompt.
+    Text(String),
+}
+
+/// # `NcDialogAnswer` methods
+impl NcDialogAnswer {
+    /// Returns the label of the button for this answer.
+    pub fn label(&self) -> &'static str {
+        match self {
+            NcDialogAnswer::Ok | NcDialogAnswer::Text(_) => "OK",
+            NcDialogAnswer::Yes => "Yes",
+            NcDialogAnswer::No => "No",
+            NcDialogAnswer::Cancel => "Cancel",
+        }
+    }
+}
+
+/// A modal box with a message and some buttons, and optionally an input field,
+/// centered over a parent plane.
+///
+/// There are three kinds of dialogs:
+/// - a [`message`], with an `OK` button.
+/// - a [`confirm`]ation, with `Yes`, `No` and `Cancel` buttons.
+/// - a [`prompt`], with an input field and `OK` and `Cancel` buttons,
+///   which answers with the entered text.
+///
+/// While it's pending, the dialog captures all the input offered to it.
+/// It can either be [`run`] until it's answered, or be polled for its
+/// [`answer`] while it's offered the input from an event loop.
+/// In both cases destroying it uncovers the planes underneath.
+///
+/// The [`Nc.alert`], [`Nc.confirm`] and [`Nc.prompt`] methods run a dialog
+/// over the standard plane.
+///
+/// # Input
+/// - `Left`, `Right`, `Tab` and `Shift+Tab` move between the buttons
+///   (only the tab keys in a prompt).
+/// - `Enter` and a left click on a button answer with that button.
+/// - the initial of a button answers with it, except in a prompt.
+/// - `Esc` cancels, or acknowledges a message.
+///
+/// # Example
+/// ```no_run
+/// # use libnotcurses_sys::{*, widgets::*};
+/// # fn main() -> NcResult<()> {
+/// # let mut nc = unsafe { Nc::new()? };
+/// // blocking
+/// if nc.confirm("Delete the file?")? == NcDialogAnswer::Yes {
+///     nc.alert("The file was deleted.")?;
+/// }
+///
+/// // pollable
+/// let stdplane = unsafe { nc.stdplane() };
+/// let mut dialog = NcDialog::prompt(stdplane, "Name:", "")?.title(" New file ");
+/// dialog.redraw()?;
+/// nc.render()?;
+/// let mut input = NcInput::new_empty();
+/// while dialog.answer().is_none() {
+///     nc.get_blocking(Some(&mut input))?;
+///     dialog.offer_input(&input);
+///     nc.render()?;
+/// }
+/// let answer = dialog.answer().cloned();
+/// dialog.destroy()?;
+/// nc.render()?;
+/// # unsafe { nc.stop()? };
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`message`]: NcDialog#method.message
+/// [`confirm`]: NcDialog#method.confirm
+/// [`prompt`]: NcDialog#method.prompt
+/// [`run`]: NcDialog#method.run
+/// [`answer`]: NcDialog#method.answer
+/// [`Nc.alert`]: crate::Nc#method.alert
+/// [`Nc.confirm`]: crate::Nc#method.confirm
+/// [`Nc.prompt`]: crate::Nc#method.prompt
+pub struct NcDialog<'a> {
+    plane: &'a mut NcPlane,
+    reader: Option<&'a mut NcReader>,
+    border: NcBorder,
+    title: Option<String>,
+    lines: Vec<NcTextLine>,
+    buttons: Vec<NcDialogAnswer>,
+    selected: usize,
+    answer: Option<NcDialogAnswer>,
+    button_channels: NcChannels,
+    selected_channels: NcChannels,
+}
+
+/// # `NcDialog` constructors
+impl<'a> NcDialog<'a> {
+    /// New `NcDialog` showing a message, centered over `parent`.
+    ///
+    /// It's answered with [`Ok`][NcDialogAnswer::Ok].
+    pub fn message(parent: &mut NcPlane, text: &str) -> NcResult<Self> {
+        Self::new(parent, text, vec![NcDialogAnswer::Ok], None)
+    }
+
+    /// New `NcDialog` asking a question, centered over `parent`.
+    ///
+    /// It's answered with [`Yes`][NcDialogAnswer::Yes],
+    /// [`No`][NcDialogAnswer::No] or [`Cancel`][NcDialogAnswer::Cancel].
+    pub fn confirm(parent: &mut NcPlane, text: &str) -> NcResult<Self> {
+        let buttons = vec![
+            NcDialogAnswer::Yes,
+            NcDialogAnswer::No,
+            NcDialogAnswer::Cancel,
+        ];
+        Self::new(parent, text, buttons, None)
+    }
+
+    /// New `NcDialog` asking for a line of text, starting with `initial`,
+    /// centered over `parent`.
+    ///
+    /// It's answered with [`Text`][NcDialogAnswer::Text] or
+    /// [`Cancel`][NcDialogAnswer::Cancel].
+    pub fn prompt(parent: &mut NcPlane, text: &str, initial: &str) -> NcResult<Self> {
+        let buttons = vec![NcDialogAnswer::Ok, NcDialogAnswer::Cancel];
+        Self::new(parent, text, buttons, Some(initial))
+    }
+
+    /// Sets the title, on the top border.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the border.
+    pub fn border(mut self, border: NcBorder) -> Self {
+        self.border = border;
+        self
+    }
+
+    /// Sets the channels of the buttons, and of the selected one.
+    pub fn button_channels(
+        mut self,
+        buttons: impl Into<NcChannels>,
+        selected: impl Into<NcChannels>,
+    ) -> Self {
+        self.button_channels = buttons.into();
+        self.selected_channels = selected.into();
+        self
+    }
+
+    fn new(
+        parent: &mut NcPlane,
+        text: &str,
+        buttons: Vec<NcDialogAnswer>,
+        input: Option<&str>,
+    ) -> NcResult<Self> {
+        let (parent_y, parent_x) = parent.dim_yx();
+        let max_cols = parent_x.saturating_sub(4).clamp(1, MAX_WIDTH);
+        let lines = NcTextLayout::new(max_cols).lines(text);
+
+        let labels: Vec<&str> = buttons.iter().map(|b| b.label()).collect();
+        let mut cols = lines.iter().map(|l| l.cols).max().unwrap_or(0);
+        cols = cols.max(buttons_width(&labels));
+        if input.is_some() {
+            cols = cols.max(INPUT_WIDTH);
+        }
+        let cols = cols.min(parent_x.saturating_sub(4)).max(1) + 4;
+        let extra = if input.is_some() { 2 } else { 0 };
+        let rows = (lines.len() as u32 + 4 + extra).min(parent_y.max(1));
+
+        let y = (parent_y.saturating_sub(rows) / 2) as i32;
+        let x = (parent_x.saturating_sub(cols) / 2) as i32;
+        let plane = NcPlane::new_child_sized(parent, y, x, rows, cols.min(parent_x.max(1)))?;
+        plane.move_top();
+        let reader = plane
+            .set_base(" ", NcStyle::None, NcChannels::default())
+            .and_then(|_| match input {
+                Some(initial) => {
+                    new_reader(plane, lines.len() as i32 + 2, cols - 4, initial).map(Some)
+                }
+                None => Ok(None),
+            });
+        let reader = match reader {
+            Ok(reader) => reader,
+            Err(error) => {
+                let _ = plane.destroy();
+                return Err(error);
+            }
+        };
+
+        let mut selected_channels = NcChannels::default();
+        selected_channels.reverse();
+        Ok(Self {
+            plane,
+            reader,
+            border: NcBorder::Rounded,
+            title: None,
+            lines,
+            buttons,
+            selected: 0,
+            answer: None,
+            button_channels: NcChannels::default(),
+            selected_channels,
+        })
+    }
+}
+
+/// # `NcDialog` methods
+impl<'a> NcDialog<'a> {
+    /// Returns the answer, or `None` while it's pending.
+    pub fn answer(&self) -> Option<&NcDialogAnswer> {
+        self.answer.as_ref()
+    }
+
+    /// Returns the text of the input field, if it's a prompt.
+    pub fn input(&self) -> Option<String> {
+        self.reader.as_ref().map(|r| r.contents())
+    }
+
+    /// Returns the answer of the selected button.
+    pub fn selected(&self) -> &NcDialogAnswer {
+        &self.buttons[self.selected]
+    }
+
+    /// Offers the `input` to the dialog, redrawing it if it's consumed.
+    ///
+    /// While the dialog is pending all the input is consumed, so it returns
+    /// true, except for the mouse events outside of it.
+    /// Once it's answered it returns false.
+    pub fn offer_input(&mut self, input: &NcInput) -> bool {
+        if self.answer.is_some() {
+            return false;
+        }
+        let consumed = if NcKey(input.id).is_mouse() {
+            self.offer_mouse(input)
+        } else if input.evtype == NcInputType::Release as u32 {
+            true
+        } else {
+            self.offer_key(input)
+        };
+        if consumed {
+            let _ = self.redraw();
+        }
+        consumed
+    }
+
+    /// Redraws the dialog in its entirety.
+    pub fn redraw(&mut self) -> NcResult<()> {
+        let (styles, channels) = (self.plane.styles(), self.plane.channels());
+        let res = self.draw();
+        self.plane.set_styles(styles);
+        self.plane.set_channels(channels);
+        res
+    }
+
+    /// Runs the dialog until it's answered, destroys it, and returns the
+    /// answer.
+    pub fn run(mut self, nc: &mut Nc) -> NcResult<NcDialogAnswer> {
+        let res = self.redraw().and_then(|_| {
+            nc.render()?;
+            let mut input = NcInput::new_empty();
+            while self.answer.is_none() {
+                nc.get_blocking(Some(&mut input))?;
+                if self.offer_input(&input) {
+                    nc.render()?;
+                }
+            }
+            Ok(())
+        });
+        let prompt = self.reader.is_some();
+        self.destroy()?;
+        if prompt {
+            let _ = nc.cursor_disable();
+        }
+        nc.render()?;
+        res.map(|_| self.answer.unwrap_or(NcDialogAnswer::Cancel))
+    }
+
+    /// Destroys the dialog, together with its plane.
+    pub fn destroy(&mut self) -> NcResult<()> {
+        if let Some(reader) = self.reader.take() {
+            reader.destroy();
+        }
+        self.plane.destroy()
+    }
+}
+
+/// # `NcDialog` private methods
+impl<'a> NcDialog<'a> {
+    /// Answers with the button at `index`.
+    fn choose(&mut self, index: usize) {
+        self.selected = index;
+        self.answer = Some(match (&self.buttons[index], &self.reader) {
+            (NcDialogAnswer::Ok, Some(reader)) => NcDialogAnswer::Text(reader.contents()),
+            (answer, _) => answer.clone(),
+        });
+    }
+
+    /// Answers when dismissed.
+    fn cancel(&mut self) {
+        let index = self
+            .buttons
+            .iter()
+            .position(|b| *b == NcDialogAnswer::Cancel)
+            .unwrap_or(0);
+        self.choose(index);
+    }
+
+    fn offer_key(&mut self, input: &NcInput) -> bool {
+        let len = self.buttons.len();
+        let plain = !input.ctrl_p() && !input.alt_p();
+        match NcReceived::from(input) {
+            NcReceived::Key(NcKey::Enter) => self.choose(self.selected),
+            NcReceived::Key(NcKey::Esc) => self.cancel(),
+            NcReceived::Key(NcKey::Tab) if input.shift_p() => {
+                self.selected = (self.selected + len - 1) % len;
+            }
+            NcReceived::Key(NcKey::Tab) => self.selected = (self.selected + 1) % len,
+            NcReceived::Key(NcKey::Left) if self.reader.is_none() => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            NcReceived::Key(NcKey::Right) if self.reader.is_none() => {
+                self.selected = (self.selected + 1).min(len - 1);
+            }
+            NcReceived::Char(c) if plain && self.reader.is_none() => {
+                let labels: Vec<&str> = self.buttons.iter().map(|b| b.label()).collect();
+                if let Some(index) = shortcut(&labels, c) {
+                    self.choose(index);
+                }
+            }
+            _ => {
+                if let Some(reader) = self.reader.as_mut() {
+                    NcReader::offer_input(reader, *input);
+                }
+            }
+        }
+        true
+    }
+
+    fn offer_mouse(&mut self, input: &NcInput) -> bool {
+        let (mut y, mut x) = (input.y, input.x);
+        if !self.plane.translate_abs(&mut y, &mut x) {
+            return false;
+        }
+        let released = input.evtype == NcInputType::Release as u32;
+        if released && input.id == NcKey::Button1.0 && y as u32 == self.buttons_row() {
+            let labels: Vec<&str> = self.buttons.iter().map(|b| b.label()).collect();
+            let spans = button_spans(&labels, self.plane.dim_x());
+            if let Some(index) = spans
+                .iter()
+                .position(|&(start, cols)| x as u32 >= start && (x as u32) < start + cols)
+            {
+                self.choose(index);
+            }
+        }
+        true
+    }
+
+    /// Returns the row of the buttons.
+    fn buttons_row(&self) -> u32 {
+        self.plane.dim_y().saturating_sub(2)
+    }
+
+    fn draw(&mut self) -> NcResult<()> {
+        self.plane.erase();
+        let (rows, cols) = self.plane.dim_yx();
+        self.plane.set_styles(NcStyle::None);
+        self.plane.set_channels(NcChannels::default());
+        for (y, line) in self.lines.iter().enumerate() {
+            let y = y as u32 + 1;
+            if y + 2 >= rows {
+                break;
+            }
+            self.plane
+                .putstr_yx(Some(y), Some(line.x + 2), &line.text)?;
+        }
+
+        let labels: Vec<&str> = self.buttons.iter().map(|b| b.label()).collect();
+        let y = self.buttons_row();
+        for (index, (x, _)) in button_spans(&labels, cols).into_iter().enumerate() {
+            if index == self.selected {
+                self.plane.set_styles(NcStyle::Bold);
+                self.plane.set_channels(self.selected_channels);
+            } else {
+                self.plane.set_styles(NcStyle::None);
+                self.plane.set_channels(self.button_channels);
+            }
+            let button = ["[ ", labels[index], " ]"].concat();
+            self.plane.putstr_yx(Some(y), Some(x), &button)?;
+        }
+        if rows >= 2 && cols >= 2 {
+            let mut frame = NcFrame::new(self.border.clone());
+            if let Some(title) = &self.title {
+                frame = frame.title(title, NcAlign::Center);
+            }
+            frame.perimeter(self.plane)?;
+        }
+        Ok(())
+    }
+}
+
+/// Creates a one-row reader at `y` of the `plane`, with the `initial` text.
+fn new_reader<'r>(
+    plane: &mut NcPlane,
+    y: i32,
+    cols: u32,
+    initial: &str,
+) -> NcResult<&'r mut NcReader> {
+    let field = NcPlane::new_child_sized(plane, y, 2, 1, cols)?;
+    if let Err(error) = field.set_base(" ", NcStyle::Underline, NcChannels::default()) {
+        let _ = field.destroy();
+        return Err(error);
+    }
+    let options = NcReaderOptions {
+        tchannels: NcChannels::default().into(),
+        tattrword: NcStyle::Underline.0.into(),
+        flags: (NcReaderOptions::HORSCROLL | NcReaderOptions::CURSOR).into(),
+    };
+    let reader = NcReader::with_options(field, &options)?;
+    for egc in NcGraphemes::new(initial) {
+        if let Err(error) = reader.write_egc(egc) {
+            reader.destroy();
+            return Err(error);
+        }
+    }
+    Ok(reader)
+}
+
+/// Returns the columns of the buttons with `labels`, including the gaps.
+fn buttons_width(labels: &[&str]) -> u32 {
+    let cols: u32 = labels.iter().map(|l| l.len() as u32 + 4).sum();
+    cols + BUTTON_GAP * (labels.len() as u32).saturating_sub(1)
+}
+
+/// Returns the starting column and the width of the buttons with `labels`,
+/// centered in `cols` columns.
+fn button_spans(labels: &[&str], cols: u32) -> Vec<(u32, u32)> {
+    let mut x = cols.saturating_sub(buttons_width(labels)) / 2;
+    labels
+        .iter()
+        .map(|label| {
+            let span = (x, label.len() as u32 + 4);
+            x += span.1 + BUTTON_GAP;
+            span
+        })
+        .collect()
+}
+
+/// Returns the index of the button whose label starts with `c`,
+/// case-insensitively.
+fn shortcut(labels: &[&str], c: char) -> Option<usize> {
+    let c = c.to_ascii_lowercase();
+    labels
+        .iter()
+        .position(|l| l.chars().next().map(|i| i.to_ascii_lowercase()) == Some(c))
+}
+
+/// # `Nc` dialog methods
+impl Nc {
+    /// Shows a message over the standard plane, until it's acknowledged.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn alert(&mut self, text: &str) -> NcResult<()> {
+        let stdplane = unsafe { self.stdplane() };
+        NcDialog::message(stdplane, text)?.run(self).map(|_| ())
+    }
+
+    /// Asks a question over the standard plane, until it's answered with
+    /// [`Yes`][NcDialogAnswer::Yes], [`No`][NcDialogAnswer::No] or
+    /// [`Cancel`][NcDialogAnswer::Cancel].
+    ///
+    /// *(No equivalent C style function)*
+    pub fn confirm(&mut self, text: &str) -> NcResult<NcDialogAnswer> {
+        let stdplane = unsafe { self.stdplane() };
+        NcDialog::confirm(stdplane, text)?.run(self)
+    }
+
+    /// Asks for a line of text over the standard plane, starting with
+    /// `initial`, and returns it, or `None` if it's cancelled.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn prompt(&mut self, text: &str, initial: &str) -> NcResult<Option<String>> {
+        let stdplane = unsafe { self.stdplane() };
+        match NcDialog::prompt(stdplane, text, initial)?.run(self)? {
+            NcDialogAnswer::Text(text) => Ok(Some(text)),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// *(No equivalent C style functions)*
+impl<'a> NcWidget for NcDialog<'a> {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        Ok(self.plane)
+    }
+    fn offer_input(&mut self, input: &NcInput) -> bool {
+        NcDialog::offer_input(self, input)
+    }
+    fn redraw(&mut self) -> NcResult<()> {
+        NcDialog::redraw(self)
+    }
+    fn destroy(&mut self) -> NcResult<()> {
+        NcDialog::destroy(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn buttons() {
+        let labels = ["Yes", "No", "Cancel"];
+        assert_eq![buttons_width(&labels), 7 + 6 + 10 + 4];
+        assert_eq![button_spans(&labels, 31), vec![(2, 7), (11, 6), (19, 10)]];
+        assert_eq![button_spans(&labels, 10)[0], (0, 7)];
+        assert_eq![buttons_width(&[]), 0];
+
+        assert_eq![shortcut(&labels, 'n'), Some(1)];
+        assert_eq![shortcut(&labels, 'C'), Some(2)];
+        assert_eq![shortcut(&labels, 'x'), None];
+    }
+}
@@ -0,0 +1,388 @@
+//! `NcFocusRing`
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use crate::{
+    c_api, widgets::NcWidget, NcInput, NcInputType, NcKey, NcKeyMod, NcReceived, NcResult,
+};
+
+/// A key chord: a key or character together with its modifiers.
+type Chord = (u32, NcKeyMod);
+
+/// The callback notified when a widget gains (`true`) or loses (`false`)
+/// the focus.
+type FocusCallback<'a> = Box<dyn FnMut(&mut dyn NcWidget, bool) + 'a>;
+
+/// Routes the input between several widgets, one of them having the focus.
+///
+/// The widgets are kept in tab order. Each input is offered:
+/// 1. to the global widgets, in the order they were added, until one
+///    consumes it, e.g. an [`NcMenu`] for its accelerators.
+/// 2. if it's a mouse event, to the widget whose plane contains it,
+///    which gains the focus with a button press.
+/// 3. if it's a focus chord, the focus moves to the next or previous widget.
+///    By default these are `Tab` and `Shift+Tab`.
+/// 4. to the focused widget.
+///
+/// The widgets are told whenever they gain or lose the focus by
+/// [`NcWidget.focus_changed`], and by the [`on_focus`] callback, which
+/// allows to restyle the widgets that don't react on their own.
+///
+/// # Example
+/// ```no_run
+/// # use libnotcurses_sys::{*, widgets::*};
+/// fn run(nc: &mut Nc, menu: &mut NcMenu, tree: &mut NcTree, reader: &mut NcReader) -> NcResult<()> {
+///     let mut ring = NcFocusRing::new()
+///         .global(menu)
+///         .on_focus(|widget, focused| {
+///             let bg = if focused { 0x224466 } else { 0x000000 };
+///             if let Ok(plane) = widget.plane() {
+///                 let _ = plane.set_base(" ", NcStyle::None, NcChannels::from_rgb(0xFFFFFF, bg));
+///             }
+///         });
+///     ring.push(tree);
+///     ring.push(reader);
+///
+///     let mut input = NcInput::new_empty();
+///     loop {
+///         let received = nc.get_blocking(Some(&mut input))?;
+///         if received == NcReceived::Char('q') && input.ctrl_p() {
+///             break;
+///         }
+///         if ring.offer_input(&input) {
+///             nc.render()?;
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// [`NcMenu`]: crate::widgets::NcMenu
+/// [`NcWidget.focus_changed`]: NcWidget#method.focus_changed
+/// [`on_focus`]: NcFocusRing#method.on_focus
+pub struct NcFocusRing<'a> {
+    widgets: Vec<Box<dyn NcWidget + 'a>>,
+    globals: Vec<Box<dyn NcWidget + 'a>>,
+    focused: Option<usize>,
+    next_chords: Vec<Chord>,
+    prev_chords: Vec<Chord>,
+    on_focus: Option<FocusCallback<'a>>,
+}
+
+impl<'a> Default for NcFocusRing<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// # `NcFocusRing` constructors
+impl<'a> NcFocusRing<'a> {
+    /// New empty `NcFocusRing`, moving the focus with `Tab` and `Shift+Tab`.
+    pub fn new() -> Self {
+        Self {
+            widgets: Vec::new(),
+            globals: Vec::new(),
+            focused: None,
+            next_chords: vec![(NcKey::Tab.0, NcKeyMod::None)],
+            prev_chords: vec![(NcKey::Tab.0, NcKeyMod::Shift)],
+            on_focus: None,
+        }
+    }
+
+    /// Adds a global `widget`, which is offered the input before the others,
+    /// and never gets the focus.
+    pub fn global(mut self, widget: impl NcWidget + 'a) -> Self {
+        self.globals.push(Box::new(widget));
+        self
+    }
+
+    /// Sets the `callback` called with a widget and `true` when it gains the
+    /// focus, or `false` when it loses it.
+    pub fn on_focus(mut self, callback: impl FnMut(&mut dyn NcWidget, bool) + 'a) -> Self {
+        self.on_focus = Some(Box::new(callback));
+        self
+    }
+
+    /// Replaces the chords that move the focus to the next widget.
+    pub fn next_chords(mut self, chords: &[(NcReceived, NcKeyMod)]) -> Self {
+        self.next_chords = chords.iter().map(|&(r, m)| (r.into(), m)).collect();
+        self
+    }
+
+    /// Replaces the chords that move the focus to the previous widget.
+    pub fn prev_chords(mut self, chords: &[(NcReceived, NcKeyMod)]) -> Self {
+        self.prev_chords = chords.iter().map(|&(r, m)| (r.into(), m)).collect();
+        self
+    }
+}
+
+/// # `NcFocusRing` methods
+impl<'a> NcFocusRing<'a> {
+    /// Adds a `widget` at the end of the tab order, and returns its index.
+    ///
+    /// The first widget added gains the focus.
+    pub fn push(&mut self, widget: impl NcWidget + 'a) -> usize {
+        self.widgets.push(Box::new(widget));
+        let index = self.widgets.len() - 1;
+        if self.focused.is_none() {
+            self.focus(index);
+        }
+        index
+    }
+
+    /// Returns the number of widgets in the tab order.
+    pub fn len(&self) -> usize {
+        self.widgets.len()
+    }
+
+    /// Returns true if there are no widgets in the tab order.
+    pub fn is_empty(&self) -> bool {
+        self.widgets.is_empty()
+    }
+
+    /// Returns a mutable reference to the widget at `index` in the tab order.
+    pub fn widget(&mut self, index: usize) -> Option<&mut (dyn NcWidget + 'a)> {
+        self.widgets.get_mut(index).map(|w| w.as_mut())
+    }
+
+    /// Returns the index of the focused widget.
+    pub fn focused(&self) -> Option<usize> {
+        self.focused
+    }
+
+    /// Returns a mutable reference to the focused widget.
+    pub fn focused_widget(&mut self) -> Option<&mut (dyn NcWidget + 'a)> {
+        let index = self.focused?;
+        self.widget(index)
+    }
+
+    /// Gives the focus to the widget at `index`.
+    ///
+    /// Returns false if there's no such widget.
+    pub fn focus(&mut self, index: usize) -> bool {
+        if index >= self.widgets.len() {
+            return false;
+        }
+        if self.focused == Some(index) {
+            return true;
+        }
+        if let Some(old) = self.focused.replace(index) {
+            self.notify(old, false);
+        }
+        self.notify(index, true);
+        true
+    }
+
+    /// Moves the focus to the next widget, wrapping around.
+    pub fn focus_next(&mut self) -> bool {
+        let len = self.widgets.len();
+        match self.focused {
+            _ if len == 0 => false,
+            Some(i) => self.focus((i + 1) % len),
+            None => self.focus(0),
+        }
+    }
+
+    /// Moves the focus to the previous widget, wrapping around.
+    pub fn focus_prev(&mut self) -> bool {
+        let len = self.widgets.len();
+        match self.focused {
+            _ if len == 0 => false,
+            Some(i) => self.focus((i + len - 1) % len),
+            None => self.focus(len - 1),
+        }
+    }
+
+    /// Offers the `input` to the global widgets, the widget under the mouse,
+    /// the focus chords and the focused widget, in that order.
+    ///
+    /// Returns true if the input was consumed.
+    pub fn offer_input(&mut self, input: &NcInput) -> bool {
+        if self.globals.iter_mut().any(|w| w.offer_input(input)) {
+            return true;
+        }
+
+        let release = input.evtype == NcInputType::Release as u32;
+        let key = NcKey(input.id);
+        if key.is_mouse() {
+            return match self.hit(input.y, input.x) {
+                Some(index) => {
+                    if !release && key != NcKey::Motion {
+                        self.focus(index);
+                    }
+                    self.widgets[index].offer_input(input)
+                }
+                None => false,
+            };
+        }
+
+        if !release {
+            if matches_chord(&self.next_chords, input) {
+                return self.focus_next();
+            }
+            if matches_chord(&self.prev_chords, input) {
+                return self.focus_prev();
+            }
+        }
+
+        match self.focused {
+            Some(index) => self.widgets[index].offer_input(input),
+            None => false,
+        }
+    }
+
+    /// Destroys all the widgets, including the global ones.
+    pub fn destroy(&mut self) -> NcResult<()> {
+        self.focused = None;
+        for widget in self.globals.iter_mut().chain(self.widgets.iter_mut()) {
+            widget.destroy()?;
+        }
+        self.globals.clear();
+        self.widgets.clear();
+        Ok(())
+    }
+
+    /// Returns the index of the widget whose plane contains the absolute
+    /// coordinates, checking the focused widget first.
+    fn hit(&mut self, y: i32, x: i32) -> Option<usize> {
+        let focused = self.focused;
+        let order = focused
+            .into_iter()
+            .chain((0..self.widgets.len()).filter(|&i| Some(i) != focused));
+        for index in order {
+            if let Ok(plane) = self.widgets[index].plane() {
+                let (mut y, mut x) = (y, x);
+                if plane.translate_abs(&mut y, &mut x) {
+                    return Some(index);
+                }
+            }
+        }
+        None
+    }
+
+    /// Notifies the widget at `index` it has gained or lost the focus.
+    fn notify(&mut self, index: usize, focused: bool) {
+        let widget = &mut self.widgets[index];
+        widget.focus_changed(focused);
+        if let Some(callback) = self.on_focus.as_mut() {
+            callback(widget.as_mut(), focused);
+        }
+    }
+}
+
+/// Returns true if the `input` matches any of the `chords`.
+///
+/// The lock modifiers are ignored.
+fn matches_chord(chords: &[Chord], input: &NcInput) -> bool {
+    let locks = c_api::NCKEY_MOD_CAPSLOCK | c_api::NCKEY_MOD_NUMLOCK;
+    let modifiers = input.modifiers & !locks;
+    chords
+        .iter()
+        .any(|&(id, m)| id == input.id && m.0 == modifiers)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{NcError, NcPlane};
+    use core::cell::RefCell;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::string::String;
+
+    /// A widget without a plane that consumes a single character, and logs
+    /// its focus changes.
+    struct Mock<'l> {
+        name: char,
+        consumes: char,
+        log: &'l RefCell<Vec<String>>,
+    }
+
+    impl<'l> NcWidget for Mock<'l> {
+        fn plane(&mut self) -> NcResult<&mut NcPlane> {
+            Err(NcError::new())
+        }
+        fn offer_input(&mut self, input: &NcInput) -> bool {
+            if input.id == self.consumes as u32 {
+                self.log.borrow_mut().push(
+                    [self.name, char::from_u32(input.id).unwrap()]
+                        .iter()
+                        .collect(),
+                );
+                true
+            } else {
+                false
+            }
+        }
+        fn redraw(&mut self) -> NcResult<()> {
+            Ok(())
+        }
+        fn destroy(&mut self) -> NcResult<()> {
+            Ok(())
+        }
+        fn focus_changed(&mut self, focused: bool) {
+            self.log.borrow_mut().push(
+                [self.name, if focused { '+' } else { '-' }]
+                    .iter()
+                    .collect(),
+            );
+        }
+    }
+
+    fn take(log: &RefCell<Vec<String>>) -> Vec<String> {
+        log.borrow_mut().drain(..).collect()
+    }
+
+    #[test]
+    fn focus() {
+        let log = RefCell::new(Vec::new());
+        let mut ring = NcFocusRing::new()
+            .global(Mock { name: 'g', consumes: 'm', log: &log })
+            .next_chords(&[(NcReceived::Char('n'), NcKeyMod::Ctrl)]);
+        assert![!ring.focus_next()];
+        assert_eq![ring.focused(), None];
+
+        ring.push(Mock { name: 'a', consumes: 'x', log: &log });
+        ring.push(Mock { name: 'b', consumes: 'x', log: &log });
+        ring.push(Mock { name: 'c', consumes: 'y', log: &log });
+        assert_eq![ring.len(), 3];
+        assert_eq![ring.focused(), Some(0)];
+        assert_eq![take(&log), ["a+"]];
+
+        // globals first, then the focused widget
+        assert![ring.offer_input(&NcInput::new('m'))];
+        assert![ring.offer_input(&NcInput::new('x'))];
+        assert![!ring.offer_input(&NcInput::new('y'))];
+        assert_eq![take(&log), ["gm", "ax"]];
+
+        // custom next chord, default previous chord
+        assert![!ring.offer_input(&NcInput::new('\t'))];
+        assert![ring.offer_input(&NcInput::with_ctrl('n'))];
+        assert_eq![ring.focused(), Some(1)];
+        assert![ring.offer_input(&NcInput::with_shift('\t'))];
+        assert![ring.offer_input(&NcInput::with_shift('\t'))];
+        assert_eq![ring.focused(), Some(2)];
+        assert![ring.offer_input(&NcInput::new('y'))];
+        assert_eq![take(&log), ["a-", "b+", "b-", "a+", "a-", "c+", "cy"]];
+
+        assert![ring.focus(2)];
+        assert![!ring.focus(3)];
+        assert![ring.focus_next()];
+        assert_eq![ring.focused(), Some(0)];
+        assert_eq![take(&log), ["c-", "a+"]];
+    }
+
+    #[test]
+    fn on_focus() {
+        let log = RefCell::new(Vec::new());
+        let changes = RefCell::new(Vec::new());
+        let mut ring = NcFocusRing::new().on_focus(|_, focused| changes.borrow_mut().push(focused));
+        ring.push(Mock { name: 'a', consumes: 'x', log: &log });
+        ring.push(Mock { name: 'b', consumes: 'x', log: &log });
+        ring.focus_prev();
+        assert_eq![ring.focused(), Some(1)];
+        drop(ring);
+        assert_eq![*changes.borrow(), [true, false, true]];
+    }
+}
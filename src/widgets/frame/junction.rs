@@ -0,0 +1,86 @@
+//! Box-drawing glyphs indexed by the arms they connect.
+
+/// Arm pointing up.
+pub(crate) const UP: u8 = 1;
+/// Arm pointing right.
+pub(crate) const RIGHT: u8 = 2;
+/// Arm pointing down.
+pub(crate) const DOWN: u8 = 4;
+/// Arm pointing left.
+pub(crate) const LEFT: u8 = 8;
+
+/// The weight of the lines of a set of box-drawing glyphs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Weight {
+    Light,
+    Heavy,
+    Double,
+    Ascii,
+}
+
+// indexed by the bitmask of arms
+const LIGHT: [&str; 16] = [
+    " ", "╵", "╶", "└", "╷", "│", "┌", "├", "╴", "┘", "─", "┴", "┐", "┤", "┬", "┼",
+];
+const HEAVY: [&str; 16] = [
+    " ", "╹", "╺", "┗", "╻", "┃", "┏", "┣", "╸", "┛", "━", "┻", "┓", "┫", "┳", "╋",
+];
+const DOUBLE: [&str; 16] = [
+    " ", "║", "═", "╚", "║", "║", "╔", "╠", "═", "╝", "═", "╩", "╗", "╣", "╦", "╬",
+];
+const ASCII: [&str; 16] = [
+    " ", "|", "-", "+", "|", "|", "+", "+", "-", "+", "-", "+", "+", "+", "+", "+",
+];
+
+/// Returns the glyph connecting the `arms` with lines of `weight`.
+pub(crate) fn glyph(arms: u8, weight: Weight) -> &'static str {
+    let table = match weight {
+        Weight::Light => &LIGHT,
+        Weight::Heavy => &HEAVY,
+        Weight::Double => &DOUBLE,
+        Weight::Ascii => &ASCII,
+    };
+    table[(arms & 0xF) as usize]
+}
+
+/// Returns the arms and weight of a box-drawing `egc`, if it's one.
+///
+/// The rounded corners are considered light.
+pub(crate) fn arms(egc: &str) -> Option<(u8, Weight)> {
+    match egc {
+        "╭" => return Some((RIGHT | DOWN, Weight::Light)),
+        "╮" => return Some((LEFT | DOWN, Weight::Light)),
+        "╰" => return Some((UP | RIGHT, Weight::Light)),
+        "╯" => return Some((UP | LEFT, Weight::Light)),
+        _ => (),
+    }
+    for (table, weight) in [
+        (&LIGHT, Weight::Light),
+        (&HEAVY, Weight::Heavy),
+        (&DOUBLE, Weight::Double),
+        (&ASCII, Weight::Ascii),
+    ] {
+        // searching backwards favours the lines over their halves
+        if let Some(arms) = (1..16).rev().find(|&i| table[i] == egc) {
+            return Some((arms as u8, weight));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn glyphs() {
+        assert_eq![glyph(UP | RIGHT | DOWN, Weight::Light), "├"];
+        assert_eq![glyph(UP | DOWN | LEFT | RIGHT, Weight::Double), "╬"];
+        assert_eq![glyph(LEFT | DOWN, Weight::Ascii), "+"];
+
+        assert_eq![arms("┳"), Some((LEFT | RIGHT | DOWN, Weight::Heavy))];
+        assert_eq![arms("║"), Some((UP | DOWN, Weight::Double))];
+        assert_eq![arms("╯"), Some((UP | LEFT, Weight::Light))];
+        assert_eq![arms("a"), None];
+    }
+}
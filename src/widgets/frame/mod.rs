@@ -0,0 +1,410 @@
+//! `NcFrame` widget.
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, vec::Vec};
+
+use crate::{
+    c_api::NCRESULT_ERR, NcAlign, NcBoxMask, NcCell, NcChannels, NcError, NcGraphemes, NcPlane,
+    NcResult, NcStyle, NcTextAlign, NcTextLayout, NcTextTruncate, NcTextWrap,
+};
+
+mod junction;
+use junction::{Weight, DOWN, LEFT, RIGHT, UP};
+
+/// The set of glyphs of the border of an [`NcFrame`].
+///
+/// # Default
+/// *[`NcBorder::Light`]*
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum NcBorder {
+    /// Light lines: `┌┐└┘─│`.
+    #[default]
+    Light,
+    /// Heavy lines: `┏┓┗┛━┃`.
+    Heavy,
+    /// Double lines: `╔╗╚╝═║`.
+    Double,
+    /// Light lines with rounded corners: `╭╮╰╯─│`.
+    Rounded,
+    /// ASCII characters: `++++-|`.
+    Ascii,
+    /// A custom set of 6 `EGC`s: the upper-left, upper-right, lower-left and
+    /// lower-right corners, and the horizontal and vertical lines.
+    ///
+    /// Its junctions are never merged.
+    Custom(String),
+}
+
+/// # `NcBorder` methods
+impl NcBorder {
+    /// Returns the `EGC`s of the border: the upper-left, upper-right,
+    /// lower-left and lower-right corners, and the horizontal and vertical
+    /// lines.
+    ///
+    /// Returns `None` if a custom border doesn't have exactly 6 `EGC`s.
+    pub fn egcs(&self) -> Option<[&str; 6]> {
+        let set = match self {
+            NcBorder::Light => "┌┐└┘─│",
+            NcBorder::Heavy => "┏┓┗┛━┃",
+            NcBorder::Double => "╔╗╚╝═║",
+            NcBorder::Rounded => "╭╮╰╯─│",
+            NcBorder::Ascii => "++++-|",
+            NcBorder::Custom(set) => set,
+        };
+        let mut egcs = [""; 6];
+        let mut graphemes = NcGraphemes::new(set);
+        for egc in egcs.iter_mut() {
+            *egc = graphemes.next()?;
+        }
+        graphemes.next().is_none().then_some(egcs)
+    }
+
+    /// Returns the weight of the lines, used for the junctions.
+    fn weight(&self) -> Option<Weight> {
+        match self {
+            NcBorder::Light | NcBorder::Rounded => Some(Weight::Light),
+            NcBorder::Heavy => Some(Weight::Heavy),
+            NcBorder::Double => Some(Weight::Double),
+            NcBorder::Ascii => Some(Weight::Ascii),
+            NcBorder::Custom(_) => None,
+        }
+    }
+}
+
+/// A rectangular border, with an optional title and footer.
+///
+/// Unlike [`NcPlane.box`], the glyphs of the border are chosen with an
+/// [`NcBorder`], and the sides masked with [`NcBoxMask`] are left out
+/// without leaving their corners behind.
+///
+/// When frames share an edge, the corners and crossings of the border are
+/// merged with the box-drawing glyphs already in the plane, turning them into
+/// the corresponding T and cross junctions, drawn with the weight of the
+/// last frame. ASCII borders only merge with each other.
+///
+/// The title and the footer are placed on the top and bottom borders,
+/// between the corners, and truncated with an ellipsis if they don't fit.
+///
+/// # Example
+/// ```no_run
+/// # use libnotcurses_sys::{*, widgets::*};
+/// # fn main() -> NcResult<()> {
+/// # let nc = unsafe { Nc::new()? };
+/// # let plane = unsafe { nc.stdplane() };
+/// let frame = NcFrame::new(NcBorder::Rounded)
+///     .title(" Files ", NcAlign::Left)
+///     .footer(" 3 items ", NcAlign::Right);
+/// frame.draw(plane, 0, 0, 10, 20)?;
+/// // a second frame sharing the right edge of the first one
+/// frame.draw(plane, 0, 19, 10, 20)?;
+/// nc.render()?;
+/// # unsafe { nc.stop()? };
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`NcPlane.box`]: crate::NcPlane#method.box
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NcFrame {
+    border: NcBorder,
+    styles: NcStyle,
+    channels: NcChannels,
+    mask: NcBoxMask,
+    title: Option<(String, NcAlign)>,
+    footer: Option<(String, NcAlign)>,
+    merge: bool,
+}
+
+impl Default for NcFrame {
+    fn default() -> Self {
+        Self::new(NcBorder::default())
+    }
+}
+
+/// # `NcFrame` constructors
+impl NcFrame {
+    /// New `NcFrame` with the provided `border`, and the default styles and
+    /// channels, that merges its junctions.
+    pub fn new(border: NcBorder) -> Self {
+        Self {
+            border,
+            styles: NcStyle::None,
+            channels: NcChannels::default(),
+            mask: NcBoxMask::None,
+            title: None,
+            footer: None,
+            merge: true,
+        }
+    }
+
+    /// Sets the styles of the border, title and footer.
+    pub fn styles(mut self, styles: impl Into<NcStyle>) -> Self {
+        self.styles = styles.into();
+        self
+    }
+
+    /// Sets the channels of the border, title and footer.
+    pub fn channels(mut self, channels: impl Into<NcChannels>) -> Self {
+        self.channels = channels.into();
+        self
+    }
+
+    /// Sets the sides to leave out, using the `Mask*` flags of [`NcBoxMask`].
+    ///
+    /// The rest of the flags are ignored.
+    pub fn mask(mut self, mask: impl Into<NcBoxMask>) -> Self {
+        self.mask = mask.into();
+        self
+    }
+
+    /// Sets the title, aligned on the top border.
+    pub fn title(mut self, title: &str, align: NcAlign) -> Self {
+        self.title = Some((title.into(), align));
+        self
+    }
+
+    /// Sets the footer, aligned on the bottom border.
+    pub fn footer(mut self, footer: &str, align: NcAlign) -> Self {
+        self.footer = Some((footer.into(), align));
+        self
+    }
+
+    /// Sets whether to merge the junctions with the box-drawing glyphs already
+    /// in the plane.
+    pub fn merge(mut self, merge: bool) -> Self {
+        self.merge = merge;
+        self
+    }
+}
+
+/// # `NcFrame` methods
+impl NcFrame {
+    /// Returns the border.
+    pub fn border(&self) -> &NcBorder {
+        &self.border
+    }
+
+    /// Draws the frame on the `plane`, with its upper-left corner at `y`, `x`,
+    /// having dimensions `len_y` * `len_x`.
+    ///
+    /// The minimum frame size is 2x2, and it cannot be drawn off the plane.
+    /// The styles and channels of the plane are left untouched.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn draw(
+        &self,
+        plane: &mut NcPlane,
+        y: u32,
+        x: u32,
+        len_y: u32,
+        len_x: u32,
+    ) -> NcResult<()> {
+        let Some(egcs) = self.border.egcs() else {
+            return Err(NcError::with_msg(
+                NCRESULT_ERR,
+                &format!["NcFrame.draw(): {:?} doesn't have 6 EGCs", self.border],
+            ));
+        };
+        let (dim_y, dim_x) = plane.dim_yx();
+        if len_y < 2 || len_x < 2 || y + len_y > dim_y || x + len_x > dim_x {
+            return Err(NcError::with_msg(
+                NCRESULT_ERR,
+                &format![
+                    "NcFrame.draw({}, {}, {}, {}): invalid size",
+                    y, x, len_y, len_x
+                ],
+            ));
+        }
+
+        let (styles, channels) = (plane.styles(), plane.channels());
+        plane.set_styles(self.styles);
+        plane.set_channels(self.channels);
+        let res = self
+            .draw_border(plane, &egcs, y, x, len_y, len_x)
+            .and_then(|_| {
+                if let Some((title, align)) = &self.title {
+                    put_label(plane, title, *align, y, x, len_x)?;
+                }
+                if let Some((footer, align)) = &self.footer {
+                    put_label(plane, footer, *align, y + len_y - 1, x, len_x)?;
+                }
+                Ok(())
+            });
+        plane.set_styles(styles);
+        plane.set_channels(channels);
+        res
+    }
+
+    /// Draws the frame around the whole `plane`.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn perimeter(&self, plane: &mut NcPlane) -> NcResult<()> {
+        let (len_y, len_x) = plane.dim_yx();
+        self.draw(plane, 0, 0, len_y, len_x)
+    }
+
+    fn draw_border(
+        &self,
+        plane: &mut NcPlane,
+        egcs: &[&str; 6],
+        y: u32,
+        x: u32,
+        len_y: u32,
+        len_x: u32,
+    ) -> NcResult<()> {
+        let mut cell = NcCell::new();
+        for (cy, cx, arms) in self.arms(len_y, len_x) {
+            let existing = if self.merge {
+                plane.at_yx_cell(y + cy, x + cx, &mut cell)?;
+                let existing = junction::arms(cell.egc(plane));
+                cell.release(plane);
+                existing
+            } else {
+                None
+            };
+            let glyph = self.glyph(egcs, cy, cx, arms, existing);
+            plane.putstr_yx(Some(y + cy), Some(x + cx), glyph)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the cells of the border that have to be drawn, relative to the
+    /// frame, as `(y, x, arms)`.
+    fn arms(&self, len_y: u32, len_x: u32) -> Vec<(u32, u32, u8)> {
+        let top = !self.mask.has(NcBoxMask::MaskTop);
+        let right = !self.mask.has(NcBoxMask::MaskRight);
+        let bottom = !self.mask.has(NcBoxMask::MaskBottom);
+        let left = !self.mask.has(NcBoxMask::MaskLeft);
+
+        let mut cells = Vec::new();
+        for y in 0..len_y {
+            for x in 0..len_x {
+                let mut arms = 0;
+                if (y == 0 && top) || (y == len_y - 1 && bottom) {
+                    arms |= if x > 0 { LEFT } else { 0 } | if x < len_x - 1 { RIGHT } else { 0 };
+                }
+                if (x == 0 && left) || (x == len_x - 1 && right) {
+                    arms |= if y > 0 { UP } else { 0 } | if y < len_y - 1 { DOWN } else { 0 };
+                }
+                if arms != 0 {
+                    cells.push((y, x, arms));
+                }
+            }
+        }
+        cells
+    }
+
+    /// Returns the glyph for the cell at `y`, `x` of the border, with the
+    /// `arms` of the frame, merged with the `existing` ones.
+    fn glyph<'a>(
+        &self,
+        egcs: &[&'a str; 6],
+        y: u32,
+        x: u32,
+        arms: u8,
+        existing: Option<(u8, Weight)>,
+    ) -> &'a str {
+        if let (Some((other, other_weight)), Some(weight)) = (existing, self.border.weight()) {
+            let ascii = weight == Weight::Ascii;
+            if (other_weight == Weight::Ascii) == ascii && other | arms != arms {
+                return junction::glyph(other | arms, weight);
+            }
+        }
+        let horizontal = arms & (LEFT | RIGHT) != 0;
+        let vertical = arms & (UP | DOWN) != 0;
+        match (horizontal, vertical) {
+            (true, true) => egcs[(if y == 0 { 0 } else { 2 }) + (if x == 0 { 0 } else { 1 })],
+            (true, false) => egcs[4],
+            _ => egcs[5],
+        }
+    }
+}
+
+/// Writes a `label` aligned on row `y`, between the corners of a frame.
+fn put_label(
+    plane: &mut NcPlane,
+    label: &str,
+    align: NcAlign,
+    y: u32,
+    x: u32,
+    len_x: u32,
+) -> NcResult<()> {
+    let align = match align {
+        NcAlign::Left => NcTextAlign::Left,
+        NcAlign::Center => NcTextAlign::Center,
+        NcAlign::Right => NcTextAlign::Right,
+        NcAlign::Unaligned => return Ok(()),
+    };
+    if len_x <= 2 {
+        return Ok(());
+    }
+    let layout = NcTextLayout::new(len_x - 2)
+        .wrap(NcTextWrap::None)
+        .truncate(NcTextTruncate::Tail)
+        .max_lines(Some(1))
+        .align(align);
+    if let Some(line) = layout.lines(label).first() {
+        plane.putstr_yx(Some(y), Some(x + 1 + line.x), &line.text)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::{string::String, vec::Vec};
+
+    /// Returns the frame as rows of text, merged with the `existing` rows.
+    fn render(frame: &NcFrame, existing: &[&str]) -> Vec<String> {
+        let egcs = frame.border().egcs().unwrap();
+        let mut rows: Vec<Vec<String>> = existing
+            .iter()
+            .map(|row| NcGraphemes::new(row).map(String::from).collect())
+            .collect();
+        let (len_y, len_x) = (rows.len() as u32, rows[0].len() as u32);
+        for (y, x, arms) in frame.arms(len_y, len_x) {
+            let cell = &mut rows[y as usize][x as usize];
+            let other = frame.merge.then(|| junction::arms(cell)).flatten();
+            *cell = frame.glyph(&egcs, y, x, arms, other).into();
+        }
+        rows.into_iter().map(|row| row.concat()).collect()
+    }
+
+    #[test]
+    fn borders() {
+        let blank = ["    "; 3];
+        let frame = NcFrame::new(NcBorder::Rounded);
+        assert_eq![render(&frame, &blank), ["╭──╮", "│  │", "╰──╯"]];
+
+        let frame = NcFrame::new(NcBorder::Heavy).mask(NcBoxMask::MaskLeft);
+        assert_eq![render(&frame, &blank), ["━━━┓", "   ┃", "━━━┛"]];
+
+        let frame = NcFrame::new(NcBorder::Custom("1234-|".into()))
+            .mask(NcBoxMask::MaskTop | NcBoxMask::MaskBottom);
+        assert_eq![render(&frame, &blank), ["|  |", "|  |", "|  |"]];
+
+        assert![NcBorder::Custom("12345".into()).egcs().is_none()];
+        assert![NcBorder::Custom("1234567".into()).egcs().is_none()];
+    }
+
+    #[test]
+    fn junctions() {
+        // a frame sharing its left edge with the right edge of another
+        let frame = NcFrame::new(NcBorder::Light);
+        let existing = ["┐   ", "│   ", "┘   "];
+        assert_eq![render(&frame, &existing), ["┬──┐", "│  │", "┴──┘"]];
+
+        // a heavy frame over a light cross
+        let frame = NcFrame::new(NcBorder::Heavy);
+        let existing = ["  │  ", "──┼──", "  │  "];
+        assert_eq![render(&frame, &existing), ["┏━╋━┓", "╋─┼─╋", "┗━╋━┛"]];
+
+        let frame = NcFrame::new(NcBorder::Light).merge(false);
+        assert_eq![render(&frame, &existing), ["┌───┐", "│─┼─│", "└───┘"]];
+
+        let frame = NcFrame::new(NcBorder::Ascii);
+        assert_eq![render(&frame, &["-|-", "   "]), ["+++", "+-+"]];
+    }
+}
@@ -0,0 +1,483 @@
+//! `NcMenuBuilder` & `NcActionMenu`
+
+use core::ptr::null_mut;
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    boxed::Box,
+    ffi::CString,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::ffi::CString;
+
+use crate::{
+    c_api, error_ref_mut,
+    widgets::{NcMenu, NcMenuItem, NcMenuOptions, NcMenuSection, NcWidget},
+    NcChannels, NcError, NcInput, NcInputType, NcKey, NcPlane, NcResult,
+};
+
+/// An action attached to a menu item.
+type Action<'a> = Box<dyn FnMut() + 'a>;
+
+/// A handy builder for an [`NcMenu`] whose items run actions.
+///
+/// The builder owns the strings of the sections and items, and the
+/// resulting [`NcActionMenu`] owns the actions.
+///
+/// # Example
+/// ```no_run
+/// # use libnotcurses_sys::{*, widgets::*};
+/// # fn main() -> NcResult<()> {
+/// # let nc = unsafe { Nc::new()? };
+/// # let stdplane = unsafe { nc.stdplane() };
+/// let quit = core::cell::Cell::new(false);
+/// let mut saved = 0;
+/// let mut menu = NcMenu::builder()
+///     .section("File", Some(NcInput::with_alt('f')))
+///     .item("Save", Some(NcInput::with_ctrl('s')), || saved += 1)
+///     .separator()
+///     .item("Quit", Some(NcInput::with_ctrl('q')), || quit.set(true))
+///     .finish(stdplane)?;
+///
+/// let mut input = NcInput::new_empty();
+/// while !quit.get() {
+///     nc.get_blocking(Some(&mut input))?;
+///     menu.offer_input(input);
+///     nc.render()?;
+/// }
+/// menu.destroy();
+/// # unsafe { nc.stop()? };
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct NcMenuBuilder<'a> {
+    sections: Vec<Section<'a>>,
+    header_channels: NcChannels,
+    section_channels: NcChannels,
+    flags: u64,
+    /// Whether an item was added before any section.
+    orphan_items: bool,
+}
+
+/// A section being built.
+struct Section<'a> {
+    name: String,
+    shortcut: NcInput,
+    /// The items, with `None` for a separator.
+    items: Vec<Option<(String, NcInput, Action<'a>)>>,
+}
+
+impl<'a> NcMenuBuilder<'a> {
+    /// New `NcMenuBuilder`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a new section, which will contain the following items.
+    ///
+    /// The `shortcut` unrolls the section, and is underlined if its
+    /// character is present in the `name`.
+    pub fn section(mut self, name: &str, shortcut: Option<NcInput>) -> Self {
+        self.sections.push(Section {
+            name: name.to_string(),
+            shortcut: shortcut.unwrap_or_else(NcInput::new_empty),
+            items: Vec::new(),
+        });
+        self
+    }
+
+    /// Adds an item to the current section, running the `action`
+    /// when it's activated.
+    ///
+    /// The `shortcut` activates the item, even if the menu is rolled up.
+    /// The descriptions must be unique among all the items of the menu.
+    pub fn item(
+        mut self,
+        desc: &str,
+        shortcut: Option<NcInput>,
+        action: impl FnMut() + 'a,
+    ) -> Self {
+        let item = (
+            desc.to_string(),
+            shortcut.unwrap_or_else(NcInput::new_empty),
+            Box::new(action) as Action<'a>,
+        );
+        match self.sections.last_mut() {
+            Some(section) => section.items.push(Some(item)),
+            None => self.orphan_items = true,
+        }
+        self
+    }
+
+    /// Adds a horizontal separator to the current section.
+    pub fn separator(mut self) -> Self {
+        match self.sections.last_mut() {
+            Some(section) => section.items.push(None),
+            None => self.orphan_items = true,
+        }
+        self
+    }
+
+    /// Sets the `NcChannels` of the header.
+    pub fn header_channels(mut self, channels: impl Into<NcChannels>) -> Self {
+        self.header_channels = channels.into();
+        self
+    }
+
+    /// Sets the `NcChannels` of the sections.
+    pub fn section_channels(mut self, channels: impl Into<NcChannels>) -> Self {
+        self.section_channels = channels.into();
+        self
+    }
+
+    /// Places the menu on the bottom row, instead of the top one.
+    pub fn bottom(mut self, bottom: bool) -> Self {
+        self.set_flag(NcMenuOptions::BOTTOM, bottom);
+        self
+    }
+
+    /// Hides the menu when it's not unrolled.
+    pub fn hiding(mut self, hiding: bool) -> Self {
+        self.set_flag(NcMenuOptions::HIDING, hiding);
+        self
+    }
+
+    /// Finishes the builder and returns the [`NcActionMenu`].
+    ///
+    /// Returns an error if there are no sections, if any section has no items,
+    /// if any item was added before the first section,
+    /// or if any item description is repeated.
+    pub fn finish(self, plane: &mut NcPlane) -> NcResult<NcActionMenu<'a>> {
+        self.validate()?;
+
+        // the C library copies all the strings
+        let mut names = Vec::new();
+        let mut descs = Vec::new();
+        for section in self.sections.iter() {
+            names.push(cstring(&section.name)?);
+            for (desc, _, _) in section.items.iter().flatten() {
+                descs.push(cstring(desc)?);
+            }
+        }
+        let mut descs_iter = descs.iter();
+        let mut items: Vec<Vec<NcMenuItem>> = self
+            .sections
+            .iter()
+            .map(|section| {
+                section
+                    .items
+                    .iter()
+                    .map(|item| match item {
+                        Some((_, shortcut, _)) => NcMenuItem {
+                            desc: descs_iter
+                                .next()
+                                .map_or(null_mut(), |d| d.as_ptr() as *mut _),
+                            shortcut: *shortcut,
+                        },
+                        None => NcMenuItem::new_empty(),
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut sections: Vec<NcMenuSection> = self
+            .sections
+            .iter()
+            .zip(names.iter())
+            .zip(items.iter_mut())
+            .map(|((section, name), items)| NcMenuSection {
+                name: name.as_ptr() as *mut _,
+                items: items.as_mut_ptr(),
+                itemcount: items.len() as i32,
+                shortcut: section.shortcut,
+            })
+            .collect();
+        let options = NcMenuOptions::with_all_args(
+            &mut sections,
+            self.header_channels,
+            self.section_channels,
+            self.flags,
+        );
+        let menu = NcMenu::new(plane, &options)?;
+
+        let mut actions = Vec::new();
+        for section in self.sections.into_iter() {
+            for (desc, shortcut, action) in section.items.into_iter().flatten() {
+                actions.push(MenuAction {
+                    section: section.name.clone(),
+                    desc,
+                    shortcut,
+                    enabled: true,
+                    action,
+                });
+            }
+        }
+        Ok(NcActionMenu { menu, actions })
+    }
+
+    fn set_flag(&mut self, flag: u64, on: bool) {
+        if on {
+            self.flags |= flag;
+        } else {
+            self.flags &= !flag;
+        }
+    }
+
+    /// Returns an error if the menu can't be created.
+    fn validate(&self) -> NcResult<()> {
+        let err = |msg: &str| {
+            Err(NcError::with_msg(
+                c_api::NCRESULT_ERR,
+                &format!["NcMenuBuilder.finish(): {}", msg],
+            ))
+        };
+        if self.orphan_items {
+            return err("item added before the first section");
+        }
+        if self.sections.is_empty() {
+            return err("no sections");
+        }
+        if let Some(section) = self
+            .sections
+            .iter()
+            .find(|s| s.items.iter().all(|i| i.is_none()))
+        {
+            return err(&format!["section {:?} has no items", section.name]);
+        }
+        let descs: Vec<&str> = self
+            .sections
+            .iter()
+            .flat_map(|s| s.items.iter().flatten().map(|(desc, _, _)| desc.as_str()))
+            .collect();
+        for (i, desc) in descs.iter().enumerate() {
+            if descs[i + 1..].contains(desc) {
+                return err(&format!["duplicated item {:?}", desc]);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns a `CString`, or an error if the `string` contains a nul byte.
+fn cstring(string: &str) -> NcResult<CString> {
+    CString::new(string).map_err(|_| {
+        NcError::with_msg(
+            c_api::NCRESULT_ERR,
+            &format!["NcMenuBuilder.finish(): nul byte in {:?}", string],
+        )
+    })
+}
+
+/// An [`NcMenu`] whose items run actions when they're activated,
+/// created with [`NcMenuBuilder`].
+///
+/// An item is activated by pressing `Enter` while it's selected,
+/// by clicking it, or by its shortcut.
+///
+/// The menu must be destroyed with [`destroy`]. The actions are
+/// freed when this is dropped.
+///
+/// [`destroy`]: NcActionMenu#method.destroy
+pub struct NcActionMenu<'a> {
+    menu: &'a mut NcMenu,
+    actions: Vec<MenuAction<'a>>,
+}
+
+/// The action of an item, and what's needed to find it.
+struct MenuAction<'a> {
+    section: String,
+    desc: String,
+    shortcut: NcInput,
+    enabled: bool,
+    action: Action<'a>,
+}
+
+/// # `NcActionMenu` methods
+impl<'a> NcActionMenu<'a> {
+    /// Returns a mutable reference to the inner [`NcMenu`].
+    pub fn menu(&mut self) -> &mut NcMenu {
+        self.menu
+    }
+
+    /// Offers the `input` to the menu, running the action of the item it
+    /// activates, if any.
+    ///
+    /// Returns true if the input was consumed. Besides the inputs relevant
+    /// to [`NcMenu.offer_input`], it consumes:
+    /// - the shortcut of an enabled item.
+    /// - `Enter` on an unrolled section.
+    /// - a mouse click on an item.
+    ///
+    /// [`NcMenu.offer_input`]: NcMenu#method.offer_input
+    pub fn offer_input(&mut self, input: NcInput) -> bool {
+        let release = input.evtype == NcInputType::Release as u32;
+        if input.id == NcKey::Button1.0 && release {
+            if let Ok(desc) = self.menu.mouse_selected(input, None) {
+                self.run(&desc);
+                return true;
+            }
+        } else if !release {
+            if let Some(index) = self
+                .actions
+                .iter()
+                .position(|a| a.enabled && shortcut_p(&a.shortcut, &input))
+            {
+                self.run_index(index);
+                return true;
+            }
+            if input.id == NcKey::Enter.0 && self.activate_selected() {
+                return true;
+            }
+        }
+        self.menu.offer_input(input)
+    }
+
+    /// Returns the description of the item corresponding to the mouse
+    /// `click`, running its action.
+    ///
+    /// See [`NcMenu.mouse_selected`].
+    ///
+    /// [`NcMenu.mouse_selected`]: NcMenu#method.mouse_selected
+    pub fn mouse_selected(
+        &mut self,
+        click: NcInput,
+        shortcut: Option<&mut NcInput>,
+    ) -> NcResult<String> {
+        let desc = self.menu.mouse_selected(click, shortcut)?;
+        self.run(&desc);
+        Ok(desc)
+    }
+
+    /// Runs the action of the selected item, if there's an unrolled section,
+    /// and rolls up the menu.
+    ///
+    /// Returns true if there was a selected item.
+    pub fn activate_selected(&mut self) -> bool {
+        match self.menu.selected(None) {
+            Some(desc) => {
+                self.run(&desc);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Disables or enables an item.
+    ///
+    /// Disabled items can't be selected, and their shortcuts are ignored.
+    ///
+    /// *C style function: [ncmenu_item_set_status()][c_api::ncmenu_item_set_status].*
+    pub fn item_set_status(&mut self, section: &str, item: &str, enabled: bool) -> NcResult<()> {
+        self.menu.item_set_status(section, item, enabled)?;
+        if let Some(a) = self
+            .actions
+            .iter_mut()
+            .find(|a| a.section == section && a.desc == item)
+        {
+            a.enabled = enabled;
+        }
+        Ok(())
+    }
+
+    /// Destroys the menu.
+    ///
+    /// *C style function: [ncmenu_destroy()][c_api::ncmenu_destroy].*
+    pub fn destroy(&mut self) {
+        self.menu.destroy();
+    }
+
+    /// Rolls up the menu and runs the action of the item with `desc`.
+    fn run(&mut self, desc: &str) {
+        if let Some(index) = self.actions.iter().position(|a| a.desc == desc) {
+            self.run_index(index);
+        }
+    }
+
+    fn run_index(&mut self, index: usize) {
+        let _ = self.menu.rollup();
+        (self.actions[index].action)();
+    }
+}
+
+/// *C style functions: [ncmenu_plane()][c_api::ncmenu_plane],
+/// [ncmenu_offer_input()][c_api::ncmenu_offer_input],
+/// [ncmenu_destroy()][c_api::ncmenu_destroy].*
+impl<'a> NcWidget for NcActionMenu<'a> {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![
+            unsafe { c_api::ncmenu_plane(self.menu) },
+            "NcActionMenu.plane()"
+        ]
+    }
+    fn offer_input(&mut self, input: &NcInput) -> bool {
+        NcActionMenu::offer_input(self, *input)
+    }
+    /// Does nothing, the menu redraws itself on every change.
+    fn redraw(&mut self) -> NcResult<()> {
+        Ok(())
+    }
+    fn destroy(&mut self) -> NcResult<()> {
+        NcActionMenu::destroy(self);
+        Ok(())
+    }
+}
+
+/// Returns true if the `input` matches the `shortcut` of an item.
+///
+/// The coordinates and the lock modifiers are ignored.
+fn shortcut_p(shortcut: &NcInput, input: &NcInput) -> bool {
+    let locks = c_api::NCKEY_MOD_CAPSLOCK | c_api::NCKEY_MOD_NUMLOCK;
+    shortcut.id != 0
+        && shortcut.id == input.id
+        && shortcut.modifiers & !locks == input.modifiers & !locks
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::NcKeyMod;
+
+    #[test]
+    fn validate() {
+        let builder = NcMenuBuilder::new().item("orphan", None, || ());
+        assert![builder.validate().is_err()];
+        assert![NcMenuBuilder::new().validate().is_err()];
+
+        let builder = NcMenuBuilder::new().section("File", None).separator();
+        assert![builder.validate().is_err()];
+
+        let builder = NcMenuBuilder::new()
+            .section("File", None)
+            .item("Open", None, || ())
+            .section("Edit", None)
+            .item("Open", None, || ());
+        assert![builder.validate().is_err()];
+
+        let builder = NcMenuBuilder::new()
+            .section("File", None)
+            .item("Open", None, || ())
+            .separator()
+            .item("Quit", None, || ());
+        assert![builder.validate().is_ok()];
+    }
+
+    #[test]
+    fn shortcuts() {
+        let shortcut = NcInput::with_ctrl('s');
+        assert![shortcut_p(&shortcut, &NcInput::with_ctrl('s'))];
+        let mut input = NcInput::with_all_args(
+            's',
+            Some(3),
+            Some(4),
+            NcKeyMod::Ctrl | NcKeyMod::CapsLock,
+            NcInputType::Press,
+        );
+        assert![shortcut_p(&shortcut, &input)];
+        input.modifiers = NcKeyMod::Alt.into();
+        assert![!shortcut_p(&shortcut, &input)];
+        assert![!shortcut_p(&NcInput::new_empty(), &NcInput::new_empty())];
+    }
+}
@@ -9,7 +9,7 @@ use alloc::{
 use crate::{
     c_api::{self, ncmenu_create},
     cstring, error, error_ref_mut, error_str, rstring,
-    widgets::{NcMenu, NcMenuOptions},
+    widgets::{NcMenu, NcMenuBuilder, NcMenuOptions},
     NcInput, NcPlane, NcResult,
 };
 
@@ -29,6 +29,11 @@ impl NcMenu {
         error_ref_mut![unsafe { ncmenu_create(plane, options) }, "Creating NcMenu"]
     }
 
+    /// Starts the builder of a menu whose items run actions.
+    pub fn builder<'a>() -> NcMenuBuilder<'a> {
+        NcMenuBuilder::new()
+    }
+
     /// Destroys an `NcMenu` created with [`new`][NcMenu#method.new].
     ///
     /// *C style function: [ncmenu_destroy()][c_api::ncmenu_destroy].*
@@ -21,8 +21,11 @@
 
 use crate::c_api::ffi;
 
+mod builder;
 mod methods;
 
+pub use builder::{NcActionMenu, NcMenuBuilder};
+
 /// menus on the top or bottom rows
 ///
 /// An [Nc][crate::Nc] instance supports menu bars on the top or bottom row
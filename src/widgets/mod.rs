@@ -1,21 +1,39 @@
 //! The notcurses widgets.
 
+pub(crate) mod chart;
+pub(crate) mod dialog;
+pub(crate) mod focus;
+pub(crate) mod frame;
 pub(crate) mod menu;
 pub(crate) mod multiselector;
+pub(crate) mod pager;
+pub(crate) mod picker;
 pub(crate) mod plot;
 pub(crate) mod progbar;
 pub(crate) mod reader;
 pub(crate) mod reel;
 pub(crate) mod selector;
 pub(crate) mod tabbed;
+pub(crate) mod table;
 pub(crate) mod tree;
+pub(crate) mod widget;
 
+pub use chart::{NcBar, NcBarChart, NcGauge, NcGaugeStyle, NcSparkline};
+pub use dialog::{NcDialog, NcDialogAnswer};
+pub use focus::NcFocusRing;
+pub use frame::{NcBorder, NcFrame};
 pub use menu::*;
 pub use multiselector::*;
+pub use pager::NcPager;
+pub use picker::NcPicker;
 pub use plot::*;
 pub use progbar::*;
 pub use reader::*;
 pub use reel::*;
-pub use selector::{NcSelector, NcSelectorBuilder, NcSelectorItem, NcSelectorOptions};
+pub use selector::{
+    NcItemSelector, NcSelector, NcSelectorBuilder, NcSelectorItem, NcSelectorOptions,
+};
 pub use tabbed::*;
+pub use table::{NcTable, NcTableColumn, NcTableModel};
 pub use tree::*;
+pub use widget::NcWidget;
@@ -0,0 +1,135 @@
+//! The lines of styled text of a pager.
+
+use core::ops::Range;
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use crate::{NcAnsi, NcChannels, NcMarkupSpan, NcStyle};
+
+/// A line of styled text.
+pub(crate) type Line = Vec<NcMarkupSpan>;
+
+/// Lines of text parsed from ANSI-colored input, which can be fed in chunks.
+#[derive(Clone, Debug)]
+pub(crate) struct Buffer {
+    lines: Vec<Line>,
+    /// Whether the last line continues with the next input.
+    open: bool,
+    ansi: NcAnsi,
+}
+
+impl Buffer {
+    /// New empty buffer, with the default `channels` of the text.
+    pub(crate) fn new(channels: NcChannels) -> Self {
+        Self { lines: Vec::new(), open: false, ansi: NcAnsi::new(NcStyle::None, channels) }
+    }
+
+    /// Appends the `text`, with its ANSI escape sequences.
+    pub(crate) fn push_str(&mut self, text: &str) {
+        for span in self.ansi.parse(text) {
+            for (n, piece) in span.text.split('\n').enumerate() {
+                if n > 0 {
+                    if !self.open {
+                        self.lines.push(Line::new());
+                    }
+                    self.open = false;
+                }
+                let piece = piece.replace('\r', "");
+                if piece.is_empty() {
+                    continue;
+                }
+                if !self.open {
+                    self.lines.push(Line::new());
+                    self.open = true;
+                }
+                let line = self.lines.last_mut().expect("an open line");
+                match line.last_mut() {
+                    Some(last) if last.styles == span.styles && last.channels == span.channels => {
+                        last.text.push_str(&piece)
+                    }
+                    _ => line.push(NcMarkupSpan {
+                        text: piece,
+                        styles: span.styles,
+                        channels: span.channels,
+                    }),
+                }
+            }
+        }
+    }
+
+    /// Discards all the lines, and the state of the ANSI parser.
+    pub(crate) fn clear(&mut self, channels: NcChannels) {
+        *self = Self::new(channels);
+    }
+
+    /// Returns the number of lines.
+    pub(crate) fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Returns the line at `index`.
+    pub(crate) fn line(&self, index: usize) -> &[NcMarkupSpan] {
+        self.lines.get(index).map_or(&[], |line| line.as_slice())
+    }
+
+    /// Returns the text of the line at `index`.
+    pub(crate) fn text(&self, index: usize) -> String {
+        self.line(index)
+            .iter()
+            .map(|span| span.text.as_str())
+            .collect()
+    }
+
+    /// Returns the non-overlapping occurrences of `pattern` in every line,
+    /// as the line index and the byte range in its text.
+    pub(crate) fn search(&self, pattern: &str) -> Vec<(usize, Range<usize>)> {
+        let mut matches = Vec::new();
+        if pattern.is_empty() {
+            return matches;
+        }
+        for index in 0..self.lines.len() {
+            let text = self.text(index);
+            matches.extend(
+                text.match_indices(pattern)
+                    .map(|(start, m)| (index, start..start + m.len())),
+            );
+        }
+        matches
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    fn texts(buffer: &Buffer) -> Vec<String> {
+        (0..buffer.len()).map(|i| buffer.text(i)).collect()
+    }
+
+    #[test]
+    fn lines() {
+        let mut buffer = Buffer::new(NcChannels::default());
+        buffer.push_str("one\r\ntw");
+        buffer.push_str("o\n\nthr\x1b[1");
+        assert_eq![texts(&buffer), ["one", "two", "", "thr"]];
+        buffer.push_str("mee\x1b[0m!\n");
+        assert_eq![texts(&buffer), ["one", "two", "", "three!"]];
+        assert_eq![buffer.line(3).len(), 3];
+        assert_eq![buffer.line(3)[1].styles, NcStyle::Bold];
+        assert_eq![buffer.line(9).len(), 0];
+
+        buffer.push_str("\n");
+        assert_eq![buffer.len(), 5];
+
+        assert_eq![buffer.search("o"), vec![(0, 0..1), (1, 2..3)]];
+        assert_eq![buffer.search("hree"), vec![(3, 1..5)]];
+        assert![buffer.search("").is_empty()];
+
+        buffer.clear(NcChannels::default());
+        assert_eq![buffer.len(), 0];
+    }
+}
@@ -0,0 +1,672 @@
+//! `NcPager` widget.
+
+use core::ops::Range;
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, vec::Vec};
+
+use crate::{
+    widgets::NcWidget, NcChannels, NcGraphemes, NcInput, NcInputType, NcKey, NcPlane, NcReceived,
+    NcResult, NcStyle, NcTextLayout,
+};
+
+mod buffer;
+use buffer::Buffer;
+
+/// The columns between tab stops.
+const TAB: u32 = 8;
+
+/// The rows scrolled by the mouse wheel.
+const WHEEL: usize = 3;
+
+/// A position in the text: a line, and a row inside of it when wrapping.
+type Position = (usize, u32);
+
+/// A viewer of long text, with vertical and horizontal scrolling.
+///
+/// The text can contain ANSI escape sequences, which are parsed with
+/// [`NcAnsi`], and can be appended in chunks, as it arrives.
+///
+/// It optionally shows the line numbers on the left, a status line at the
+/// bottom with the visible lines and the percentage of the text above its
+/// end, and wraps the lines that don't fit in the plane.
+///
+/// The occurrences of a [`search`]ed pattern are highlighted, and can be
+/// moved through with [`search_next`] and [`search_prev`].
+///
+/// # Input
+/// - `Up`, `Down`, `k` and `j` scroll by a row, and so does the mouse wheel
+///   by 3 rows.
+/// - `PgUp`, `PgDown`, `b` and `Space` scroll by a page.
+/// - `Home`, `End`, `g` and `G` scroll to the start and to the end.
+/// - `Left`, `Right`, `h` and `l` scroll horizontally, when not wrapping.
+/// - `n` and `N` move to the next and previous search match.
+///
+/// # Example
+/// ```no_run
+/// # use libnotcurses_sys::{*, widgets::*};
+/// # fn main() -> NcResult<()> {
+/// # let mut nc = unsafe { Nc::new()? };
+/// # let stdplane = unsafe { nc.stdplane() };
+/// let (rows, cols) = stdplane.dim_yx();
+/// let plane = NcPlane::new_child_sized(stdplane, 0, 0, rows, cols)?;
+/// let mut pager = NcPager::new(plane).line_numbers(true).wrap(true);
+/// pager.push_str("\x1b[1;31merror\x1b[0m: something failed\n");
+/// pager.push_str(include_str!["../../../README.md"]);
+/// pager.search("notcurses");
+///
+/// let mut input = NcInput::new_empty();
+/// pager.redraw()?;
+/// nc.render()?;
+/// while nc.get_blocking(Some(&mut input))? != NcReceived::Char('q') {
+///     if pager.offer_input(&input) {
+///         nc.render()?;
+///     }
+/// }
+/// pager.destroy()?;
+/// # unsafe { nc.stop()? };
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`NcAnsi`]: crate::NcAnsi
+/// [`search`]: NcPager#method.search
+/// [`search_next`]: NcPager#method.search_next
+/// [`search_prev`]: NcPager#method.search_prev
+pub struct NcPager<'a> {
+    plane: &'a mut NcPlane,
+    buffer: Buffer,
+    wrap: bool,
+    numbers: bool,
+    status: bool,
+    /// The first position shown.
+    top: Position,
+    /// The horizontal scroll, when not wrapping.
+    left: u32,
+    pattern: String,
+    matches: Vec<(usize, Range<usize>)>,
+    /// The index of the current match.
+    current: Option<usize>,
+    channels: NcChannels,
+    number_channels: NcChannels,
+    status_channels: NcChannels,
+    match_channels: NcChannels,
+    current_match_channels: NcChannels,
+}
+
+/// A grapheme of a line, laid out.
+struct Cell<'t> {
+    egc: &'t str,
+    width: u32,
+    styles: NcStyle,
+    channels: NcChannels,
+    /// The byte offset in the text of the line.
+    byte: usize,
+    /// The row, when wrapping.
+    row: u32,
+    /// The column, relative to the row.
+    col: u32,
+}
+
+/// # `NcPager` constructors
+impl<'a> NcPager<'a> {
+    /// New empty `NcPager` over a `plane`, without line numbers nor wrapping,
+    /// and with a status line.
+    pub fn new(plane: &'a mut NcPlane) -> Self {
+        let mut status_channels = NcChannels::default();
+        status_channels.reverse();
+        Self {
+            plane,
+            buffer: Buffer::new(NcChannels::default()),
+            wrap: false,
+            numbers: false,
+            status: true,
+            top: (0, 0),
+            left: 0,
+            pattern: String::new(),
+            matches: Vec::new(),
+            current: None,
+            channels: NcChannels::default(),
+            number_channels: NcChannels::from_rgb(0x808080, 0).set_bg_default(),
+            status_channels,
+            match_channels: NcChannels::from_rgb(0, 0xFFCC00),
+            current_match_channels: NcChannels::from_rgb(0, 0xFF8800),
+        }
+    }
+
+    /// Sets whether to wrap the lines that don't fit.
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self.left = 0;
+        self
+    }
+
+    /// Sets whether to show the line numbers.
+    pub fn line_numbers(mut self, numbers: bool) -> Self {
+        self.numbers = numbers;
+        self
+    }
+
+    /// Sets whether to show the status line.
+    pub fn status_line(mut self, status: bool) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Sets the channels of the text without colors.
+    ///
+    /// It only takes effect right away if there's no text yet. Otherwise the
+    /// text appended keeps the previous channels until it's all replaced with
+    /// [`set_text`] or [`clear`].
+    ///
+    /// [`set_text`]: NcPager#method.set_text
+    /// [`clear`]: NcPager#method.clear
+    pub fn channels(mut self, channels: impl Into<NcChannels>) -> Self {
+        self.channels = channels.into();
+        if self.buffer.len() == 0 {
+            self.buffer.clear(self.channels);
+        }
+        self
+    }
+
+    /// Sets the channels of the line numbers.
+    pub fn number_channels(mut self, channels: impl Into<NcChannels>) -> Self {
+        self.number_channels = channels.into();
+        self
+    }
+
+    /// Sets the channels of the status line.
+    pub fn status_channels(mut self, channels: impl Into<NcChannels>) -> Self {
+        self.status_channels = channels.into();
+        self
+    }
+
+    /// Sets the channels of the search matches, and of the current one.
+    pub fn match_channels(
+        mut self,
+        matches: impl Into<NcChannels>,
+        current: impl Into<NcChannels>,
+    ) -> Self {
+        self.match_channels = matches.into();
+        self.current_match_channels = current.into();
+        self
+    }
+}
+
+/// # `NcPager` methods
+impl<'a> NcPager<'a> {
+    /// Replaces the text, scrolling back to the start.
+    pub fn set_text(&mut self, text: &str) {
+        self.buffer.clear(self.channels);
+        self.top = (0, 0);
+        self.left = 0;
+        self.push_str(text);
+    }
+
+    /// Appends the `text`, which can end in the middle of a line,
+    /// or of an escape sequence.
+    pub fn push_str(&mut self, text: &str) {
+        self.buffer.push_str(text);
+        if !self.pattern.is_empty() {
+            let current = self.current.map(|c| self.matches[c].clone());
+            self.matches = self.buffer.search(&self.pattern);
+            self.current = current.and_then(|c| self.matches.iter().position(|m| *m == c));
+        }
+    }
+
+    /// Discards the text.
+    pub fn clear(&mut self) {
+        self.set_text("");
+    }
+
+    /// Returns the number of lines.
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns true if there are no lines.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.len() == 0
+    }
+
+    /// Returns the text of the line at `index`, without escape sequences.
+    pub fn line_text(&self, index: usize) -> Option<String> {
+        (index < self.buffer.len()).then(|| self.buffer.text(index))
+    }
+
+    /// Returns the index of the first line shown.
+    pub fn top_line(&self) -> usize {
+        self.top.0
+    }
+
+    /// Scrolls to show the line at `index` at the top, as far as possible.
+    pub fn scroll_to(&mut self, index: usize) {
+        self.top = (index.min(self.len().saturating_sub(1)), 0);
+        self.clamp_top();
+    }
+
+    /// Scrolls down `n` rows.
+    pub fn scroll_down(&mut self, n: usize) {
+        for _ in 0..n {
+            match self.next_row(self.top) {
+                Some(position) => self.top = position,
+                None => break,
+            }
+        }
+        self.clamp_top();
+    }
+
+    /// Scrolls up `n` rows.
+    pub fn scroll_up(&mut self, n: usize) {
+        for _ in 0..n {
+            match self.prev_row(self.top) {
+                Some(position) => self.top = position,
+                None => break,
+            }
+        }
+    }
+
+    /// Scrolls to the end of the text.
+    pub fn scroll_end(&mut self) {
+        self.top = self.max_top();
+    }
+
+    /// Returns the horizontal scroll, in columns.
+    pub fn scroll_x(&self) -> u32 {
+        self.left
+    }
+
+    /// Sets the horizontal scroll, limited to the width of the visible lines.
+    ///
+    /// Does nothing when wrapping.
+    pub fn set_scroll_x(&mut self, x: u32) {
+        if self.wrap {
+            return;
+        }
+        let cols = self.text_size().1;
+        let width = self
+            .visible()
+            .iter()
+            .map(|&(line, _)| self.cells(line).last().map_or(0, |c| c.col + c.width))
+            .max()
+            .unwrap_or(0);
+        self.left = x.min(width.saturating_sub(cols));
+    }
+
+    /// Searches the occurrences of `pattern`, moving to the first one from
+    /// the top line, and returns how many there are.
+    ///
+    /// An empty `pattern` clears the search.
+    pub fn search(&mut self, pattern: &str) -> usize {
+        self.pattern = pattern.into();
+        self.matches = self.buffer.search(pattern);
+        self.current = None;
+        if let Some(index) = self
+            .matches
+            .iter()
+            .position(|(line, _)| *line >= self.top.0)
+        {
+            self.reveal(index);
+        } else if !self.matches.is_empty() {
+            self.reveal(0);
+        }
+        self.matches.len()
+    }
+
+    /// Moves to the next search match, wrapping around.
+    ///
+    /// Returns false if there are no matches.
+    pub fn search_next(&mut self) -> bool {
+        if self.matches.is_empty() {
+            return false;
+        }
+        let index = self.current.map_or(0, |c| (c + 1) % self.matches.len());
+        self.reveal(index);
+        true
+    }
+
+    /// Moves to the previous search match, wrapping around.
+    ///
+    /// Returns false if there are no matches.
+    pub fn search_prev(&mut self) -> bool {
+        let len = self.matches.len();
+        if len == 0 {
+            return false;
+        }
+        let index = self.current.map_or(len - 1, |c| (c + len - 1) % len);
+        self.reveal(index);
+        true
+    }
+
+    /// Returns the number of search matches, and the index of the current one.
+    pub fn search_matches(&self) -> (usize, Option<usize>) {
+        (self.matches.len(), self.current)
+    }
+
+    /// Offers the `input` to the pager, redrawing it if it's consumed.
+    ///
+    /// Returns true if the input was relevant.
+    pub fn offer_input(&mut self, input: &NcInput) -> bool {
+        if input.evtype == NcInputType::Release as u32 {
+            return false;
+        }
+        let page = (self.text_size().0 as usize).max(1);
+        let half = (self.text_size().1 / 2).max(1);
+        let plain = !input.ctrl_p() && !input.alt_p();
+        match NcReceived::from(input) {
+            NcReceived::Key(NcKey::ScrollUp) | NcReceived::Key(NcKey::ScrollDown) => {
+                let (mut y, mut x) = (input.y, input.x);
+                if !self.plane.translate_abs(&mut y, &mut x) {
+                    return false;
+                }
+                if input.id == NcKey::ScrollUp.0 {
+                    self.scroll_up(WHEEL);
+                } else {
+                    self.scroll_down(WHEEL);
+                }
+            }
+            NcReceived::Key(NcKey::Up) => self.scroll_up(1),
+            NcReceived::Key(NcKey::Down) => self.scroll_down(1),
+            NcReceived::Key(NcKey::PgUp) => self.scroll_up(page),
+            NcReceived::Key(NcKey::PgDown) => self.scroll_down(page),
+            NcReceived::Key(NcKey::Home) => self.top = (0, 0),
+            NcReceived::Key(NcKey::End) => self.scroll_end(),
+            NcReceived::Key(NcKey::Left) => self.set_scroll_x(self.left.saturating_sub(half)),
+            NcReceived::Key(NcKey::Right) => self.set_scroll_x(self.left + half),
+            NcReceived::Char(c) if plain => match c {
+                'k' => self.scroll_up(1),
+                'j' => self.scroll_down(1),
+                'b' => self.scroll_up(page),
+                ' ' => self.scroll_down(page),
+                'g' => self.top = (0, 0),
+                'G' => self.scroll_end(),
+                'h' => self.set_scroll_x(self.left.saturating_sub(half)),
+                'l' => self.set_scroll_x(self.left + half),
+                'n' => return self.search_next() && self.redraw().is_ok(),
+                'N' => return self.search_prev() && self.redraw().is_ok(),
+                _ => return false,
+            },
+            _ => return false,
+        }
+        let _ = self.redraw();
+        true
+    }
+
+    /// Redraws the pager in its entirety.
+    pub fn redraw(&mut self) -> NcResult<()> {
+        let (styles, channels) = (self.plane.styles(), self.plane.channels());
+        let res = self.draw();
+        self.plane.set_styles(styles);
+        self.plane.set_channels(channels);
+        res
+    }
+
+    /// Destroys the pager, together with its plane.
+    pub fn destroy(&mut self) -> NcResult<()> {
+        self.plane.destroy()
+    }
+}
+
+/// # `NcPager` private methods
+impl<'a> NcPager<'a> {
+    /// Returns the width of the line numbers, including their margin.
+    fn gutter(&self) -> u32 {
+        if self.numbers {
+            digits(self.len().max(1)) + 1
+        } else {
+            0
+        }
+    }
+
+    /// Returns the rows and columns available for the text.
+    fn text_size(&self) -> (u32, u32) {
+        let (dim_y, dim_x) = self.plane.dim_yx();
+        (
+            dim_y.saturating_sub(u32::from(self.status)),
+            dim_x.saturating_sub(self.gutter()),
+        )
+    }
+
+    /// Returns the laid out graphemes of the line at `index`.
+    fn cells(&self, index: usize) -> Vec<Cell<'_>> {
+        layout(&self.buffer, index, self.wrap, self.text_size().1.max(1))
+    }
+
+    /// Returns the number of rows of the line at `index`.
+    fn rows(&self, index: usize) -> u32 {
+        if self.wrap {
+            self.cells(index).last().map_or(1, |c| c.row + 1)
+        } else {
+            1
+        }
+    }
+
+    fn next_row(&self, (line, row): Position) -> Option<Position> {
+        if row + 1 < self.rows(line) {
+            Some((line, row + 1))
+        } else if line + 1 < self.len() {
+            Some((line + 1, 0))
+        } else {
+            None
+        }
+    }
+
+    fn prev_row(&self, (line, row): Position) -> Option<Position> {
+        if row > 0 {
+            Some((line, row - 1))
+        } else if line > 0 {
+            Some((line - 1, self.rows(line - 1) - 1))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the top position that shows the end of the text at the bottom.
+    fn max_top(&self) -> Position {
+        let Some(last) = self.len().checked_sub(1) else {
+            return (0, 0);
+        };
+        let mut top = (last, self.rows(last) - 1);
+        for _ in 1..self.text_size().0 {
+            match self.prev_row(top) {
+                Some(position) => top = position,
+                None => break,
+            }
+        }
+        top
+    }
+
+    fn clamp_top(&mut self) {
+        self.top = self.top.min(self.max_top());
+    }
+
+    /// Returns the positions shown, from the top.
+    fn visible(&self) -> Vec<Position> {
+        let mut rows = Vec::new();
+        let mut position = (!self.is_empty()).then_some(self.top);
+        while let Some(p) = position {
+            if rows.len() as u32 >= self.text_size().0 {
+                break;
+            }
+            rows.push(p);
+            position = self.next_row(p);
+        }
+        rows
+    }
+
+    /// Makes the match at `index` the current one, scrolling to show it.
+    fn reveal(&mut self, index: usize) {
+        self.current = Some(index);
+        let (line, start) = (self.matches[index].0, self.matches[index].1.start);
+        if !self.visible().iter().any(|&(l, _)| l == line) {
+            self.scroll_to(line);
+        }
+        if !self.wrap {
+            let cols = self.text_size().1;
+            let col = self
+                .cells(line)
+                .iter()
+                .find(|c| c.byte >= start)
+                .map_or(0, |c| c.col);
+            if col < self.left || col >= self.left + cols {
+                self.set_scroll_x(col.saturating_sub(cols / 4));
+            }
+        }
+    }
+
+    /// Returns the channels of the byte at `byte` of the line at `index`,
+    /// if it's part of a search match.
+    fn channels_at(&self, index: usize, byte: usize) -> Option<NcChannels> {
+        let first = self.matches.partition_point(|(line, _)| *line < index);
+        self.matches[first..]
+            .iter()
+            .enumerate()
+            .take_while(|(_, (line, _))| *line == index)
+            .find(|(_, (_, range))| range.contains(&byte))
+            .map(|(i, _)| {
+                if self.current == Some(first + i) {
+                    self.current_match_channels
+                } else {
+                    self.match_channels
+                }
+            })
+    }
+
+    fn draw(&mut self) -> NcResult<()> {
+        let (dim_y, dim_x) = self.plane.dim_yx();
+        let (gutter, cols) = (self.gutter(), self.text_size().1);
+        self.plane.erase();
+
+        let visible = self.visible();
+        for (y, &(line, row)) in visible.iter().enumerate() {
+            let y = y as u32;
+            if self.numbers && row == 0 {
+                let number = format!["{:>1$} ", line + 1, gutter as usize - 1];
+                self.plane.set_styles(NcStyle::None);
+                self.plane.set_channels(self.number_channels);
+                self.plane.putstr_yx(Some(y), Some(0), &number)?;
+            }
+            let cells = layout(&self.buffer, line, self.wrap, cols.max(1));
+            for cell in cells.iter().filter(|c| c.row == row) {
+                let x = if self.wrap {
+                    cell.col
+                } else if cell.col >= self.left && cell.col + cell.width <= self.left + cols {
+                    cell.col - self.left
+                } else {
+                    continue;
+                };
+                let channels = self.channels_at(line, cell.byte).unwrap_or(cell.channels);
+                self.plane.set_styles(cell.styles);
+                self.plane.set_channels(channels);
+                self.plane.putstr_yx(Some(y), Some(gutter + x), cell.egc)?;
+            }
+        }
+
+        if self.status && dim_y > 0 {
+            let y = dim_y - 1;
+            self.plane.set_styles(NcStyle::None);
+            self.plane.set_channels(self.status_channels);
+            for x in 0..dim_x {
+                self.plane.putstr_yx(Some(y), Some(x), " ")?;
+            }
+            let (first, last) = match (visible.first(), visible.last()) {
+                (Some(first), Some(last)) => (first.0 + 1, last.0 + 1),
+                _ => (0, 0),
+            };
+            let percent = if self.is_empty() { 100 } else { last * 100 / self.len() };
+            let position = format![" {}-{}/{} {}%", first, last, self.len(), percent];
+            let search = match (self.pattern.is_empty(), self.current) {
+                (true, _) => String::new(),
+                (false, Some(c)) => format!["{}/{} ", c + 1, self.matches.len()],
+                (false, None) => "no matches ".into(),
+            };
+            put_clipped(self.plane, y, 0, &position, dim_x)?;
+            let search_x = dim_x.saturating_sub(cols_of(&search));
+            if search_x > cols_of(&position) {
+                put_clipped(self.plane, y, search_x, &search, dim_x)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Lays out the graphemes of the line at `index` of the `buffer` in `cols`
+/// columns, expanding the tabs to spaces.
+fn layout(buffer: &Buffer, index: usize, wrap: bool, cols: u32) -> Vec<Cell<'_>> {
+    let mut cells = Vec::new();
+    let (mut byte, mut x, mut row) = (0, 0, 0);
+    for span in buffer.line(index) {
+        for egc in NcGraphemes::new(&span.text) {
+            let (glyph, width, count) = if egc == "\t" {
+                (" ", 1, TAB - (x % TAB))
+            } else {
+                (egc, NcTextLayout::strwidth(egc), 1)
+            };
+            for _ in 0..count {
+                if width == 0 {
+                    break;
+                }
+                if wrap && x + width > cols && x > 0 {
+                    row += 1;
+                    x = 0;
+                }
+                cells.push(Cell {
+                    egc: glyph,
+                    width,
+                    styles: span.styles,
+                    channels: span.channels,
+                    byte,
+                    row,
+                    col: x,
+                });
+                x += width;
+            }
+            byte += egc.len();
+        }
+    }
+    cells
+}
+
+/// Writes the graphemes of `string` at `y`, `x` that fit before `max_x`.
+fn put_clipped(plane: &mut NcPlane, y: u32, mut x: u32, string: &str, max_x: u32) -> NcResult<()> {
+    for egc in NcGraphemes::new(string) {
+        let width = NcTextLayout::strwidth(egc);
+        if x + width > max_x {
+            break;
+        }
+        plane.putstr_yx(Some(y), Some(x), egc)?;
+        x += width;
+    }
+    Ok(())
+}
+
+/// Returns the number of columns of `string`.
+fn cols_of(string: &str) -> u32 {
+    NcGraphemes::new(string).map(NcTextLayout::strwidth).sum()
+}
+
+/// Returns the number of decimal digits of `n`.
+fn digits(mut n: usize) -> u32 {
+    let mut digits = 1;
+    while n >= 10 {
+        n /= 10;
+        digits += 1;
+    }
+    digits
+}
+
+/// *(No equivalent C style functions)*
+impl<'a> NcWidget for NcPager<'a> {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        Ok(self.plane)
+    }
+    fn offer_input(&mut self, input: &NcInput) -> bool {
+        NcPager::offer_input(self, input)
+    }
+    fn redraw(&mut self) -> NcResult<()> {
+        NcPager::redraw(self)
+    }
+    fn destroy(&mut self) -> NcResult<()> {
+        NcPager::destroy(self)
+    }
+}
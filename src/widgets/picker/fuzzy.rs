@@ -0,0 +1,143 @@
+//! Fuzzy matching of a query against a text, by grapheme clusters.
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use crate::NcGraphemes;
+
+/// The score of each matched grapheme.
+const MATCH: i32 = 16;
+/// The bonus of a match at the start of a word.
+const BOUNDARY: i32 = 8;
+/// The bonus of a match right after the previous one.
+const CONSECUTIVE: i32 = 8;
+/// The penalty of starting a gap between two matches.
+const GAP_START: i32 = 3;
+/// The penalty of each extra grapheme in a gap between two matches.
+const GAP: i32 = 1;
+
+const NONE: i32 = i32::MIN / 2;
+
+/// Returns the score of the best match of the `query` graphemes in `text`,
+/// and the indices of the matched graphemes of `text`, or `None` if the
+/// `query` is not a subsequence of the `text`.
+///
+/// The comparison ignores the case, unless the `query` has uppercase letters.
+pub(crate) fn fuzzy_match(query: &[&str], text: &str) -> Option<(i32, Vec<usize>)> {
+    let text: Vec<&str> = NcGraphemes::new(text).collect();
+    let (n, m) = (query.len(), text.len());
+    if n == 0 {
+        return Some((0, Vec::new()));
+    }
+    if n > m {
+        return None;
+    }
+    let ignore_case = !query.iter().any(|q| q.chars().any(char::is_uppercase));
+    let eq = |q: &str, t: &str| {
+        if ignore_case {
+            q.chars()
+                .flat_map(char::to_lowercase)
+                .eq(t.chars().flat_map(char::to_lowercase))
+        } else {
+            q == t
+        }
+    };
+
+    // quick rejection of the texts that don't contain the query
+    let mut rest = text.iter();
+    if !query.iter().all(|q| rest.any(|t| eq(q, t))) {
+        return None;
+    }
+
+    // score[i * m + j] is the best score matching query[..=i] with query[i]
+    // at text[j], and from[i * m + j] the position of query[i - 1].
+    let mut score = vec![NONE; n * m];
+    let mut from = vec![0; n * m];
+    for (j, t) in text.iter().enumerate() {
+        if eq(query[0], t) {
+            // the first match counts its bonus twice
+            score[j] = MATCH + 2 * bonus(&text, j);
+        }
+    }
+    for (i, q) in query.iter().enumerate().skip(1) {
+        let (prev, row) = ((i - 1) * m, i * m);
+        // the best previous match followed by a gap, for the current j
+        let mut run = (NONE, 0);
+        for j in i..m {
+            if j >= 2 && score[prev + j - 2] - GAP_START > run.0 - GAP {
+                run = (score[prev + j - 2] - GAP_START, j - 2);
+            } else {
+                run.0 -= GAP;
+            }
+            if !eq(q, text[j]) {
+                continue;
+            }
+            let consecutive = score[prev + j - 1] + CONSECUTIVE;
+            let (best, k) = if consecutive >= run.0 { (consecutive, j - 1) } else { run };
+            if best > NONE / 2 {
+                score[row + j] = best + MATCH + bonus(&text, j);
+                from[row + j] = k;
+            }
+        }
+    }
+
+    let last = (n - 1) * m;
+    let (mut j, best) = (0..m)
+        .map(|j| (j, score[last + j]))
+        .max_by_key(|&(j, s)| (s, core::cmp::Reverse(j)))?;
+    if best <= NONE / 2 {
+        return None;
+    }
+    let mut positions = vec![0; n];
+    for i in (0..n).rev() {
+        positions[i] = j;
+        j = from[i * m + j];
+    }
+    Some((best, positions))
+}
+
+/// Returns the bonus of a match at `text[j]`.
+fn bonus(text: &[&str], j: usize) -> i32 {
+    let Some(prev) = j.checked_sub(1).map(|p| text[p]) else {
+        return BOUNDARY;
+    };
+    let separator = prev
+        .chars()
+        .all(|c| c.is_whitespace() || "_-/\\.:,;".contains(c));
+    let camel = prev.chars().all(char::is_lowercase) && text[j].chars().all(char::is_uppercase);
+    if separator || camel {
+        BOUNDARY
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn matches(query: &str, text: &str) -> Option<(i32, Vec<usize>)> {
+        let query: Vec<&str> = NcGraphemes::new(query).collect();
+        fuzzy_match(&query, text)
+    }
+
+    #[test]
+    fn fuzzy() {
+        assert_eq![matches("fb", "foo_bar").unwrap().1, [0, 4]];
+        assert_eq![matches("ab", "a_ab").unwrap().1, [2, 3]];
+        assert_eq![matches("ñu", "el Ñu").unwrap().1, [3, 4]];
+        assert_eq![matches("", "text"), Some((0, Vec::new()))];
+        assert_eq![matches("abc", "ab"), None];
+        assert_eq![matches("ba", "abc"), None];
+
+        // smart case
+        assert![matches("b", "aBc").is_some()];
+        assert![matches("B", "abc").is_none()];
+
+        // consecutive matches and word starts rank higher
+        let sel = |text| matches("sel", text).unwrap().0;
+        assert![sel("selector") > sel("sxexl")];
+        assert![sel("NcSelector") > sel("ansel")];
+        assert![sel("multi_select") > sel("muselect")];
+    }
+}
@@ -0,0 +1,512 @@
+//! `NcPicker` widget.
+//!
+//! ```txt
+//! > sel▁                       3/120
+//!   NcSelector
+//!   multi_select
+//!   ask_else_l
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{
+    widgets::NcWidget, Nc, NcChannels, NcGraphemes, NcInput, NcInputType, NcKey, NcPlane,
+    NcReceived, NcResult, NcStyle, NcTextLayout,
+};
+
+mod fuzzy;
+use fuzzy::fuzzy_match;
+
+/// Returns the candidate items for a query.
+type Source<'a> = Box<dyn FnMut(&str) -> Vec<String> + 'a>;
+
+/// A fuzzy finder: a query line above the list of items that match it,
+/// ranked by how well they match, and filtered as you type.
+///
+/// The matched characters of each item are highlighted.
+///
+/// The items can be provided upfront, or by a callback that is called with
+/// the query every time it changes, whose results are then matched.
+///
+/// # Input
+/// - printable characters, `Backspace`, `Del`, `Left`, `Right`, `Home`,
+///   `End` and `Ctrl+U` edit the query.
+/// - `Up`, `Down`, `Ctrl+P`, `Ctrl+N`, `PgUp`, `PgDown` and the mouse
+///   wheel move the selection.
+/// - `Enter` chooses the selected item, and `Esc` cancels.
+/// - clicking an item selects it, and clicking it again chooses it.
+///
+/// # Example
+/// ```no_run
+/// # use libnotcurses_sys::{*, widgets::*};
+/// # fn main() -> NcResult<()> {
+/// # let mut nc = unsafe { Nc::new()? };
+/// # let stdplane = unsafe { nc.stdplane() };
+/// let commands = ["open file", "save file", "close window", "quit"];
+/// let plane = NcPlane::new_child_sized(stdplane, 2, 10, 8, 40)?;
+/// let mut picker = NcPicker::new(plane, commands).prompt(": ");
+/// if let Some(command) = picker.pick(&mut nc)? {
+///     // …
+/// }
+/// picker.destroy()?;
+/// # unsafe { nc.stop()? };
+/// # Ok(())
+/// # }
+/// ```
+pub struct NcPicker<'a> {
+    plane: &'a mut NcPlane,
+    /// The callback providing the candidates, if they're not fixed.
+    source: Option<Source<'a>>,
+    candidates: Vec<String>,
+    /// The matching candidates, ranked.
+    matches: Vec<Match>,
+    query: String,
+    /// The byte offset of the cursor in the query.
+    cursor: usize,
+    prompt: String,
+    selected: usize,
+    /// The index of the first match shown.
+    top: usize,
+    channels: NcChannels,
+    selected_channels: NcChannels,
+    highlight: NcChannels,
+    chosen: Option<String>,
+    done: bool,
+}
+
+/// A candidate matching the query.
+#[derive(Clone, Debug)]
+struct Match {
+    index: usize,
+    score: i32,
+    /// The indices of the matched graphemes.
+    positions: Vec<usize>,
+}
+
+/// # `NcPicker` constructors
+impl<'a> NcPicker<'a> {
+    /// New `NcPicker` over a `plane`, with a fixed list of `items`.
+    ///
+    /// The picker uses the whole plane, the first row for the query.
+    pub fn new<I>(plane: &'a mut NcPlane, items: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let mut picker = Self::with_items(plane, None);
+        picker.candidates = items.into_iter().map(Into::into).collect();
+        picker.refilter();
+        picker
+    }
+
+    /// New `NcPicker` over a `plane`, whose candidate items are returned by
+    /// `source` for each query.
+    ///
+    /// The candidates are matched against the query afterwards, so the
+    /// source can return more items than the ones matching.
+    pub fn with_source(
+        plane: &'a mut NcPlane,
+        source: impl FnMut(&str) -> Vec<String> + 'a,
+    ) -> Self {
+        let mut picker = Self::with_items(plane, Some(Box::new(source)));
+        picker.refilter();
+        picker
+    }
+
+    fn with_items(plane: &'a mut NcPlane, source: Option<Source<'a>>) -> Self {
+        let mut selected_channels = NcChannels::default();
+        selected_channels.reverse();
+        Self {
+            plane,
+            source,
+            candidates: Vec::new(),
+            matches: Vec::new(),
+            query: String::new(),
+            cursor: 0,
+            prompt: "> ".into(),
+            selected: 0,
+            top: 0,
+            channels: NcChannels::default(),
+            selected_channels,
+            highlight: NcChannels::from_rgb(0xFFCC00, 0),
+            chosen: None,
+            done: false,
+        }
+    }
+
+    /// Sets the prompt written before the query.
+    pub fn prompt(mut self, prompt: &str) -> Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Sets the channels of the query and the items.
+    pub fn channels(mut self, channels: impl Into<NcChannels>) -> Self {
+        self.channels = channels.into();
+        self
+    }
+
+    /// Sets the channels of the selected item.
+    pub fn selected_channels(mut self, channels: impl Into<NcChannels>) -> Self {
+        self.selected_channels = channels.into();
+        self
+    }
+
+    /// Sets the foreground of the matched characters, using the foreground
+    /// channel of `channels`.
+    pub fn highlight(mut self, channels: impl Into<NcChannels>) -> Self {
+        self.highlight = channels.into();
+        self
+    }
+}
+
+/// # `NcPicker` methods
+impl<'a> NcPicker<'a> {
+    /// Returns the query.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Replaces the query, moving the cursor to its end.
+    pub fn set_query(&mut self, query: &str) {
+        self.query = query.into();
+        self.cursor = self.query.len();
+        self.refilter();
+    }
+
+    /// Returns the matching items, from the best match to the worst.
+    pub fn matches(&self) -> impl Iterator<Item = &str> {
+        self.matches
+            .iter()
+            .map(|m| self.candidates[m.index].as_str())
+    }
+
+    /// Returns the selected item, if any item matches.
+    pub fn selected(&self) -> Option<&str> {
+        self.matches
+            .get(self.selected)
+            .map(|m| self.candidates[m.index].as_str())
+    }
+
+    /// Selects the match at `index`, limited to the number of matches.
+    pub fn select(&mut self, index: usize) {
+        self.selected = index.min(self.matches.len().saturating_sub(1));
+    }
+
+    /// Returns the chosen item, once it has been chosen.
+    pub fn chosen(&self) -> Option<&str> {
+        self.chosen.as_deref()
+    }
+
+    /// Returns true if an item has been chosen, or the picker was cancelled.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Clears the query, the choice, and the done state.
+    pub fn reset(&mut self) {
+        self.chosen = None;
+        self.done = false;
+        self.set_query("");
+    }
+
+    /// Offers the `input` to the picker, redrawing it if it's consumed.
+    ///
+    /// Returns true if the input was relevant.
+    pub fn offer_input(&mut self, input: &NcInput) -> bool {
+        let consumed = if NcKey(input.id).is_mouse() {
+            self.offer_mouse(input)
+        } else if input.evtype == NcInputType::Release as u32 {
+            false
+        } else {
+            self.offer_key(input)
+        };
+        if consumed {
+            let _ = self.redraw();
+        }
+        consumed
+    }
+
+    /// Redraws the picker in its entirety.
+    pub fn redraw(&mut self) -> NcResult<()> {
+        let (styles, channels) = (self.plane.styles(), self.plane.channels());
+        let res = self.draw();
+        self.plane.set_styles(styles);
+        self.plane.set_channels(channels);
+        res
+    }
+
+    /// Runs the picker until an item is chosen, which is returned,
+    /// or it's cancelled.
+    pub fn pick(&mut self, nc: &mut Nc) -> NcResult<Option<String>> {
+        self.redraw()?;
+        nc.render()?;
+        let mut input = NcInput::new_empty();
+        while !self.done {
+            nc.get_blocking(Some(&mut input))?;
+            if self.offer_input(&input) {
+                nc.render()?;
+            }
+        }
+        Ok(self.chosen.clone())
+    }
+
+    /// Destroys the picker, together with its plane.
+    pub fn destroy(&mut self) -> NcResult<()> {
+        self.plane.destroy()
+    }
+}
+
+/// # `NcPicker` private methods
+impl<'a> NcPicker<'a> {
+    /// Updates the matches after the query has changed.
+    fn refilter(&mut self) {
+        if let Some(source) = self.source.as_mut() {
+            self.candidates = source(&self.query);
+        }
+        let query: Vec<&str> = NcGraphemes::new(&self.query).collect();
+        self.matches = self
+            .candidates
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                fuzzy_match(&query, item).map(|(score, positions)| Match {
+                    index,
+                    score,
+                    positions,
+                })
+            })
+            .collect();
+        if !query.is_empty() {
+            let candidates = &self.candidates;
+            // the sort is stable, keeping the original order of the ties
+            self.matches
+                .sort_by_key(|m| (core::cmp::Reverse(m.score), cols(&candidates[m.index])));
+        }
+        self.selected = 0;
+        self.top = 0;
+    }
+
+    fn offer_key(&mut self, input: &NcInput) -> bool {
+        let ctrl = input.ctrl_p();
+        let page = (self.plane.dim_y() as usize).saturating_sub(1).max(1);
+        match NcReceived::from(input) {
+            NcReceived::Key(NcKey::Enter) => {
+                if let Some(item) = self.selected() {
+                    self.chosen = Some(item.to_string());
+                    self.done = true;
+                }
+            }
+            NcReceived::Key(NcKey::Esc) => {
+                self.chosen = None;
+                self.done = true;
+            }
+            NcReceived::Key(NcKey::Up) => self.select(self.selected.saturating_sub(1)),
+            NcReceived::Key(NcKey::Down) => self.select(self.selected + 1),
+            NcReceived::Key(NcKey::PgUp) => self.select(self.selected.saturating_sub(page)),
+            NcReceived::Key(NcKey::PgDown) => self.select(self.selected + page),
+            NcReceived::Char('p') if ctrl => self.select(self.selected.saturating_sub(1)),
+            NcReceived::Char('n') if ctrl => self.select(self.selected + 1),
+            NcReceived::Char('u') if ctrl => self.set_query(""),
+            NcReceived::Key(NcKey::Left) => self.cursor = self.prev_boundary(),
+            NcReceived::Key(NcKey::Right) => self.cursor = self.next_boundary(),
+            NcReceived::Key(NcKey::Home) => self.cursor = 0,
+            NcReceived::Key(NcKey::End) => self.cursor = self.query.len(),
+            NcReceived::Key(NcKey::Backspace) => {
+                let start = self.prev_boundary();
+                self.query.replace_range(start..self.cursor, "");
+                self.cursor = start;
+                self.refilter();
+            }
+            NcReceived::Key(NcKey::Del) => {
+                let end = self.next_boundary();
+                self.query.replace_range(self.cursor..end, "");
+                self.refilter();
+            }
+            NcReceived::Char(c) if !ctrl && !input.alt_p() && !c.is_control() => {
+                self.query.insert(self.cursor, c);
+                self.cursor += c.len_utf8();
+                self.refilter();
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn offer_mouse(&mut self, input: &NcInput) -> bool {
+        let (mut y, mut x) = (input.y, input.x);
+        if !self.plane.translate_abs(&mut y, &mut x) {
+            return false;
+        }
+        let release = input.evtype == NcInputType::Release as u32;
+        match NcKey(input.id) {
+            NcKey::Button4 if !release => self.select(self.selected.saturating_sub(1)),
+            NcKey::Button5 if !release => self.select(self.selected + 1),
+            NcKey::Button1 if y > 0 => {
+                let index = self.top + y as usize - 1;
+                if index >= self.matches.len() {
+                    return false;
+                }
+                if release {
+                    return true;
+                }
+                if index == self.selected {
+                    self.chosen = self.selected().map(String::from);
+                    self.done = true;
+                } else {
+                    self.select(index);
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Returns the byte offset of the grapheme before the cursor.
+    fn prev_boundary(&self) -> usize {
+        NcGraphemes::new(&self.query[..self.cursor])
+            .last()
+            .map_or(0, |egc| self.cursor - egc.len())
+    }
+
+    /// Returns the byte offset of the grapheme after the cursor.
+    fn next_boundary(&self) -> usize {
+        NcGraphemes::new(&self.query[self.cursor..])
+            .next()
+            .map_or(self.cursor, |egc| self.cursor + egc.len())
+    }
+
+    fn draw(&mut self) -> NcResult<()> {
+        let (dim_y, dim_x) = self.plane.dim_yx();
+        let rows = (dim_y as usize).saturating_sub(1);
+        if self.selected < self.top {
+            self.top = self.selected;
+        } else if rows > 0 && self.selected >= self.top + rows {
+            self.top = self.selected + 1 - rows;
+        }
+        self.plane.erase();
+        self.plane.set_styles(NcStyle::None);
+
+        // the query line, with the count of matches on the right
+        self.fill_row(0, self.channels)?;
+        let count = format!["{}/{}", self.matches.len(), self.candidates.len()];
+        let count_x = dim_x.saturating_sub(cols(&count));
+        let mut x = self.put(0, 0, &self.prompt.clone(), dim_x)?;
+        let mut cursor_drawn = false;
+        for (offset, egc) in grapheme_offsets(&self.query.clone()) {
+            if offset == self.cursor {
+                x = self.put_cursor(x, egc, dim_x)?;
+                cursor_drawn = true;
+            } else {
+                x = self.put(0, x, egc, dim_x)?;
+            }
+        }
+        if !cursor_drawn {
+            x = self.put_cursor(x, " ", dim_x)?;
+        }
+        if x < count_x {
+            self.put(0, count_x, &count, dim_x)?;
+        }
+
+        // the list of matches
+        for row in 0..rows.min(self.matches.len() - self.top.min(self.matches.len())) {
+            let index = self.top + row;
+            let y = row as u32 + 1;
+            let channels =
+                if index == self.selected { self.selected_channels } else { self.channels };
+            self.fill_row(y, channels)?;
+            let Match { index: item, ref positions, .. } = self.matches[index];
+            let (item, positions) = (self.candidates[item].clone(), positions.clone());
+            let mut highlight = channels;
+            highlight.set_fchannel(self.highlight.fchannel());
+            let mut x = 1;
+            for (i, egc) in NcGraphemes::new(&item).enumerate() {
+                let matched = positions.contains(&i);
+                self.plane
+                    .set_channels(if matched { highlight } else { channels });
+                self.plane
+                    .set_styles(if matched { NcStyle::Bold } else { NcStyle::None });
+                let next = self.put(y, x, egc, dim_x)?;
+                if next == x && NcTextLayout::strwidth(egc) > 0 {
+                    break;
+                }
+                x = next;
+            }
+            self.plane.set_styles(NcStyle::None);
+        }
+        Ok(())
+    }
+
+    /// Fills the row `y` with blanks.
+    fn fill_row(&mut self, y: u32, channels: NcChannels) -> NcResult<()> {
+        self.plane.set_channels(channels);
+        for x in 0..self.plane.dim_x() {
+            self.plane.putstr_yx(Some(y), Some(x), " ")?;
+        }
+        Ok(())
+    }
+
+    /// Writes `string` at `y`, `x` if it fits before `max_x`,
+    /// returning the column after it.
+    fn put(&mut self, y: u32, x: u32, string: &str, max_x: u32) -> NcResult<u32> {
+        let width = cols(string);
+        if x + width > max_x {
+            return Ok(x);
+        }
+        if width > 0 {
+            self.plane.putstr_yx(Some(y), Some(x), string)?;
+        }
+        Ok(x + width)
+    }
+
+    /// Writes the cursor over `egc` on the query line.
+    fn put_cursor(&mut self, x: u32, egc: &str, max_x: u32) -> NcResult<u32> {
+        let mut channels = self.channels;
+        channels.reverse();
+        self.plane.set_channels(channels);
+        self.plane.set_styles(NcStyle::Underline);
+        let next = self.put(0, x, egc, max_x);
+        self.plane.set_channels(self.channels);
+        self.plane.set_styles(NcStyle::None);
+        next
+    }
+}
+
+/// Returns the number of columns of `string`.
+fn cols(string: &str) -> u32 {
+    NcGraphemes::new(string).map(NcTextLayout::strwidth).sum()
+}
+
+/// Returns the graphemes of `string` with their byte offsets.
+fn grapheme_offsets(string: &str) -> Vec<(usize, &str)> {
+    let mut offset = 0;
+    NcGraphemes::new(string)
+        .map(|egc| {
+            let o = offset;
+            offset += egc.len();
+            (o, egc)
+        })
+        .collect()
+}
+
+/// *(No equivalent C style functions)*
+impl<'a> NcWidget for NcPicker<'a> {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        Ok(self.plane)
+    }
+    fn offer_input(&mut self, input: &NcInput) -> bool {
+        NcPicker::offer_input(self, input)
+    }
+    fn redraw(&mut self) -> NcResult<()> {
+        NcPicker::redraw(self)
+    }
+    fn destroy(&mut self) -> NcResult<()> {
+        NcPicker::destroy(self)
+    }
+}
@@ -14,6 +14,9 @@
 use crate::c_api::ffi;
 
 mod methods;
+mod progress;
+
+pub use progress::{NcProgress, NcSpinner};
 
 /// Progress bars. They proceed linearly in any of four directions.
 ///
@@ -32,6 +35,9 @@ mod methods;
 /// right (50 > 40), or left with
 /// [NcProgBarOptions::RETROGRADE][NcProgBarOptions#associatedconstant.RETROGRADE].
 ///
+/// See also [`NcProgress`], drawn in Rust, for indeterminate, stacked and
+/// labeled progress.
+///
 /// `type in C: ncprogbar (struct)`
 ///
 pub type NcProgBar = ffi::ncprogbar;
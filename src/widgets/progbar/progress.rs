@@ -0,0 +1,606 @@
+//! `NcProgress` widget.
+
+use core::time::Duration;
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, vec, vec::Vec};
+
+use crate::{
+    c_api::NCRESULT_ERR,
+    math,
+    widgets::{
+        chart::{fit, HBLOCKS},
+        NcWidget,
+    },
+    NcChannels, NcError, NcInput, NcPlane, NcResult, NcStyle, NcTextAlign,
+};
+
+/// The weight of the latest measure in the smoothed rate.
+const RATE_SMOOTHING: f64 = 0.3;
+
+/// The tolerance when checking that the segments don't exceed the total.
+const EPSILON: f64 = 1e-9;
+
+/// The frames of an animation shown with the `{spinner}` placeholder of the
+/// label of an [`NcProgress`].
+///
+/// # Default
+/// *[`NcSpinner::Braille`]*
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum NcSpinner {
+    /// `⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏`
+    #[default]
+    Braille,
+    /// `|/-\`
+    Line,
+    /// `◜◠◝◞◡◟`
+    Arc,
+    /// `▖▘▝▗`
+    Quadrants,
+    /// Custom frames.
+    Custom(Vec<String>),
+}
+
+/// # `NcSpinner` methods
+impl NcSpinner {
+    /// Returns the number of frames.
+    pub fn len(&self) -> usize {
+        match self {
+            NcSpinner::Custom(frames) => frames.len(),
+            _ => self.builtin().len(),
+        }
+    }
+
+    /// Returns true if there are no frames.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the frame at `index`, wrapping around.
+    pub fn frame(&self, index: usize) -> &str {
+        match self {
+            NcSpinner::Custom(frames) if frames.is_empty() => "",
+            NcSpinner::Custom(frames) => &frames[index % frames.len()],
+            _ => {
+                let frames = self.builtin();
+                frames[index % frames.len()]
+            }
+        }
+    }
+
+    fn builtin(&self) -> &'static [&'static str] {
+        match self {
+            NcSpinner::Braille => &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+            NcSpinner::Line => &["|", "/", "-", "\\"],
+            NcSpinner::Arc => &["◜", "◠", "◝", "◞", "◡", "◟"],
+            NcSpinner::Quadrants => &["▖", "▘", "▝", "▗"],
+            NcSpinner::Custom(_) => &[],
+        }
+    }
+}
+
+/// A progress bar drawn in Rust, with the modes missing in [`NcProgBar`].
+///
+/// - **determinate**: filled up to the progress, with eighth blocks.
+/// - **stacked**: several [`segment`]s filled one after the other,
+///   e.g. downloaded, verified and failed, each with its own channels.
+/// - **indeterminate**: a block bouncing from side to side, while the
+///   amount of work isn't known.
+///
+/// It fills the rows of its plane from the left, or from the right if it's
+/// [`retrograde`]. If it has a [`label`] it's shown on the last row, or over
+/// the bar if there's only one.
+///
+/// The animations, the elapsed time and the rate are driven by the time deltas
+/// passed to [`tick`], so they don't depend on a clock.
+///
+/// # Label
+/// The label is a template with the placeholders:
+/// - `{percent}`: the progress, e.g. `42%`.
+/// - `{done}` and `{total}`: the amounts, if a [`total`] is set.
+/// - `{rate}`: the amount per second, or the percentage without a total.
+/// - `{eta}` and `{elapsed}`: the estimated remaining time and the elapsed
+///   time, as `m:ss` or `h:mm:ss`.
+/// - `{spinner}`: the current frame of the [`spinner`].
+///
+/// In indeterminate mode the placeholders of the progress are left empty.
+///
+/// # Example
+/// ```no_run
+/// # use libnotcurses_sys::{*, widgets::*};
+/// # use core::time::Duration;
+/// # fn main() -> NcResult<()> {
+/// # let nc = unsafe { Nc::new()? };
+/// # let stdplane = unsafe { nc.stdplane() };
+/// let plane = NcPlane::new_child_sized(stdplane, 0, 0, 2, 40)?;
+/// let mut progress = NcProgress::new(plane)
+///     .channels(NcChannels::from_rgb(0x00AA00, 0))
+///     .segment(NcChannels::from_rgb(0xAA0000, 0))
+///     .total(2048.0)
+///     .spinner(NcSpinner::Braille)
+///     .label("{spinner} {percent} {done}/{total} {rate} eta {eta}");
+///
+/// let frame = Duration::from_millis(50);
+/// for step in 1..=100 {
+///     progress.set_segment(0, step as f64 * 0.009)?; // completed
+///     progress.set_segment(1, step as f64 * 0.001)?; // failed
+///     progress.tick(frame);
+///     progress.redraw()?;
+///     nc.render()?;
+///     std::thread::sleep(frame);
+/// }
+/// # unsafe { nc.stop()? };
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`NcProgBar`]: crate::widgets::NcProgBar
+/// [`segment`]: NcProgress#method.segment
+/// [`retrograde`]: NcProgress#method.retrograde
+/// [`label`]: NcProgress#method.label
+/// [`tick`]: NcProgress#method.tick
+/// [`total`]: NcProgress#method.total
+/// [`spinner`]: NcProgress#method.spinner
+pub struct NcProgress<'a> {
+    plane: &'a mut NcPlane,
+    /// The progress and channels of each segment.
+    segments: Vec<(f64, NcChannels)>,
+    track_channels: NcChannels,
+    label_channels: NcChannels,
+    retrograde: bool,
+    label: Option<String>,
+    total: Option<f64>,
+    spinner: NcSpinner,
+    spinner_interval: Duration,
+    /// The position of the bouncing block, from `0.0` to `1.0`, or `None`
+    /// if it's determinate.
+    bounce: Option<f64>,
+    bounce_forward: bool,
+    bounce_speed: f64,
+    bounce_cols: u32,
+    elapsed: Duration,
+    /// The progress when the rate was last measured.
+    measured: f64,
+    /// The smoothed progress per second.
+    rate: Option<f64>,
+    frame: usize,
+    frame_elapsed: Duration,
+}
+
+/// # `NcProgress` constructors
+impl<'a> NcProgress<'a> {
+    /// New determinate `NcProgress` over a `plane`, at 0%, without a label.
+    pub fn new(plane: &'a mut NcPlane) -> Self {
+        Self {
+            plane,
+            segments: vec![(0.0, NcChannels::default())],
+            track_channels: NcChannels::default(),
+            label_channels: NcChannels::default(),
+            retrograde: false,
+            label: None,
+            total: None,
+            spinner: NcSpinner::default(),
+            spinner_interval: Duration::from_millis(80),
+            bounce: None,
+            bounce_forward: true,
+            bounce_speed: 0.5,
+            bounce_cols: 0,
+            elapsed: Duration::ZERO,
+            measured: 0.0,
+            rate: None,
+            frame: 0,
+            frame_elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Sets the channels of the first segment.
+    pub fn channels(mut self, channels: impl Into<NcChannels>) -> Self {
+        self.segments[0].1 = channels.into();
+        self
+    }
+
+    /// Adds a segment with `channels`, stacked after the previous ones.
+    pub fn segment(mut self, channels: impl Into<NcChannels>) -> Self {
+        self.segments.push((0.0, channels.into()));
+        self
+    }
+
+    /// Sets the channels of the unfilled part.
+    pub fn track_channels(mut self, channels: impl Into<NcChannels>) -> Self {
+        self.track_channels = channels.into();
+        self
+    }
+
+    /// Sets the channels of the label.
+    pub fn label_channels(mut self, channels: impl Into<NcChannels>) -> Self {
+        self.label_channels = channels.into();
+        self
+    }
+
+    /// Sets whether it fills from the right.
+    pub fn retrograde(mut self, retrograde: bool) -> Self {
+        self.retrograde = retrograde;
+        self
+    }
+
+    /// Sets the label template.
+    pub fn label(mut self, template: &str) -> Self {
+        self.label = Some(template.into());
+        self
+    }
+
+    /// Sets the total amount of work, for the `{done}`, `{total}` and `{rate}`
+    /// placeholders.
+    pub fn total(mut self, total: f64) -> Self {
+        self.total = Some(total);
+        self
+    }
+
+    /// Sets the spinner.
+    pub fn spinner(mut self, spinner: NcSpinner) -> Self {
+        self.spinner = spinner;
+        self
+    }
+
+    /// Sets the time each frame of the spinner is shown.
+    ///
+    /// The default is 80 milliseconds.
+    pub fn spinner_interval(mut self, interval: Duration) -> Self {
+        self.spinner_interval = interval;
+        self
+    }
+
+    /// Sets the bouncing block of the indeterminate mode: its columns, or a
+    /// quarter of the bar if `0`, and its speed, in trips across the bar per
+    /// second.
+    ///
+    /// The default is a quarter of the bar, at `0.5` trips per second.
+    pub fn bounce(mut self, cols: u32, speed: f64) -> Self {
+        self.bounce_cols = cols;
+        self.bounce_speed = speed;
+        self
+    }
+}
+
+/// # `NcProgress` methods
+impl<'a> NcProgress<'a> {
+    /// Returns the progress, from `0.0` to `1.0`, adding all the segments.
+    pub fn progress(&self) -> f64 {
+        self.segments.iter().map(|(p, _)| p).sum()
+    }
+
+    /// Sets the progress of the first segment, from `0.0` to `1.0`, and
+    /// leaves the indeterminate mode.
+    ///
+    /// Returns an error if the progress of all the segments would exceed `1.0`.
+    pub fn set_progress(&mut self, progress: f64) -> NcResult<()> {
+        self.set_segment(0, progress)
+    }
+
+    /// Returns the progress of the segment at `index`.
+    pub fn segment_progress(&self, index: usize) -> Option<f64> {
+        self.segments.get(index).map(|(p, _)| *p)
+    }
+
+    /// Sets the `progress` of the segment at `index`, and leaves the
+    /// indeterminate mode.
+    ///
+    /// Returns an error if there's no such segment, or if the progress of all
+    /// the segments would exceed `1.0`.
+    pub fn set_segment(&mut self, index: usize, progress: f64) -> NcResult<()> {
+        let others: f64 = (self.progress() - self.segment_progress(index).unwrap_or(0.0)).max(0.0);
+        if index >= self.segments.len() || !(0.0..=1.0 + EPSILON - others).contains(&progress) {
+            return Err(NcError::with_msg(
+                NCRESULT_ERR,
+                &format!["NcProgress.set_segment({}, {}): invalid", index, progress],
+            ));
+        }
+        self.segments[index].0 = progress;
+        self.bounce = None;
+        Ok(())
+    }
+
+    /// Returns true if it's in indeterminate mode.
+    pub fn is_indeterminate(&self) -> bool {
+        self.bounce.is_some()
+    }
+
+    /// Enters or leaves the indeterminate mode.
+    pub fn set_indeterminate(&mut self, indeterminate: bool) {
+        match (indeterminate, self.bounce) {
+            (true, None) => {
+                self.bounce = Some(0.0);
+                self.bounce_forward = true;
+            }
+            (false, Some(_)) => self.bounce = None,
+            _ => (),
+        }
+    }
+
+    /// Returns the time elapsed, as the sum of the ticks.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the estimated progress per second.
+    pub fn rate(&self) -> Option<f64> {
+        self.rate
+    }
+
+    /// Returns the estimated time remaining.
+    pub fn eta(&self) -> Option<Duration> {
+        match self.rate {
+            Some(rate) if rate > 0.0 && !self.is_indeterminate() => {
+                let secs = (1.0 - self.progress()).max(0.0) / rate;
+                (secs < u32::MAX as f64).then(|| Duration::from_secs_f64(secs))
+            }
+            _ => None,
+        }
+    }
+
+    /// Advances the time by `delta`, moving the animations and updating the
+    /// rate.
+    pub fn tick(&mut self, delta: Duration) {
+        self.elapsed += delta;
+
+        if !self.spinner_interval.is_zero() {
+            self.frame_elapsed += delta;
+            let frames = self.frame_elapsed.as_nanos() / self.spinner_interval.as_nanos();
+            self.frame = self.frame.wrapping_add(frames as usize);
+            self.frame_elapsed = Duration::from_nanos(
+                (self.frame_elapsed.as_nanos() % self.spinner_interval.as_nanos()) as u64,
+            );
+        }
+
+        let secs = delta.as_secs_f64();
+        if let Some(position) = self.bounce {
+            let (position, forward) =
+                bounce(position, self.bounce_forward, self.bounce_speed * secs);
+            self.bounce = Some(position);
+            self.bounce_forward = forward;
+        } else if secs > 0.0 {
+            let progress = self.progress();
+            let measure = (progress - self.measured) / secs;
+            self.rate = Some(match self.rate {
+                Some(rate) => rate + RATE_SMOOTHING * (measure - rate),
+                None => measure,
+            });
+            self.measured = progress;
+        }
+    }
+
+    /// Returns the label, with its placeholders replaced.
+    pub fn label_text(&self) -> Option<String> {
+        let template = self.label.as_ref()?;
+        let determinate = !self.is_indeterminate();
+        let progress = self.progress();
+        let amount = |fraction: f64| match self.total {
+            Some(total) => format!["{:.0}", fraction * total],
+            None => String::new(),
+        };
+        let rate = match (self.rate, self.total) {
+            (Some(rate), Some(total)) if determinate => format!["{:.1}/s", rate * total],
+            (Some(rate), None) if determinate => format!["{:.1}%/s", rate * 100.0],
+            _ => String::new(),
+        };
+        let eta = match self.eta() {
+            Some(eta) => format_duration(eta),
+            None if determinate => "-:--".into(),
+            None => String::new(),
+        };
+        let percent = if determinate { format!["{:.0}%", progress * 100.0] } else { String::new() };
+        Some(fill_template(
+            template,
+            &[
+                ("percent", &percent),
+                (
+                    "done",
+                    &if determinate { amount(progress) } else { String::new() },
+                ),
+                ("total", &amount(1.0)),
+                ("rate", &rate),
+                ("eta", &eta),
+                ("elapsed", &format_duration(self.elapsed)),
+                ("spinner", self.spinner.frame(self.frame)),
+            ],
+        ))
+    }
+
+    /// Redraws the progress bar.
+    pub fn redraw(&mut self) -> NcResult<()> {
+        let (styles, channels) = (self.plane.styles(), self.plane.channels());
+        let res = self.draw();
+        self.plane.set_styles(styles);
+        self.plane.set_channels(channels);
+        res
+    }
+
+    /// Destroys the progress bar, together with its plane.
+    pub fn destroy(&mut self) -> NcResult<()> {
+        self.plane.destroy()
+    }
+}
+
+/// # `NcProgress` private methods
+impl<'a> NcProgress<'a> {
+    /// Returns the segment and the eighths filled of each column of the bar.
+    fn cells(&self, cols: u32) -> Vec<Option<(usize, usize)>> {
+        if cols == 0 {
+            return Vec::new();
+        }
+        if let Some(position) = self.bounce {
+            let width = if self.bounce_cols == 0 { cols / 4 } else { self.bounce_cols };
+            let width = width.clamp(1, cols);
+            let start = math::round(position * (cols - width) as f64) as u32;
+            return (0..cols)
+                .map(|x| (start..start + width).contains(&x).then_some((0, 8)))
+                .collect();
+        }
+        let mut ends = Vec::with_capacity(self.segments.len());
+        let mut sum = 0.0;
+        for (progress, _) in &self.segments {
+            sum += progress;
+            ends.push(math::round(sum.min(1.0) * cols as f64 * 8.0) as u32);
+        }
+        (0..cols).map(|x| cell(x, &ends)).collect()
+    }
+
+    fn draw(&mut self) -> NcResult<()> {
+        self.plane.erase();
+        let (rows, cols) = self.plane.dim_yx();
+        let label = self.label_text();
+        let bar_rows = if label.is_some() && rows > 1 { rows - 1 } else { rows };
+
+        self.plane.set_styles(NcStyle::None);
+        for (x, cell) in self.cells(cols).into_iter().enumerate() {
+            let x = x as u32;
+            let (egc, channels) = match cell {
+                Some((segment, 8)) => (HBLOCKS[8], self.segments[segment].1),
+                // the partial blocks are only left-aligned
+                Some((segment, eighths)) if !self.retrograde => {
+                    (HBLOCKS[eighths], self.segments[segment].1)
+                }
+                Some((segment, eighths)) if eighths >= 4 => ("▐", self.segments[segment].1),
+                _ => (" ", self.track_channels),
+            };
+            let x = if self.retrograde { cols - 1 - x } else { x };
+            self.plane.set_channels(channels);
+            for y in 0..bar_rows {
+                self.plane.putstr_yx(Some(y), Some(x), egc)?;
+            }
+        }
+
+        let line = label.and_then(|label| fit(&label, cols, NcTextAlign::Center));
+        if let (Some(line), true) = (line, rows > 0) {
+            self.plane.set_channels(self.label_channels);
+            self.plane
+                .putstr_yx(Some(rows - 1), Some(line.x), &line.text)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the segment covering the column `x`, and how many eighths of it,
+/// given the cumulative `ends` of the segments in eighths.
+///
+/// A column shared by two segments goes to the first one.
+fn cell(x: u32, ends: &[u32]) -> Option<(usize, usize)> {
+    let start = x * 8;
+    let segment = ends.iter().position(|&end| end > start)?;
+    Some((segment, (ends[segment] - start).min(8) as usize))
+}
+
+/// Moves a bouncing `position` by `delta`, reflecting it at `0.0` and `1.0`.
+///
+/// Returns the new position and whether it's moving forward.
+fn bounce(position: f64, forward: bool, delta: f64) -> (f64, bool) {
+    // unfold the trip into a cycle of length 2.0
+    let cycle = if forward { position } else { 2.0 - position };
+    let cycle = cycle + delta;
+    let cycle = cycle - math::floor(cycle / 2.0) * 2.0;
+    if cycle <= 1.0 {
+        (cycle, true)
+    } else {
+        (2.0 - cycle, false)
+    }
+}
+
+/// Formats a `duration` as `m:ss`, or `h:mm:ss` from an hour.
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if h > 0 {
+        format!["{}:{:02}:{:02}", h, m, s]
+    } else {
+        format!["{}:{:02}", m, s]
+    }
+}
+
+/// Replaces the `{name}` placeholders in `template` with their `values`.
+///
+/// Unknown placeholders are left as they are.
+fn fill_template(template: &str, values: &[(&str, &str)]) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        text.push_str(&rest[..open]);
+        rest = &rest[open..];
+        let value = rest.find('}').and_then(|close| {
+            let name = &rest[1..close];
+            values
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, v)| (*v, close))
+        });
+        match value {
+            Some((value, close)) => {
+                text.push_str(value);
+                rest = &rest[close + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+/// *(No equivalent C style functions)*
+impl<'a> NcWidget for NcProgress<'a> {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        Ok(self.plane)
+    }
+    fn offer_input(&mut self, _input: &NcInput) -> bool {
+        false
+    }
+    fn redraw(&mut self) -> NcResult<()> {
+        NcProgress::redraw(self)
+    }
+    fn destroy(&mut self) -> NcResult<()> {
+        NcProgress::destroy(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cells() {
+        // 1.5 columns of the first segment, and 1 of the second
+        let ends = [12, 20];
+        assert_eq![cell(0, &ends), Some((0, 8))];
+        assert_eq![cell(1, &ends), Some((0, 4))];
+        assert_eq![cell(2, &ends), Some((1, 4))];
+        assert_eq![cell(3, &ends), None];
+        assert_eq![cell(0, &[]), None];
+    }
+
+    #[test]
+    fn bouncing() {
+        assert_eq![bounce(0.0, true, 0.25), (0.25, true)];
+        assert_eq![bounce(0.75, true, 0.5), (0.75, false)];
+        assert_eq![bounce(0.5, false, 0.75), (0.25, true)];
+        assert_eq![bounce(0.5, true, 2.0), (0.5, true)];
+    }
+
+    #[test]
+    fn labels() {
+        assert_eq![format_duration(Duration::from_secs(75)), "1:15"];
+        assert_eq![format_duration(Duration::from_secs(3725)), "1:02:05"];
+
+        let values = [("percent", "42%"), ("eta", "0:10")];
+        assert_eq![
+            fill_template("{percent} eta {eta}", &values),
+            "42% eta 0:10"
+        ];
+        assert_eq![fill_template("{x} {percent}{", &values), "{x} 42%{"];
+        assert_eq![fill_template("", &values), ""];
+
+        let spinner = NcSpinner::Line;
+        assert_eq![(spinner.len(), spinner.frame(5)), (4, "/")];
+        assert_eq![NcSpinner::Custom(vec![]).frame(3), ""];
+    }
+}
@@ -1,7 +1,12 @@
 //! `NcReader*` methods and associated functions.
 
+use core::ptr::null_mut;
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String};
+
 use super::{NcReader, NcReaderOptions};
-use crate::{c_api::ncreader_create, error_ref_mut, NcPlane, NcResult};
+use crate::{c_api, cstring, error, error_ref_mut, rstring_free, NcInput, NcPlane, NcResult};
 
 /// # `NcReaderOptions` Constructors
 impl NcReaderOptions {
@@ -30,6 +35,50 @@ impl NcReader {
         plane: &mut NcPlane,
         options: &NcReaderOptions,
     ) -> NcResult<&'a mut Self> {
-        error_ref_mut![unsafe { ncreader_create(plane, options) }]
+        error_ref_mut![unsafe { c_api::ncreader_create(plane, options) }]
+    }
+}
+
+/// # `NcReader` methods
+impl NcReader {
+    /// Offers an `input` to the reader.
+    ///
+    /// Returns true if the input was consumed.
+    ///
+    /// *C style function: [ncreader_offer_input()][c_api::ncreader_offer_input].*
+    pub fn offer_input(&mut self, input: impl Into<NcInput>) -> bool {
+        unsafe { c_api::ncreader_offer_input(self, &input.into()) }
+    }
+
+    /// Returns the contents of the reader, with the rows joined.
+    ///
+    /// *C style function: [ncreader_contents()][c_api::ncreader_contents].*
+    pub fn contents(&self) -> String {
+        rstring_free![c_api::ncreader_contents(self)]
+    }
+
+    /// Writes an `egc` at the cursor, as if it was typed.
+    ///
+    /// *C style function: [ncreader_write_egc()][c_api::ncreader_write_egc].*
+    pub fn write_egc(&mut self, egc: &str) -> NcResult<()> {
+        let cs = cstring![egc];
+        error![
+            unsafe { c_api::ncreader_write_egc(self, cs.as_ptr()) },
+            &format!["NcReader.write_egc({:?})", egc]
+        ]
+    }
+
+    /// Erases the contents, and moves the cursor home.
+    ///
+    /// *C style function: [ncreader_clear()][c_api::ncreader_clear].*
+    pub fn clear(&mut self) -> NcResult<()> {
+        error![unsafe { c_api::ncreader_clear(self) }, "NcReader.clear()"]
+    }
+
+    /// Destroys the reader, together with its plane.
+    ///
+    /// *C style function: [ncreader_destroy()][c_api::ncreader_destroy].*
+    pub fn destroy(&mut self) {
+        unsafe { c_api::ncreader_destroy(self, null_mut()) };
     }
 }
@@ -1,14 +1,15 @@
-use core::cmp::min;
-
 #[cfg(not(feature = "std"))]
-use alloc::{vec, vec::Vec};
+use alloc::{format, vec, vec::Vec};
 
 use crate::{
-    widgets::{NcSelector, NcSelectorItem, NcSelectorOptions},
-    NcChannels, NcPlane, NcResult, NcString,
+    c_api,
+    widgets::{NcItemSelector, NcSelectorItem, NcSelectorOptions},
+    NcChannels, NcError, NcPlane, NcResult, NcString,
 };
 
-/// A handy builder for [`NcSelector`].
+/// A handy builder for [`NcSelector`], returning an [`NcItemSelector`].
+///
+/// [`NcSelector`]: crate::widgets::NcSelector
 ///
 #[derive(Default, Debug)]
 pub struct NcSelectorBuilder {
@@ -36,9 +37,10 @@ impl NcSelectorBuilder {
 
     /// Selects the default item
     ///
-    /// It is selected at the start and must be between 0 and itemcount-1.
-    //
-    // TODO: check when finish it's n-1 at max
+    /// It is selected at the start and must be between 0 and itemcount-1,
+    /// otherwise [`finish`] returns an error.
+    ///
+    /// [`finish`]: NcSelectorBuilder#method.finish
     pub fn default_item(mut self, item: u32) -> Self {
         self.default_item = item;
         self
@@ -124,22 +126,34 @@ impl NcSelectorBuilder {
         self
     }
 
-    /// Finishes the builder and returns the `NcSelector`.
-    pub fn finish(self, plane: &mut NcPlane) -> NcResult<&mut NcSelector> {
+    /// Finishes the builder and returns the [`NcItemSelector`].
+    ///
+    /// Returns an error if the default item is out of range,
+    /// or if any option is repeated.
+    pub fn finish<'a>(self, plane: &mut NcPlane) -> NcResult<NcItemSelector<'a>> {
+        if self.default_item as usize >= self.items.len().max(1) {
+            return Err(NcError::with_msg(
+                c_api::NCRESULT_ERR,
+                &format![
+                    "NcSelectorBuilder.finish(): default item {} out of {} items",
+                    self.default_item,
+                    self.items.len()
+                ],
+            ));
+        }
+
         let mut selitems = vec![];
         for (o, d) in self.items.iter() {
             selitems.push(NcSelectorItem::new(o, d));
         }
         selitems.push(NcSelectorItem::new_empty());
 
-        let default_item = min(self.default_item, selitems.len() as u32 - 1);
-
         let options = NcSelectorOptions::with_all_options(
             self.title.as_ref(),
             self.secondary.as_ref(),
             self.footer.as_ref(),
             &selitems,
-            default_item,
+            self.default_item,
             self.max_display,
             self.channels[0],
             self.channels[1],
@@ -148,6 +162,6 @@ impl NcSelectorBuilder {
             self.channels[4],
         );
 
-        NcSelector::new(plane, &options)
+        NcItemSelector::new(plane, &options)
     }
 }
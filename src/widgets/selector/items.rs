@@ -0,0 +1,253 @@
+//! `NcItemSelector`
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{
+    c_api, error_ref_mut,
+    widgets::{NcSelector, NcSelectorOptions, NcWidget},
+    NcError, NcInput, NcPlane, NcResult,
+};
+
+/// An [`NcSelector`] that keeps a copy of its `(option, description)` items,
+/// created with [`new`] or [`NcSelectorBuilder`].
+///
+/// Since the C struct is opaque, the items are tracked here in order to
+/// list them, replace them, insert them at any position, and select them
+/// by their index.
+///
+/// The selector must be destroyed with [`destroy`], which also frees the items.
+///
+/// [`new`]: NcItemSelector#method.new
+/// [`destroy`]: NcItemSelector#method.destroy
+/// [`NcSelectorBuilder`]: crate::widgets::NcSelectorBuilder
+#[derive(Debug)]
+pub struct NcItemSelector<'a> {
+    selector: &'a mut NcSelector,
+    items: Vec<(String, String)>,
+}
+
+/// # `NcItemSelector` constructors
+impl<'a> NcItemSelector<'a> {
+    /// Creates a selector over a `plane`, copying the items in the `options`.
+    ///
+    /// The selector will take care of destroying the plane.
+    ///
+    /// The options must be unique.
+    ///
+    /// *C style function: [ncselector_create()][c_api::ncselector_create].*
+    pub fn new(plane: &mut NcPlane, options: &NcSelectorOptions) -> NcResult<Self> {
+        let mut items = Vec::new();
+        let mut item = options.items;
+        while !item.is_null() && !unsafe { (*item).option }.is_null() {
+            let (option, desc) = unsafe { ((*item).option, (*item).desc) };
+            let desc = if desc.is_null() { "" } else { crate::rstring![desc] };
+            items.push((crate::rstring![option].to_string(), desc.to_string()));
+            item = unsafe { item.add(1) };
+        }
+        check_unique(
+            items.iter().map(|(o, _)| o.as_str()),
+            "NcItemSelector.new()",
+        )?;
+        let selector = NcSelector::new(plane, options)?;
+        Ok(Self { selector, items })
+    }
+}
+
+/// # `NcItemSelector` methods
+impl<'a> NcItemSelector<'a> {
+    /// Returns a mutable reference to the inner [`NcSelector`].
+    ///
+    /// Adding or deleting items through it leaves them out of sync.
+    pub fn selector(&mut self) -> &mut NcSelector {
+        self.selector
+    }
+
+    /// Offers an input to the selector.
+    ///
+    /// See [`NcSelector.offer_input`][NcSelector#method.offer_input].
+    pub fn offer_input(&mut self, input: impl Into<NcInput>) -> bool {
+        self.selector.offer_input(input)
+    }
+
+    /// Destroys the selector and its plane, and frees the items.
+    ///
+    /// *C style function: [ncselector_destroy()][c_api::ncselector_destroy].*
+    pub fn destroy(&mut self) -> NcResult<()> {
+        self.items = Vec::new();
+        self.selector.destroy()
+    }
+
+    /// Returns the `(option, description)` items.
+    pub fn items(&self) -> &[(String, String)] {
+        &self.items
+    }
+
+    /// Adds an item at the end.
+    ///
+    /// The `option` must be unique among the items.
+    ///
+    /// *C style function: [ncselector_additem()][c_api::ncselector_additem].*
+    pub fn additem(&mut self, option: &str, desc: &str) -> NcResult<()> {
+        self.check_new_option(option, "NcItemSelector.additem()")?;
+        self.selector.additem(option, desc)?;
+        self.items.push((option.to_string(), desc.to_string()));
+        Ok(())
+    }
+
+    /// Deletes the item with the `option`.
+    ///
+    /// *C style function: [ncselector_delitem()][c_api::ncselector_delitem].*
+    pub fn delitem(&mut self, option: &str) -> NcResult<()> {
+        self.selector.delitem(option)?;
+        self.items.retain(|(o, _)| o != option);
+        Ok(())
+    }
+
+    /// Replaces all the items, selecting the first one.
+    ///
+    /// The options must be unique.
+    pub fn set_items(&mut self, items: Vec<(String, String)>) -> NcResult<()> {
+        check_unique(
+            items.iter().map(|(o, _)| o.as_str()),
+            "NcItemSelector.set_items()",
+        )?;
+        for (option, _) in core::mem::take(&mut self.items) {
+            self.selector.delitem(&option)?;
+        }
+        for (option, desc) in items.into_iter() {
+            self.selector.additem(&option, &desc)?;
+            self.items.push((option, desc));
+        }
+        Ok(())
+    }
+
+    /// Inserts an item at `index`, keeping the selected item.
+    ///
+    /// The `option` must be unique among the items.
+    pub fn insert_at(&mut self, index: usize, option: &str, desc: &str) -> NcResult<()> {
+        let len = self.items.len();
+        if index > len {
+            return Err(NcError::with_msg(
+                c_api::NCRESULT_ERR,
+                &format!["NcItemSelector.insert_at(): index {} > len {}", index, len],
+            ));
+        }
+        self.check_new_option(option, "NcItemSelector.insert_at()")?;
+        let selected = self.selected_index();
+
+        // the C library only appends, so the tail is removed and added again
+        let tail: Vec<(String, String)> = self.items.drain(index..).collect();
+        for (o, _) in tail.iter() {
+            self.selector.delitem(o)?;
+        }
+        self.selector.additem(option, desc)?;
+        self.items.push((option.to_string(), desc.to_string()));
+        for (o, d) in tail.into_iter() {
+            self.selector.additem(&o, &d)?;
+            self.items.push((o, d));
+        }
+
+        match selected {
+            Some(s) if s >= index => self.select(s + 1),
+            Some(s) => self.select(s),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the selected option if there is one.
+    ///
+    /// *C style function: [ncselector_selected()][c_api::ncselector_selected].*
+    pub fn selected(&mut self) -> Option<String> {
+        self.selector.selected()
+    }
+
+    /// Returns the index of the selected item, if there's any.
+    pub fn selected_index(&mut self) -> Option<usize> {
+        let option = self.selector.selected()?;
+        self.items.iter().position(|(o, _)| *o == option)
+    }
+
+    /// Selects the item at `index`.
+    pub fn select(&mut self, index: usize) -> NcResult<()> {
+        let len = self.items.len();
+        let Some(current) = self.selected_index().filter(|_| index < len) else {
+            return Err(NcError::with_msg(
+                c_api::NCRESULT_ERR,
+                &format![
+                    "NcItemSelector.select(): index {} out of {} items",
+                    index, len
+                ],
+            ));
+        };
+        // the selection wraps around, so it moves the shortest way
+        let forward = (index + len - current) % len;
+        if forward <= len - forward {
+            for _ in 0..forward {
+                self.selector.nextitem()?;
+            }
+        } else {
+            for _ in 0..len - forward {
+                self.selector.previtem()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns an error if the `option` is already among the items.
+    fn check_new_option(&self, option: &str, caller: &str) -> NcResult<()> {
+        let options = self.items.iter().map(|(o, _)| o.as_str());
+        check_unique(options.chain(core::iter::once(option)), caller)
+    }
+}
+
+/// *C style functions: [ncselector_plane()][c_api::ncselector_plane],
+/// [ncselector_offer_input()][c_api::ncselector_offer_input],
+/// [ncselector_destroy()][c_api::ncselector_destroy].*
+impl<'a> NcWidget for NcItemSelector<'a> {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![
+            unsafe { c_api::ncselector_plane(self.selector) },
+            "NcItemSelector.plane()"
+        ]
+    }
+    fn offer_input(&mut self, input: &NcInput) -> bool {
+        NcItemSelector::offer_input(self, *input)
+    }
+    /// Does nothing, the selector redraws itself on every change.
+    fn redraw(&mut self) -> NcResult<()> {
+        Ok(())
+    }
+    fn destroy(&mut self) -> NcResult<()> {
+        NcItemSelector::destroy(self)
+    }
+}
+
+/// Returns an error if any of the `options` is repeated.
+fn check_unique<'a>(options: impl Iterator<Item = &'a str> + Clone, caller: &str) -> NcResult<()> {
+    for (i, option) in options.clone().enumerate() {
+        if options.clone().skip(i + 1).any(|o| o == option) {
+            return Err(NcError::with_msg(
+                c_api::NCRESULT_ERR,
+                &format!["{}: duplicated option {:?}", caller, option],
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::check_unique;
+
+    #[test]
+    fn unique_options() {
+        assert![check_unique(["a", "b", "c"].into_iter(), "test").is_ok()];
+        assert![check_unique(core::iter::empty(), "test").is_ok()];
+        assert![check_unique(["a", "b", "a"].into_iter(), "test").is_err()];
+    }
+}
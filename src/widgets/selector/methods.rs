@@ -15,7 +15,12 @@ use crate::{
 impl NcSelector {
     /// Creates a selector over a `plane`.
     ///
-    /// The selector will take care of destroying the plane.
+    /// The selector will take care of destroying the plane, and the items are
+    /// copied, so the strings in the `options` can be dropped afterwards.
+    ///
+    /// See also [`NcItemSelector`], which keeps track of the items.
+    ///
+    /// [`NcItemSelector`]: crate::widgets::NcItemSelector
     pub fn new<'a>(plane: &mut NcPlane, options: &NcSelectorOptions) -> NcResult<&'a mut Self> {
         error_ref_mut![
             unsafe { c_api::ncselector_create(plane, options) },
@@ -53,24 +58,26 @@ impl NcSelector {
         Ok(())
     }
 
-    /// Adds an item.
-    //
-    // CHECK whether this works for multiple items.
-    pub fn additem(&mut self, item: NcSelectorItem) -> NcResult<i32> {
+    /// Adds an item at the end.
+    ///
+    /// *C style function: [ncselector_additem()][c_api::ncselector_additem].*
+    pub fn additem(&mut self, option: &str, desc: &str) -> NcResult<()> {
+        let (option, desc) = (NcString::new(option), NcString::new(desc));
+        let item = NcSelectorItem::new(&option, &desc);
         error![
             unsafe { c_api::ncselector_additem(self, &item) },
-            "Calling selector.additem", -1
+            "NcSelector.additem()"
         ]
     }
 
-    /// Deletes an item.
+    /// Deletes the item with the `option`.
     ///
     /// *C style function: [ncselector_delitem()][c_api::ncselector_delitem].*
-    pub fn delitem(&mut self, item: &str) -> NcResult<i32> {
-        let cs = cstring![item];
+    pub fn delitem(&mut self, option: &str) -> NcResult<()> {
+        let cs = cstring![option];
         error![
             unsafe { c_api::ncselector_delitem(self, cs.as_ptr()) },
-            "Calling selector.delitem", -1
+            "NcSelector.delitem()"
         ]
     }
 
@@ -109,7 +116,10 @@ impl NcSelector {
 }
 
 impl NcSelectorItem {
-    /// New item
+    /// New item, pointing to the `option` and `desc` strings.
+    ///
+    /// The strings must outlive the [`NcSelectorOptions`] using this item.
+    /// They are copied when the [`NcSelector`] is created.
     pub fn new(option: &NcString, desc: &NcString) -> Self {
         Self { option: option.as_ptr(), desc: desc.as_ptr() }
     }
@@ -20,9 +20,11 @@
 use crate::c_api::ffi;
 
 mod builder;
+mod items;
 mod methods;
 
 pub use builder::NcSelectorBuilder;
+pub use items::NcItemSelector;
 
 /// High-level widget for selecting one item from a set.
 pub type NcSelector = ffi::ncselector;
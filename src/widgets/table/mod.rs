@@ -0,0 +1,492 @@
+//! `NcTable` widget.
+//!
+//! ```txt
+//!  Name ▲       Size  Modified
+//!  Cargo.toml   1.2K  2022-01-10
+//!  README.md    4.0K  2022-01-12
+//!  src             -  2022-01-12
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, vec::Vec};
+
+use crate::{
+    plane::layout::distribute, widgets::NcWidget, NcAlign, NcChannels, NcConstraint, NcGraphemes,
+    NcInput, NcInputType, NcKey, NcPlane, NcReceived, NcResult, NcStyle, NcTextAlign, NcTextLayout,
+    NcTextWrap,
+};
+
+mod model;
+use model::sort_order;
+pub use model::{NcTableColumn, NcTableModel};
+
+/// The number of blank columns between two table columns.
+const GAP: u32 = 1;
+
+/// The number of columns scrolled horizontally with `Left` and `Right`.
+const SCROLL_X: u32 = 4;
+
+/// A table over a plane, with a header row and a row for each row of an
+/// [`NcTableModel`].
+///
+/// The width of the columns is shared according to their [`NcConstraint`]s.
+/// When the fixed and minimum widths don't fit in the plane, the table can be
+/// scrolled horizontally.
+///
+/// # Input
+/// - `Up`, `Down`, `PgUp`, `PgDown`, `Home`, `End` and the mouse wheel move
+///   the selection, scrolling vertically.
+/// - `Left` and `Right` scroll horizontally.
+/// - `1` to `9` sort by that column, reversing the order if it's already
+///   sorted by it, and so does clicking a column title.
+/// - clicking a row selects it.
+///
+/// # Example
+/// ```no_run
+/// # use libnotcurses_sys::{*, widgets::*};
+/// # fn main() -> NcResult<()> {
+/// # let nc = unsafe { Nc::new()? };
+/// # let stdplane = unsafe { nc.stdplane() };
+/// let files = vec![["Cargo.toml", "1.2K"], ["README.md", "4.0K"], ["src", "-"]];
+/// let plane = NcPlane::new_child_sized(stdplane, 0, 0, 10, 30)?;
+/// let mut table = NcTable::new(
+///     plane,
+///     files,
+///     vec![
+///         NcTableColumn::new("Name"),
+///         NcTableColumn::new("Size").width(NcConstraint::Fixed(6)).align(NcAlign::Right),
+///     ],
+/// );
+/// table.sort(0, true);
+/// table.redraw()?;
+/// nc.render()?;
+/// # unsafe { nc.stop()? };
+/// # Ok(())
+/// # }
+/// ```
+pub struct NcTable<'a, M: NcTableModel> {
+    plane: &'a mut NcPlane,
+    model: M,
+    columns: Vec<NcTableColumn>,
+    /// The model rows in view order.
+    order: Vec<usize>,
+    /// The sorting column, and whether it's ascending.
+    sort: Option<(usize, bool)>,
+    /// The selected position in the view order.
+    selected: usize,
+    /// The first position shown.
+    top: usize,
+    /// The horizontal scroll.
+    left: u32,
+    header_channels: NcChannels,
+    row_channels: [NcChannels; 2],
+    selected_channels: NcChannels,
+}
+
+/// # `NcTable` constructors
+impl<'a, M: NcTableModel> NcTable<'a, M> {
+    /// New `NcTable` over a `plane`, showing the rows of the `model`.
+    ///
+    /// The table uses the whole plane, the first row for the header.
+    pub fn new(plane: &'a mut NcPlane, model: M, columns: Vec<NcTableColumn>) -> Self {
+        let order = sort_order(&model, None);
+        let mut selected_channels = NcChannels::default();
+        selected_channels.reverse();
+        Self {
+            plane,
+            model,
+            columns,
+            order,
+            sort: None,
+            selected: 0,
+            top: 0,
+            left: 0,
+            header_channels: NcChannels::default(),
+            row_channels: [NcChannels::default(); 2],
+            selected_channels,
+        }
+    }
+
+    /// Sets the channels of the header.
+    pub fn header_channels(mut self, channels: impl Into<NcChannels>) -> Self {
+        self.header_channels = channels.into();
+        self
+    }
+
+    /// Sets the channels of the even and odd rows.
+    pub fn row_channels(mut self, even: impl Into<NcChannels>, odd: impl Into<NcChannels>) -> Self {
+        self.row_channels = [even.into(), odd.into()];
+        self
+    }
+
+    /// Sets the channels of the selected row.
+    pub fn selected_channels(mut self, channels: impl Into<NcChannels>) -> Self {
+        self.selected_channels = channels.into();
+        self
+    }
+}
+
+/// # `NcTable` methods
+impl<'a, M: NcTableModel> NcTable<'a, M> {
+    /// Returns a reference to the model.
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+
+    /// Returns a mutable reference to the model.
+    ///
+    /// Call [`refresh`] after changing it.
+    ///
+    /// [`refresh`]: NcTable#method.refresh
+    pub fn model_mut(&mut self) -> &mut M {
+        &mut self.model
+    }
+
+    /// Returns the columns.
+    pub fn columns(&self) -> &[NcTableColumn] {
+        &self.columns
+    }
+
+    /// Sorts the rows again, after the model has changed, keeping the
+    /// selected row if it still exists.
+    pub fn refresh(&mut self) {
+        let selected = self.selected();
+        self.order = sort_order(&self.model, self.sort);
+        self.select_position(
+            selected
+                .and_then(|row| self.position(row))
+                .unwrap_or(self.selected),
+        );
+    }
+
+    /// Returns the sorting column, and whether it's ascending.
+    pub fn sorting(&self) -> Option<(usize, bool)> {
+        self.sort
+    }
+
+    /// Sorts the rows by `column`, keeping the selected row.
+    ///
+    /// Does nothing if the column doesn't exist.
+    pub fn sort(&mut self, column: usize, ascending: bool) {
+        if column < self.columns.len() {
+            self.sort = Some((column, ascending));
+            self.refresh();
+        }
+    }
+
+    /// Returns the rows to their model order.
+    pub fn unsort(&mut self) {
+        self.sort = None;
+        self.refresh();
+    }
+
+    /// Returns the model index of the selected row, if there are rows.
+    pub fn selected(&self) -> Option<usize> {
+        self.order.get(self.selected).copied()
+    }
+
+    /// Selects the row with the model index `row`.
+    ///
+    /// Returns false if there's no such row.
+    pub fn select(&mut self, row: usize) -> bool {
+        match self.position(row) {
+            Some(position) => {
+                self.select_position(position);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the horizontal scroll, in columns.
+    pub fn scroll_x(&self) -> u32 {
+        self.left
+    }
+
+    /// Sets the horizontal scroll, limited to the width of the table.
+    pub fn set_scroll_x(&mut self, x: u32) {
+        let dim_x = self.plane.dim_x();
+        let width = table_width(&self.columns, dim_x);
+        self.left = x.min(width.saturating_sub(dim_x));
+    }
+
+    /// Offers the `input` to the table, redrawing it if it's consumed.
+    ///
+    /// Returns true if the input was relevant.
+    pub fn offer_input(&mut self, input: &NcInput) -> bool {
+        let consumed = if NcKey(input.id).is_mouse() {
+            self.offer_mouse(input)
+        } else if input.evtype == NcInputType::Release as u32 {
+            false
+        } else {
+            self.offer_key(input)
+        };
+        if consumed {
+            let _ = self.redraw();
+        }
+        consumed
+    }
+
+    /// Redraws the table in its entirety.
+    pub fn redraw(&mut self) -> NcResult<()> {
+        let (styles, channels) = (self.plane.styles(), self.plane.channels());
+        let res = self.draw();
+        self.plane.set_styles(styles);
+        self.plane.set_channels(channels);
+        res
+    }
+
+    /// Destroys the table, together with its plane.
+    pub fn destroy(&mut self) -> NcResult<()> {
+        self.plane.destroy()
+    }
+}
+
+/// # `NcTable` private methods
+impl<'a, M: NcTableModel> NcTable<'a, M> {
+    /// Returns the position in the view order of the model `row`.
+    fn position(&self, row: usize) -> Option<usize> {
+        self.order.iter().position(|&r| r == row)
+    }
+
+    fn select_position(&mut self, position: usize) {
+        self.selected = position.min(self.order.len().saturating_sub(1));
+    }
+
+    /// Sorts by `column`, reversing the order if it's already sorted by it.
+    fn toggle_sort(&mut self, column: usize) {
+        match self.sort {
+            Some((c, ascending)) if c == column => self.sort(column, !ascending),
+            _ => self.sort(column, true),
+        }
+    }
+
+    fn offer_key(&mut self, input: &NcInput) -> bool {
+        let page = (self.plane.dim_y() as usize).saturating_sub(1).max(1);
+        match NcReceived::from(input) {
+            NcReceived::Key(NcKey::Up) => self.select_position(self.selected.saturating_sub(1)),
+            NcReceived::Key(NcKey::Down) => self.select_position(self.selected + 1),
+            NcReceived::Key(NcKey::PgUp) => {
+                self.select_position(self.selected.saturating_sub(page))
+            }
+            NcReceived::Key(NcKey::PgDown) => self.select_position(self.selected + page),
+            NcReceived::Key(NcKey::Home) => self.select_position(0),
+            NcReceived::Key(NcKey::End) => self.select_position(usize::MAX),
+            NcReceived::Key(NcKey::Left) => self.set_scroll_x(self.left.saturating_sub(SCROLL_X)),
+            NcReceived::Key(NcKey::Right) => self.set_scroll_x(self.left + SCROLL_X),
+            NcReceived::Char(c @ '1'..='9') if input.nomod_p() || input.shift_p() => {
+                let column = c as usize - '1' as usize;
+                if column >= self.columns.len() {
+                    return false;
+                }
+                self.toggle_sort(column);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn offer_mouse(&mut self, input: &NcInput) -> bool {
+        let (mut y, mut x) = (input.y, input.x);
+        if !self.plane.translate_abs(&mut y, &mut x) {
+            return false;
+        }
+        let release = input.evtype == NcInputType::Release as u32;
+        match NcKey(input.id) {
+            NcKey::Button4 if !release => self.select_position(self.selected.saturating_sub(1)),
+            NcKey::Button5 if !release => self.select_position(self.selected + 1),
+            NcKey::Button1 if release => (),
+            NcKey::Button1 if y == 0 => {
+                let dim_x = self.plane.dim_x();
+                let x = x as u32 + self.left;
+                let hit = column_spans(&self.columns, dim_x)
+                    .iter()
+                    .position(|&(cx, w)| x >= cx && x < cx + w);
+                match hit {
+                    Some(column) => self.toggle_sort(column),
+                    None => return false,
+                }
+            }
+            NcKey::Button1 => {
+                let position = self.top + y as usize - 1;
+                if position >= self.order.len() {
+                    return false;
+                }
+                self.selected = position;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn draw(&mut self) -> NcResult<()> {
+        let (dim_y, dim_x) = self.plane.dim_yx();
+        let rows = (dim_y as usize).saturating_sub(1);
+        if self.selected < self.top {
+            self.top = self.selected;
+        } else if rows > 0 && self.selected >= self.top + rows {
+            self.top = self.selected + 1 - rows;
+        }
+        self.set_scroll_x(self.left);
+        let spans = column_spans(&self.columns, dim_x);
+        self.plane.erase();
+
+        // the header, with the sorting indicator after the title
+        self.plane.set_styles(NcStyle::Bold);
+        self.fill_row(0, self.header_channels)?;
+        for (column, &span) in spans.iter().enumerate() {
+            let col = &self.columns[column];
+            let title = match self.sort {
+                Some((c, ascending)) if c == column => {
+                    format!["{} {}", col.title, if ascending { "▲" } else { "▼" }]
+                }
+                _ => col.title.clone(),
+            };
+            self.put_cell(0, span, &title, column)?;
+        }
+        self.plane.set_styles(NcStyle::None);
+
+        let visible = rows.min(self.order.len().saturating_sub(self.top));
+        for row in 0..visible {
+            let position = self.top + row;
+            let y = row as u32 + 1;
+            let channels = if position == self.selected {
+                self.selected_channels
+            } else {
+                self.row_channels[position % 2]
+            };
+            self.fill_row(y, channels)?;
+            for (column, &span) in spans.iter().enumerate() {
+                let text = String::from(self.model.cell(self.order[position], column));
+                self.put_cell(y, span, &text, column)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Fills the row `y` with blanks.
+    fn fill_row(&mut self, y: u32, channels: NcChannels) -> NcResult<()> {
+        self.plane.set_channels(channels);
+        for x in 0..self.plane.dim_x() {
+            self.plane.putstr_yx(Some(y), Some(x), " ")?;
+        }
+        Ok(())
+    }
+
+    /// Writes the `text` of a cell in the row `y`, laid out in the column
+    /// `span`, and clipped to the visible part of the table.
+    fn put_cell(
+        &mut self,
+        y: u32,
+        (x, width): (u32, u32),
+        text: &str,
+        column: usize,
+    ) -> NcResult<()> {
+        if width == 0 {
+            return Ok(());
+        }
+        let col = &self.columns[column];
+        let align = match col.align {
+            NcAlign::Center => NcTextAlign::Center,
+            NcAlign::Right => NcTextAlign::Right,
+            _ => NcTextAlign::Left,
+        };
+        let layout = NcTextLayout::new(width)
+            .wrap(NcTextWrap::None)
+            .truncate(col.truncate)
+            .max_lines(Some(1))
+            .align(align);
+        let Some(line) = layout.lines(text).into_iter().next() else {
+            return Ok(());
+        };
+        let (left, right) = (self.left, self.left + self.plane.dim_x());
+        let mut cx = x + line.x;
+        for egc in NcGraphemes::new(&line.text) {
+            let w = NcTextLayout::strwidth(egc);
+            if cx + w > (x + width).min(right) {
+                break;
+            }
+            if cx >= left && w > 0 {
+                self.plane.putstr_yx(Some(y), Some(cx - left), egc)?;
+            }
+            cx += w;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the width of the table: the width of the plane, or the minimum
+/// width of the columns if it's larger.
+fn table_width(columns: &[NcTableColumn], dim_x: u32) -> u32 {
+    let min: u32 = columns
+        .iter()
+        .map(|c| match c.width {
+            NcConstraint::Fixed(w) | NcConstraint::Min(w) => w,
+            _ => 0,
+        })
+        .sum();
+    let gaps = GAP * (columns.len() as u32).saturating_sub(1);
+    dim_x.max(min + gaps)
+}
+
+/// Returns the `(x, width)` of each column in the table.
+fn column_spans(columns: &[NcTableColumn], dim_x: u32) -> Vec<(u32, u32)> {
+    let constraints: Vec<NcConstraint> = columns.iter().map(|c| c.width).collect();
+    let mut x = 0;
+    distribute(table_width(columns, dim_x), GAP, &constraints)
+        .into_iter()
+        .map(|width| {
+            let span = (x, width);
+            x += width + GAP;
+            span
+        })
+        .collect()
+}
+
+/// *(No equivalent C style functions)*
+impl<'a, M: NcTableModel> NcWidget for NcTable<'a, M> {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        Ok(self.plane)
+    }
+    fn offer_input(&mut self, input: &NcInput) -> bool {
+        NcTable::offer_input(self, input)
+    }
+    fn redraw(&mut self) -> NcResult<()> {
+        NcTable::redraw(self)
+    }
+    fn destroy(&mut self) -> NcResult<()> {
+        NcTable::destroy(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    #[test]
+    fn spans() {
+        let columns = vec![
+            NcTableColumn::new("Name"),
+            NcTableColumn::new("Size").width(NcConstraint::Fixed(6)),
+            NcTableColumn::new("Date").width(NcConstraint::Min(10)),
+        ];
+        assert_eq![column_spans(&columns, 40), [(0, 11), (12, 6), (19, 21)]];
+        // too narrow, scrolls horizontally
+        assert_eq![table_width(&columns, 10), 18];
+        assert_eq![column_spans(&columns, 10), [(0, 0), (1, 6), (8, 10)]];
+    }
+
+    #[test]
+    fn sorting() {
+        let model = vec![["b", "2"], ["a", "3"], ["c", "1"], ["a", "0"]];
+        assert_eq![model.cell(1, 0), "a"];
+        assert_eq![model.cell(1, 5), ""];
+        assert_eq![sort_order(&model, None), [0, 1, 2, 3]];
+        assert_eq![sort_order(&model, Some((0, true))), [1, 3, 0, 2]];
+        assert_eq![sort_order(&model, Some((0, false))), [2, 0, 1, 3]];
+        assert_eq![sort_order(&model, Some((1, true))), [3, 2, 0, 1]];
+    }
+}
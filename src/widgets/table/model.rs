@@ -0,0 +1,122 @@
+//! `NcTableModel` & `NcTableColumn`
+
+use core::cmp::Ordering;
+
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::Cow, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+use crate::{NcAlign, NcConstraint, NcTextTruncate};
+
+/// The rows of an [`NcTable`][crate::widgets::NcTable].
+///
+/// It's implemented for vectors of rows of strings, e.g. `Vec<Vec<String>>`
+/// or `Vec<[&str; 3]>`.
+pub trait NcTableModel {
+    /// Returns the number of rows.
+    fn rows(&self) -> usize;
+
+    /// Returns the text of the cell at `row`, `column`.
+    fn cell(&self, row: usize, column: usize) -> Cow<'_, str>;
+
+    /// Compares the rows `a` and `b` by `column`, for sorting.
+    ///
+    /// By default it compares the text of the cells.
+    fn compare(&self, a: usize, b: usize, column: usize) -> Ordering {
+        self.cell(a, column).cmp(&self.cell(b, column))
+    }
+}
+
+impl<S: AsRef<str>> NcTableModel for Vec<Vec<S>> {
+    fn rows(&self) -> usize {
+        self.len()
+    }
+    /// Returns an empty string for the missing cells.
+    fn cell(&self, row: usize, column: usize) -> Cow<'_, str> {
+        cell_of(self.get(row).map(|r| r.as_slice()), column)
+    }
+}
+
+impl<S: AsRef<str>, const N: usize> NcTableModel for Vec<[S; N]> {
+    fn rows(&self) -> usize {
+        self.len()
+    }
+    /// Returns an empty string for the missing cells.
+    fn cell(&self, row: usize, column: usize) -> Cow<'_, str> {
+        cell_of(self.get(row).map(|r| r.as_slice()), column)
+    }
+}
+
+/// Returns the text of the `column` of a `row`, or an empty string.
+fn cell_of<S: AsRef<str>>(row: Option<&[S]>, column: usize) -> Cow<'_, str> {
+    Cow::Borrowed(row.and_then(|r| r.get(column)).map_or("", |c| c.as_ref()))
+}
+
+/// A column of an [`NcTable`][crate::widgets::NcTable].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NcTableColumn {
+    pub(crate) title: String,
+    pub(crate) width: NcConstraint,
+    pub(crate) align: NcAlign,
+    pub(crate) truncate: NcTextTruncate,
+}
+
+/// # `NcTableColumn` constructors
+impl NcTableColumn {
+    /// New `NcTableColumn` with a `title`, that fills the available width,
+    /// aligned to the left and truncated at the end.
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.into(),
+            width: NcConstraint::Fill,
+            align: NcAlign::Left,
+            truncate: NcTextTruncate::Tail,
+        }
+    }
+
+    /// Sets the width constraint.
+    pub fn width(mut self, width: NcConstraint) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets the alignment of the title and the cells.
+    pub fn align(mut self, align: NcAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Sets how the text that doesn't fit is truncated.
+    pub fn truncate(mut self, truncate: NcTextTruncate) -> Self {
+        self.truncate = truncate;
+        self
+    }
+}
+
+/// # `NcTableColumn` methods
+impl NcTableColumn {
+    /// Returns the title.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+}
+
+/// Returns the view order of the rows of the `model`, sorted by `column`.
+pub(crate) fn sort_order<M: NcTableModel + ?Sized>(
+    model: &M,
+    sort: Option<(usize, bool)>,
+) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..model.rows()).collect();
+    if let Some((column, ascending)) = sort {
+        order.sort_by(|&a, &b| {
+            let ordering = model.compare(a, b, column);
+            if ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+    }
+    order
+}
@@ -0,0 +1,258 @@
+//! `NcWidget`
+
+use core::ptr::null_mut;
+
+use crate::{
+    c_api, error_ref_mut,
+    widgets::{NcMenu, NcPlotF64, NcPlotU64, NcProgBar, NcReader, NcSelector, NcTabbed, NcTree},
+    NcInput, NcPlane, NcResult,
+};
+
+/// The common interface of the widgets.
+///
+/// It allows to handle different widgets together, e.g. as
+/// `Vec<&mut dyn NcWidget>` or `Box<dyn NcWidget + '_>`, forwarding the
+/// input to each one until a widget consumes it.
+///
+/// The widgets that don't take input never consume it, and the ones that
+/// redraw themselves on every change do nothing on [`redraw`].
+///
+/// # Example
+/// ```no_run
+/// # use libnotcurses_sys::{*, widgets::*};
+/// /// Offers the input to the widgets until one consumes it, and redraws them.
+/// fn dispatch(widgets: &mut [Box<dyn NcWidget + '_>], input: &NcInput) -> NcResult<bool> {
+///     let consumed = widgets.iter_mut().any(|w| w.offer_input(input));
+///     for widget in widgets.iter_mut() {
+///         widget.redraw()?;
+///     }
+///     Ok(consumed)
+/// }
+///
+/// # fn main() -> NcResult<()> {
+/// # let nc = unsafe { Nc::new()? };
+/// # let stdplane = unsafe { nc.stdplane() };
+/// let plane = NcPlane::new_child_sized(stdplane, 0, 0, 1, 20)?;
+/// let mut widgets: Vec<Box<dyn NcWidget>> = vec![Box::new(NcProgBar::new(plane))];
+/// dispatch(&mut widgets, &NcInput::new_empty())?;
+/// nc.render()?;
+/// for widget in widgets.iter_mut() {
+///     widget.destroy()?;
+/// }
+/// # unsafe { nc.stop()? };
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`redraw`]: NcWidget#tymethod.redraw
+pub trait NcWidget {
+    /// Returns the [`NcPlane`] backing the widget.
+    fn plane(&mut self) -> NcResult<&mut NcPlane>;
+
+    /// Offers the `input` to the widget.
+    ///
+    /// Returns `true` if it's relevant, and then the input ought not be
+    /// processed further, or `false` if it's irrelevant to the widget.
+    fn offer_input(&mut self, input: &NcInput) -> bool;
+
+    /// Redraws the widget in its entirety.
+    fn redraw(&mut self) -> NcResult<()>;
+
+    /// Destroys the widget, together with its plane.
+    ///
+    /// The widget must not be used afterwards.
+    fn destroy(&mut self) -> NcResult<()>;
+
+    /// Called when the widget gains (`true`) or loses (`false`) the focus
+    /// in an [`NcFocusRing`].
+    ///
+    /// Does nothing by default.
+    ///
+    /// [`NcFocusRing`]: crate::widgets::NcFocusRing
+    fn focus_changed(&mut self, focused: bool) {
+        let _ = focused;
+    }
+}
+
+impl<W: NcWidget + ?Sized> NcWidget for &mut W {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        (**self).plane()
+    }
+    fn offer_input(&mut self, input: &NcInput) -> bool {
+        (**self).offer_input(input)
+    }
+    fn redraw(&mut self) -> NcResult<()> {
+        (**self).redraw()
+    }
+    fn destroy(&mut self) -> NcResult<()> {
+        (**self).destroy()
+    }
+    fn focus_changed(&mut self, focused: bool) {
+        (**self).focus_changed(focused)
+    }
+}
+
+/// *C style functions: [ncselector_plane()][c_api::ncselector_plane],
+/// [ncselector_offer_input()][c_api::ncselector_offer_input],
+/// [ncselector_destroy()][c_api::ncselector_destroy].*
+impl NcWidget for NcSelector {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![
+            unsafe { c_api::ncselector_plane(self) },
+            "NcSelector.plane()"
+        ]
+    }
+    fn offer_input(&mut self, input: &NcInput) -> bool {
+        unsafe { c_api::ncselector_offer_input(self, input) }
+    }
+    /// Does nothing, the selector redraws itself on every change.
+    fn redraw(&mut self) -> NcResult<()> {
+        Ok(())
+    }
+    fn destroy(&mut self) -> NcResult<()> {
+        NcSelector::destroy(self)
+    }
+}
+
+/// *C style functions: [ncmenu_plane()][c_api::ncmenu_plane],
+/// [ncmenu_offer_input()][c_api::ncmenu_offer_input],
+/// [ncmenu_destroy()][c_api::ncmenu_destroy].*
+impl NcWidget for NcMenu {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![unsafe { c_api::ncmenu_plane(self) }, "NcMenu.plane()"]
+    }
+    fn offer_input(&mut self, input: &NcInput) -> bool {
+        unsafe { c_api::ncmenu_offer_input(self, input) }
+    }
+    /// Does nothing, the menu redraws itself on every change.
+    fn redraw(&mut self) -> NcResult<()> {
+        Ok(())
+    }
+    fn destroy(&mut self) -> NcResult<()> {
+        NcMenu::destroy(self);
+        Ok(())
+    }
+}
+
+/// *C style functions: [nctree_plane()][c_api::nctree_plane],
+/// [nctree_offer_input()][c_api::nctree_offer_input],
+/// [nctree_redraw()][c_api::nctree_redraw],
+/// [nctree_destroy()][c_api::nctree_destroy].*
+impl NcWidget for NcTree {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![unsafe { c_api::nctree_plane(self) }, "NcTree.plane()"]
+    }
+    fn offer_input(&mut self, input: &NcInput) -> bool {
+        unsafe { c_api::nctree_offer_input(self, input) }
+    }
+    fn redraw(&mut self) -> NcResult<()> {
+        NcTree::redraw(self)
+    }
+    fn destroy(&mut self) -> NcResult<()> {
+        NcTree::destroy(self);
+        Ok(())
+    }
+}
+
+/// *C style functions: [ncprogbar_plane()][c_api::ncprogbar_plane],
+/// [ncprogbar_set_progress()][c_api::ncprogbar_set_progress],
+/// [ncprogbar_destroy()][c_api::ncprogbar_destroy].*
+impl NcWidget for NcProgBar {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![unsafe { c_api::ncprogbar_plane(self) }, "NcProgBar.plane()"]
+    }
+    /// Never consumes the input.
+    fn offer_input(&mut self, _input: &NcInput) -> bool {
+        false
+    }
+    /// Sets the current progress again.
+    fn redraw(&mut self) -> NcResult<()> {
+        self.set_progress(self.progress())
+    }
+    fn destroy(&mut self) -> NcResult<()> {
+        NcProgBar::destroy(self);
+        Ok(())
+    }
+}
+
+/// *C style functions: [ncreader_plane()][c_api::ncreader_plane],
+/// [ncreader_offer_input()][c_api::ncreader_offer_input],
+/// [ncreader_destroy()][c_api::ncreader_destroy].*
+impl NcWidget for NcReader {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![unsafe { c_api::ncreader_plane(self) }, "NcReader.plane()"]
+    }
+    fn offer_input(&mut self, input: &NcInput) -> bool {
+        unsafe { c_api::ncreader_offer_input(self, input) }
+    }
+    /// Does nothing, the reader redraws itself on every change.
+    fn redraw(&mut self) -> NcResult<()> {
+        Ok(())
+    }
+    /// Destroys the reader, discarding its contents.
+    fn destroy(&mut self) -> NcResult<()> {
+        unsafe { c_api::ncreader_destroy(self, null_mut()) };
+        Ok(())
+    }
+}
+
+/// *C style functions: [nctabbed_plane()][c_api::nctabbed_plane],
+/// [nctabbed_redraw()][c_api::nctabbed_redraw],
+/// [nctabbed_destroy()][c_api::nctabbed_destroy].*
+impl NcWidget for NcTabbed {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![unsafe { c_api::nctabbed_plane(self) }, "NcTabbed.plane()"]
+    }
+    /// Never consumes the input.
+    fn offer_input(&mut self, _input: &NcInput) -> bool {
+        false
+    }
+    fn redraw(&mut self) -> NcResult<()> {
+        unsafe { c_api::nctabbed_redraw(self) };
+        Ok(())
+    }
+    fn destroy(&mut self) -> NcResult<()> {
+        unsafe { c_api::nctabbed_destroy(self) };
+        Ok(())
+    }
+}
+
+/// *C style functions: [ncuplot_plane()][c_api::ncuplot_plane],
+/// [ncuplot_destroy()][c_api::ncuplot_destroy].*
+impl NcWidget for NcPlotU64 {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![unsafe { c_api::ncuplot_plane(self) }, "NcPlotU64.plane()"]
+    }
+    /// Never consumes the input.
+    fn offer_input(&mut self, _input: &NcInput) -> bool {
+        false
+    }
+    /// Does nothing, the plot redraws itself with every sample.
+    fn redraw(&mut self) -> NcResult<()> {
+        Ok(())
+    }
+    fn destroy(&mut self) -> NcResult<()> {
+        unsafe { c_api::ncuplot_destroy(self) };
+        Ok(())
+    }
+}
+
+/// *C style functions: [ncdplot_plane()][c_api::ncdplot_plane],
+/// [ncdplot_destroy()][c_api::ncdplot_destroy].*
+impl NcWidget for NcPlotF64 {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![unsafe { c_api::ncdplot_plane(self) }, "NcPlotF64.plane()"]
+    }
+    /// Never consumes the input.
+    fn offer_input(&mut self, _input: &NcInput) -> bool {
+        false
+    }
+    /// Does nothing, the plot redraws itself with every sample.
+    fn redraw(&mut self) -> NcResult<()> {
+        Ok(())
+    }
+    fn destroy(&mut self) -> NcResult<()> {
+        unsafe { c_api::ncdplot_destroy(self) };
+        Ok(())
+    }
+}